@@ -0,0 +1,10 @@
+import java.lang.annotation.*;
+import java.util.List;
+@Retention(RetentionPolicy.RUNTIME) @Target({ElementType.TYPE, ElementType.METHOD, ElementType.FIELD, ElementType.PARAMETER, ElementType.TYPE_USE})
+@interface Tag { String value() default "x"; int[] nums() default {1,2}; Class<?> k() default Object.class; ElementType e() default ElementType.TYPE; Retention nested() default @Retention(RetentionPolicy.CLASS); }
+@interface Invis {}
+@Tag("cls") @Invis
+public class Annotated {
+    @Tag(value="f", nums={3}) List<@Tag String> field;
+    @Tag(k=String.class, e=ElementType.FIELD) void m(@Tag("p") int a, @Invis int b) throws @Tag Exception {}
+}
@@ -0,0 +1,12 @@
+import java.util.*;
+public sealed class Attributes<T extends Comparable<T>> permits Attributes.Sub {
+    static final int K = 5;
+    @Deprecated public List<T> items = new ArrayList<>();
+    public static final class Sub extends Attributes<String> {}
+    record Point(int x, int y) {}
+    public <E extends Exception> void m(T t, int q) throws E, java.io.IOException {
+        Runnable r = () -> System.out.println(t);
+        Object o = new Object() {};
+        for (int i = 0; i < q; i++) { if (i % 2 == 0) continue; r.run(); }
+    }
+}
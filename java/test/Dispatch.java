@@ -0,0 +1,12 @@
+public class Dispatch {
+    static native int answer();
+
+    static synchronized int locked(int a) {
+        return a + 1;
+    }
+
+    public static void main(String[] args) {
+        System.out.println(locked(41));
+        System.out.println(answer());
+    }
+}
@@ -0,0 +1,66 @@
+public class Instructions {
+    private int counter;
+    protected static String name = "instructions";
+    public float ratio = 1.5f;
+
+    public interface Shape { double area(); }
+
+    public static int tableSwitch(int v) {
+        switch (v) {
+            case 1: return 10;
+            case 2: return 20;
+            case 3: return 30;
+            default: return -1;
+        }
+    }
+
+    public static int lookupSwitch(int v) {
+        switch (v) {
+            case -100: return 1;
+            case 7: return 2;
+            case 100000: return 3;
+            default: return 0;
+        }
+    }
+
+    public static long arrays(int n) {
+        int[] a = new int[n];
+        long[][] b = new long[n][2];
+        String[] c = new String[n];
+        a[0] = 300;
+        b[0][1] = 1L << 40;
+        c[0] = "x";
+        return a.length + b[0][1] + c.length;
+    }
+
+    public static double floats(float f, double d) {
+        float g = f * 2.5f - 0.1f;
+        double e = d / 3.0 + g;
+        return e % 1.0;
+    }
+
+    public static int catching(int x) {
+        try {
+            return 10 / x;
+        } catch (ArithmeticException e) {
+            return -1;
+        } finally {
+            name = "done";
+        }
+    }
+
+    public synchronized void wide() {
+        int a0 = 0, a1 = 1, a2 = 2, a3 = 3, a4 = 4, a5 = 5, a6 = 6, a7 = 7, a8 = 8, a9 = 9;
+        long l = 3L;
+        double[] big = new double[300];
+        int last = 0;
+        for (int i = 0; i < 300; i += 1000) { last += i; }
+        counter += last + a0 + a1 + a2 + a3 + a4 + a5 + a6 + a7 + a8 + a9 + (int) l + big.length;
+        Object o = this;
+        if (o instanceof Instructions) { counter++; }
+        Shape s = () -> 2.0;
+        counter -= (int) s.area();
+        counter = (short) (byte) (char) counter;
+        counter = counter > 5 ? 0x12345 : -40000;
+    }
+}
@@ -0,0 +1,4 @@
+public class ModifiedUtf8 {
+    // NUL, a two-byte character and a supplementary character (U+1F600) as a surrogate pair.
+    static final String TEXT = "nul:\0 caf\u00e9 grin:\uD83D\uDE00";
+}
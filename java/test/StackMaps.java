@@ -0,0 +1,10 @@
+public class StackMaps {
+    // More than 63 bytes of code between two frames with the same locals need frame types 251
+    // (same_frame_extended) and 247 (same_locals_1_stack_item_frame_extended).
+    static int extended(int a, int b) {
+        if (a > b) {
+            a = a * 31 + b * 17 + a * 13 + b * 11 + a * 7 + b * 5 + a * 3 + b * 2 + a * 19 + b * 23 + a * 29 + b * 37 + a * 41;
+        }
+        return b + (a > 0 ? a * 31 + b * 17 + a * 13 + b * 11 + a * 7 + b * 5 + a * 3 + b * 2 + a * 19 + b * 23 + a * 29 + b * 37 + a * 41 : b);
+    }
+}
@@ -0,0 +1,13 @@
+public class WideConstants {
+    static final long BIG = 123456789012345L;
+    static final double PI = 3.141592653589793;
+
+    public static void main(String[] args) {
+        long a = 9876543210L;
+        double b = 2.718281828459045;
+        String after = "after wide constants";
+        System.out.println(a);
+        System.out.println(b);
+        System.out.println(after);
+    }
+}
@@ -0,0 +1,134 @@
+public class Arrays {
+    public static void main(String[] args) {
+        // int[] with arraylength, iaload and iastore.
+        int[] squares = new int[5];
+        for (int i = 0; i < squares.length; i++) {
+            squares[i] = i * i;
+        }
+        int sum = 0;
+        for (int i = 0; i < squares.length; i++) {
+            sum += squares[i];
+        }
+        System.out.println(squares.length);
+        System.out.println(sum);
+    }
+}
+
+class PrimitiveArrays {
+    public static void main(String[] args) {
+        // The other primitive component types, starting with their default values.
+        byte[] bytes = new byte[2];
+        bytes[1] = -56;
+        System.out.println(bytes[0]);
+        System.out.println(bytes[1]);
+        char[] chars = new char[2];
+        chars[1] = 'a';
+        System.out.println(chars[1]);
+        short[] shorts = new short[2];
+        shorts[1] = -3;
+        System.out.println(shorts[1]);
+        boolean[] flags = new boolean[2];
+        flags[1] = true;
+        System.out.println(flags[0]);
+        System.out.println(flags[1]);
+        long[] longs = new long[2];
+        longs[1] = longs[0];
+        System.out.println(longs[1]);
+        float[] floats = new float[1];
+        System.out.println(floats[0]);
+        double[] doubles = new double[3];
+        doubles[2] = doubles[0];
+        System.out.println(doubles[2]);
+        System.out.println(doubles.length);
+    }
+}
+
+class ReferenceArrays {
+    public static void main(String[] args) {
+        // Arrays of references and of arrays.
+        String[] words = {"zero", "one"};
+        System.out.println(words[1]);
+        int[][] grid = new int[3][4];
+        grid[2][3] = 7;
+        System.out.println(grid.length);
+        System.out.println(grid[2].length);
+        System.out.println(grid[2][3]);
+        System.out.println(grid[0][3]);
+        int[][] jagged = new int[2][];
+        System.out.println(jagged[1]);
+        jagged[1] = new int[] {0, 1, 4, 9, 16};
+        System.out.println(jagged[1][4]);
+        String[][][] cube = new String[2][3][];
+        System.out.println(cube[1].length);
+        System.out.println(cube[1][2]);
+    }
+}
+
+class CovariantStores {
+    public static void main(String[] args) {
+        // Covariant stores of subclasses, implementations and arrays.
+        Shape[] shapes = new Square[1];
+        shapes[0] = new Square();
+        Named[] named = new Named[1];
+        named[0] = new Square();
+        String[] words = {"zero", "one"};
+        int[][] grid = new int[3][4];
+        Object[] objects = new Object[3];
+        objects[0] = words;
+        objects[1] = grid;
+        objects[2] = "string";
+        Object[] rows = grid;
+        rows[0] = new int[1];
+        Object[][] tables = new String[1][];
+        tables[0] = words;
+        System.out.println(named[0].name());
+    }
+}
+
+class NullArrays {
+    public static void main(String[] args) {
+        // arraylength of null.
+        int[] none = null;
+        System.out.println(none.length);
+    }
+}
+
+interface Named {
+    String name();
+}
+
+class Shape {
+}
+
+class Square extends Shape implements Named {
+    public String name() {
+        return "square";
+    }
+}
+
+class OutOfBounds {
+    public static void main(String[] args) {
+        int[] values = new int[3];
+        values[3] = 1;
+    }
+}
+
+class NegativeSize {
+    public static void main(String[] args) {
+        int[][] values = new int[2][-1];
+    }
+}
+
+class WrongStore {
+    public static void main(String[] args) {
+        Shape[] shapes = new Square[1];
+        shapes[0] = new Shape();
+    }
+}
+
+class WrongArrayStore {
+    public static void main(String[] args) {
+        Object[] rows = new int[1][];
+        rows[0] = new Object[0];
+    }
+}
@@ -0,0 +1,4 @@
+// A and B extend each other. They cannot be compiled together: compile A against a stub
+// `class B {}`, then B against a stub `class A {}`.
+class A extends B {
+}
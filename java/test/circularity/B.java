@@ -0,0 +1,3 @@
+// See A.java.
+class B extends A {
+}
@@ -0,0 +1,7 @@
+public class Main {
+    public static void main(String[] args) {
+        System.out.println(1);
+        new A();
+        System.out.println(2);
+    }
+}
@@ -0,0 +1,17 @@
+import util.MathUtil;
+
+public class Main {
+    public static void main(String[] args) {
+        System.out.println(MathUtil.twice(21));
+        System.out.println(MathUtil.square(MathUtil.twice(3)));
+    }
+
+    /** Never called: Unused.class is deliberately missing, and classes are only loaded when used. */
+    static int unused() {
+        return Unused.VALUE_FROM_NOWHERE();
+    }
+
+    static int readField() {
+        return MathUtil.factor;
+    }
+}
@@ -0,0 +1,17 @@
+package util;
+
+public class MathUtil {
+    public static int factor = 2;
+
+    public static int twice(int value) {
+        return value * factor();
+    }
+
+    public static int square(int value) {
+        return value * value;
+    }
+
+    private static int factor() {
+        return 2;
+    }
+}
@@ -0,0 +1,8 @@
+package util;
+
+/** An older MathUtil without square, to provoke a NoSuchMethodError. */
+public class MathUtil {
+    public static int twice(int value) {
+        return value + value;
+    }
+}
@@ -0,0 +1,58 @@
+#!/usr/bin/env python3
+# Writes Condy.class, which javac cannot produce: a class whose static method `value()` loads a
+# dynamically-computed constant (CONSTANT_Dynamic) bootstrapped by ConstantBootstraps.nullConstant.
+#
+#     public class Condy {
+#         public static Object value() { return <condy value:Ljava/lang/Object;>; }
+#     }
+import struct
+
+def u1(value):
+    return struct.pack(">B", value)
+
+def u2(value):
+    return struct.pack(">H", value)
+
+def u4(value):
+    return struct.pack(">I", value)
+
+def utf8(string):
+    data = string.encode()
+    return u1(1) + u2(len(data)) + data
+
+constant_pool = [
+    utf8("Condy"),                                                      # 1
+    u1(7) + u2(1),                                                      # 2 Class Condy
+    utf8("java/lang/Object"),                                           # 3
+    u1(7) + u2(3),                                                      # 4 Class java/lang/Object
+    utf8("java/lang/invoke/ConstantBootstraps"),                        # 5
+    u1(7) + u2(5),                                                      # 6 Class ConstantBootstraps
+    utf8("nullConstant"),                                               # 7
+    utf8("(Ljava/lang/invoke/MethodHandles$Lookup;Ljava/lang/String;Ljava/lang/Class;)Ljava/lang/Object;"),  # 8
+    u1(12) + u2(7) + u2(8),                                             # 9 NameAndType nullConstant
+    u1(10) + u2(6) + u2(9),                                             # 10 Methodref
+    u1(15) + u1(6) + u2(10),                                            # 11 MethodHandle REF_invokeStatic #10
+    utf8("value"),                                                      # 12
+    utf8("Ljava/lang/Object;"),                                         # 13
+    u1(12) + u2(12) + u2(13),                                           # 14 NameAndType value:Object
+    u1(17) + u2(0) + u2(14),                                            # 15 Dynamic #0:value
+    utf8("()Ljava/lang/Object;"),                                       # 16
+    utf8("Code"),                                                       # 17
+    utf8("BootstrapMethods"),                                           # 18
+]
+
+# ldc #15; areturn
+code = u1(0x12) + u1(15) + u1(0xb0)
+code_attribute = u2(17) + u4(12 + len(code)) + u2(1) + u2(0) + u4(len(code)) + code + u2(0) + u2(0)
+method = u2(0x0009) + u2(12) + u2(16) + u2(1) + code_attribute
+bootstrap_methods = u2(18) + u4(6) + u2(1) + u2(11) + u2(0)
+
+class_file = (u4(0xCAFEBABE) + u2(0) + u2(61)
+    + u2(len(constant_pool) + 1) + b"".join(constant_pool)
+    + u2(0x0021) + u2(2) + u2(4)
+    + u2(0) + u2(0)
+    + u2(1) + method
+    + u2(1) + bootstrap_methods)
+
+with open("Condy.class", "wb") as output:
+    output.write(class_file)
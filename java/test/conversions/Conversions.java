@@ -0,0 +1,74 @@
+/** Prints the name of each conversion followed by its result for every input. */
+public class Conversions {
+    // The low 16 bits of 0x1007f, 0x10080, 0x17fff and 0x18000 are the limits of byte and short.
+    static int[] ints = {0, 1, -1, 127, -128, 32768, 65535, 65536, 0x1007f, 0x10080, 0x17fff, 0x18000, -65537, 16777217,
+        Integer.MAX_VALUE, Integer.MIN_VALUE};
+    static long[] longs = {0L, -1L, 5_000_000_005L, 2_147_483_648L, 9_007_199_254_740_993L, Long.MAX_VALUE, Long.MIN_VALUE};
+    static float[] floats = {0.0f, -0.0f, 1.1f, 1.5f, -1.5f, 2.5f, -2.9f, 3.0e9f, -3.0e9f, 1.0e20f, -1.0e20f,
+        Float.MAX_VALUE, Float.MIN_VALUE, Float.NaN, Float.POSITIVE_INFINITY, Float.NEGATIVE_INFINITY};
+    static double[] doubles = {0.0, -0.0, 0.1, 1.5, -1.5, 2147483647.9, -2147483648.9, 3.0e9, -3.0e9, 1.0e20, -1.0e20,
+        1.0e300, Double.MIN_VALUE, Double.NaN, Double.POSITIVE_INFINITY, Double.NEGATIVE_INFINITY};
+
+    public static void main(String[] args) {
+        System.out.println("i2l");
+        for (int value : ints) {
+            System.out.println((long) value);
+        }
+        System.out.println("i2f");
+        for (int value : ints) {
+            System.out.println((float) value);
+        }
+        System.out.println("i2d");
+        for (int value : ints) {
+            System.out.println((double) value);
+        }
+        System.out.println("i2b");
+        for (int value : ints) {
+            System.out.println((byte) value);
+        }
+        System.out.println("i2c");
+        for (int value : ints) {
+            System.out.println((int) (char) value);
+        }
+        System.out.println("i2s");
+        for (int value : ints) {
+            System.out.println((short) value);
+        }
+        System.out.println("l2i");
+        for (long value : longs) {
+            System.out.println((int) value);
+        }
+        System.out.println("l2f");
+        for (long value : longs) {
+            System.out.println((float) value);
+        }
+        System.out.println("l2d");
+        for (long value : longs) {
+            System.out.println((double) value);
+        }
+        System.out.println("f2i");
+        for (float value : floats) {
+            System.out.println((int) value);
+        }
+        System.out.println("f2l");
+        for (float value : floats) {
+            System.out.println((long) value);
+        }
+        System.out.println("f2d");
+        for (float value : floats) {
+            System.out.println((double) value);
+        }
+        System.out.println("d2i");
+        for (double value : doubles) {
+            System.out.println((int) value);
+        }
+        System.out.println("d2l");
+        for (double value : doubles) {
+            System.out.println((long) value);
+        }
+        System.out.println("d2f");
+        for (double value : doubles) {
+            System.out.println((float) value);
+        }
+    }
+}
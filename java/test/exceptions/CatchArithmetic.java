@@ -0,0 +1,16 @@
+public class CatchArithmetic {
+    public static void main(String[] args) {
+        int zero = 0;
+        try {
+            System.out.println(1 / zero);
+        } catch (ArithmeticException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            System.out.println(1L % zero);
+        } catch (RuntimeException e) {
+            System.out.println(e.getMessage());
+        }
+        System.out.println("done");
+    }
+}
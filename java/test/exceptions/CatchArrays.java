@@ -0,0 +1,27 @@
+public class CatchArrays {
+    public static void main(String[] args) {
+        int[] numbers = new int[2];
+        try {
+            numbers[2] = 1;
+        } catch (ArrayIndexOutOfBoundsException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            numbers = new int[-1];
+        } catch (NegativeArraySizeException e) {
+            System.out.println(e.getMessage());
+        }
+        Object[] strings = new String[1];
+        try {
+            strings[0] = new CatchArrays();
+        } catch (ArrayStoreException e) {
+            System.out.println(e.getMessage());
+        }
+        int[] missing = null;
+        try {
+            System.out.println(missing.length);
+        } catch (NullPointerException e) {
+            System.out.println("null array");
+        }
+    }
+}
@@ -0,0 +1,19 @@
+public class CatchLinkage {
+    public static void main(String[] args) {
+        try {
+            Lib.removed();
+        } catch (NoSuchMethodError e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            Lib.changed();
+        } catch (IncompatibleClassChangeError e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            Lib.removed();
+        } catch (LinkageError e) {
+            System.out.println("linkage");
+        }
+    }
+}
@@ -0,0 +1,19 @@
+public class CatchStackOverflow {
+    public static void main(String[] args) {
+        try {
+            recurse();
+        } catch (StackOverflowError e) {
+            System.out.println("stack overflow");
+        }
+        // The stack is unwound, so there is room for calls again.
+        try {
+            recurse();
+        } catch (StackOverflowError e) {
+            System.out.println("stack overflow");
+        }
+    }
+
+    static void recurse() {
+        recurse();
+    }
+}
@@ -0,0 +1,38 @@
+public class CatchThrown {
+    public static void main(String[] args) {
+        try {
+            fail(3);
+        } catch (Failure e) {
+            System.out.println(e.getMessage());
+            System.out.println(e.depth);
+        }
+        try {
+            fail(1);
+        } catch (IllegalStateException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            throw new IllegalStateException("library");
+        } catch (Exception e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            throw new Failure(null);
+        } catch (Throwable e) {
+            System.out.println(e.getMessage());
+        }
+        Failure failure = null;
+        try {
+            throw failure;
+        } catch (NullPointerException e) {
+            System.out.println("null throwable");
+        }
+    }
+
+    static void fail(int depth) {
+        if (depth == 0) {
+            throw new Failure("deep");
+        }
+        fail(depth - 1);
+    }
+}
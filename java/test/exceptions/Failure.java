@@ -0,0 +1,7 @@
+class Failure extends IllegalStateException {
+    int depth = 7;
+
+    Failure(String message) {
+        super(message);
+    }
+}
@@ -0,0 +1,23 @@
+public class Finally {
+    public static void main(String[] args) {
+        try {
+            try {
+                CatchThrown.fail(0);
+            } finally {
+                System.out.println("finally");
+            }
+        } catch (Failure e) {
+            System.out.println(e.getMessage());
+        }
+        System.out.println(returnFromFinally());
+    }
+
+    @SuppressWarnings("finally")
+    static int returnFromFinally() {
+        try {
+            return 1 / 0;
+        } finally {
+            return 2;
+        }
+    }
+}
@@ -0,0 +1,6 @@
+// The version on the class path. CatchLinkage is compiled against stub/Lib.java, in which
+// removed() exists and changed() is static.
+class Lib {
+    void changed() {
+    }
+}
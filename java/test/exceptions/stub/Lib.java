@@ -0,0 +1,7 @@
+class Lib {
+    static void removed() {
+    }
+
+    static void changed() {
+    }
+}
@@ -0,0 +1,180 @@
+public class Floats {
+    public static void main(String[] args) {
+        float zero = 0.0f;
+        float one = 1.0f;
+        float two = 2.0f;
+        float third = one / 3.0f;
+        float nan = zero / zero;
+        float infinity = one / zero;
+
+        // Constants and arithmetic in single precision.
+        System.out.println(zero);
+        System.out.println(two);
+        System.out.println(third);
+        System.out.println(0.1f + 0.2f);
+        System.out.println(two * 1.0e10f);
+        System.out.println(one - 1.0e-5f);
+        System.out.println(-third);
+        System.out.println(nan);
+        System.out.println(infinity);
+        System.out.println(-infinity);
+        System.out.println(-zero);
+        System.out.println(Float.MAX_VALUE * two);
+        System.out.println(Float.MIN_VALUE / two);
+    }
+}
+
+class FloatRemainders {
+    public static void main(String[] args) {
+        float zero = 0.0f;
+        float one = 1.0f;
+        float two = 2.0f;
+        float infinity = one / zero;
+
+        // frem truncates the quotient and keeps the sign of the dividend.
+        System.out.println(5.5f % two);
+        System.out.println(-5.5f % two);
+        System.out.println(5.5f % -two);
+        System.out.println(one % zero);
+        System.out.println(infinity % two);
+        System.out.println(two % infinity);
+        System.out.println(-zero % one);
+    }
+}
+
+class FloatComparisons {
+    public static void main(String[] args) {
+        float zero = 0.0f;
+        float one = 1.0f;
+        float third = one / 3.0f;
+        float nan = zero / zero;
+
+        // NaN is not equal to itself.
+        if (nan == nan) {
+            System.out.println("equal");
+        } else {
+            System.out.println("unordered");
+        }
+        if (third == third) {
+            System.out.println("equal");
+        }
+        // Both comparisons with NaN are false: < compiles to fcmpg, which pushes 1 for NaN, and
+        // > to fcmpl, which pushes -1.
+        if (one < nan) {
+            System.out.println("less");
+        } else {
+            System.out.println("not less");
+        }
+        if (one > nan) {
+            System.out.println("greater");
+        } else {
+            System.out.println("not greater");
+        }
+        System.out.println(half(3.0f));
+    }
+
+    static float half(float value) {
+        return value / 2.0f;
+    }
+}
+
+class Doubles {
+    public static void main(String[] args) {
+        double zero = 0.0;
+        double one = 1.0;
+        double third = one / 3.0;
+        double nan = zero / zero;
+        double infinity = one / zero;
+
+        // Constants and arithmetic in double precision.
+        System.out.println(zero);
+        System.out.println(one);
+        System.out.println(third);
+        System.out.println(0.1 + 0.2);
+        System.out.println(1.0e10);
+        System.out.println(1.0e-5);
+        System.out.println(0.001);
+        System.out.println(1234567.0);
+        System.out.println(12345678.0);
+        System.out.println(100.0 / 7.0);
+        System.out.println(-third);
+        System.out.println(nan);
+        System.out.println(-infinity);
+        System.out.println(-zero);
+        System.out.println(Double.MAX_VALUE);
+        System.out.println(Double.MIN_VALUE);
+    }
+}
+
+class DoubleRemainders {
+    public static void main(String[] args) {
+        double zero = 0.0;
+        double one = 1.0;
+        double nan = zero / zero;
+        double infinity = one / zero;
+
+        // drem
+        System.out.println(5.5 % 2.0);
+        System.out.println(-5.5 % 2.0);
+        System.out.println(1.0e300 % 3.0);
+        System.out.println(one % zero);
+        System.out.println(nan % one);
+        System.out.println(one % infinity);
+    }
+}
+
+class DoubleComparisons {
+    public static void main(String[] args) {
+        double zero = 0.0;
+        double one = 1.0;
+        double nan = zero / zero;
+
+        // NaN is not equal to itself.
+        if (nan == nan) {
+            System.out.println("equal");
+        } else {
+            System.out.println("unordered");
+        }
+        // Both comparisons with NaN are false: < compiles to dcmpg, which pushes 1 for NaN, and
+        // > to dcmpl, which pushes -1.
+        if (one < nan) {
+            System.out.println("less");
+        } else {
+            System.out.println("not less");
+        }
+        if (one > nan) {
+            System.out.println("greater");
+        } else {
+            System.out.println("not greater");
+        }
+    }
+}
+
+class DoubleValues {
+    static double scale = 2.5;
+
+    public static void main(String[] args) {
+        double one = 1.0;
+        double third = one / 3.0;
+
+        // Arguments, return values, fields and array elements.
+        System.out.println(multiply(3.0, 7, scale));
+        double[] values = new double[2];
+        values[1] = third;
+        System.out.println(values[1]);
+        float[] floats = new float[1];
+        floats[0] = 0.75f;
+        System.out.println(floats[0]);
+        Box box = new Box();
+        box.value = scale;
+        System.out.println(box.value);
+    }
+
+    static double multiply(double a, int ignored, double b) {
+        return a * b;
+    }
+}
+
+class Box {
+    double value;
+}
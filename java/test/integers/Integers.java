@@ -0,0 +1,227 @@
+/**
+ * Prints the name of each instruction followed by its result for every input. The methods named
+ * after a branch instruction return 1 if the branch is taken.
+ */
+public class Integers {
+    static int[] left = {7, -7, 7, -7, 0, Integer.MAX_VALUE, Integer.MIN_VALUE, Integer.MIN_VALUE, 0x12345678};
+    static int[] right = {2, 2, -2, -2, 5, 1, -1, Integer.MIN_VALUE, 0xf0f0f0f0};
+    static int[] values = {Integer.MIN_VALUE, -1, 0, 1, Integer.MAX_VALUE};
+    static int[] shifts = {0, 1, 4, 31, 32, 33, -1};
+
+    public static void main(String[] args) {
+        System.out.println("iadd");
+        for (int i = 0; i < left.length; i++) {
+            System.out.println(left[i] + right[i]);
+        }
+        System.out.println("isub");
+        for (int i = 0; i < left.length; i++) {
+            System.out.println(left[i] - right[i]);
+        }
+        System.out.println("imul");
+        for (int i = 0; i < left.length; i++) {
+            System.out.println(left[i] * right[i]);
+        }
+        System.out.println("idiv");
+        for (int i = 0; i < left.length; i++) {
+            System.out.println(left[i] / right[i]);
+        }
+        System.out.println("irem");
+        for (int i = 0; i < left.length; i++) {
+            System.out.println(left[i] % right[i]);
+        }
+        System.out.println("iand");
+        for (int i = 0; i < left.length; i++) {
+            System.out.println(left[i] & right[i]);
+        }
+        System.out.println("ior");
+        for (int i = 0; i < left.length; i++) {
+            System.out.println(left[i] | right[i]);
+        }
+        System.out.println("ixor");
+        for (int i = 0; i < left.length; i++) {
+            System.out.println(left[i] ^ right[i]);
+        }
+        System.out.println("ineg");
+        for (int value : values) {
+            System.out.println(-value);
+        }
+        System.out.println("ishl");
+        for (int shift : shifts) {
+            System.out.println(-17 << shift);
+        }
+        System.out.println("ishr");
+        for (int shift : shifts) {
+            System.out.println(-17 >> shift);
+        }
+        System.out.println("iushr");
+        for (int shift : shifts) {
+            System.out.println(-17 >>> shift);
+        }
+        System.out.println("sipush");
+        System.out.println(128);
+        System.out.println(-129);
+        System.out.println(32767);
+        System.out.println(-32768);
+        System.out.println("iinc");
+        int counter = Integer.MAX_VALUE;
+        counter++;
+        System.out.println(counter);
+        counter += -128;
+        System.out.println(counter);
+        counter += 1000;
+        System.out.println(counter);
+
+        System.out.println("ifeq");
+        for (int value : values) {
+            System.out.println(ifeq(value));
+        }
+        System.out.println("ifne");
+        for (int value : values) {
+            System.out.println(ifne(value));
+        }
+        System.out.println("iflt");
+        for (int value : values) {
+            System.out.println(iflt(value));
+        }
+        System.out.println("ifge");
+        for (int value : values) {
+            System.out.println(ifge(value));
+        }
+        System.out.println("ifgt");
+        for (int value : values) {
+            System.out.println(ifgt(value));
+        }
+        System.out.println("ifle");
+        for (int value : values) {
+            System.out.println(ifle(value));
+        }
+        System.out.println("if_icmpeq");
+        for (int i = 0; i < left.length; i++) {
+            System.out.println(if_icmpeq(left[i], right[i]));
+        }
+        System.out.println(if_icmpeq(3, 3));
+        System.out.println("if_icmpne");
+        for (int i = 0; i < left.length; i++) {
+            System.out.println(if_icmpne(left[i], right[i]));
+        }
+        System.out.println(if_icmpne(3, 3));
+        System.out.println("if_icmplt");
+        for (int i = 0; i < left.length; i++) {
+            System.out.println(if_icmplt(left[i], right[i]));
+        }
+        System.out.println(if_icmplt(3, 3));
+        System.out.println("if_icmpge");
+        for (int i = 0; i < left.length; i++) {
+            System.out.println(if_icmpge(left[i], right[i]));
+        }
+        System.out.println(if_icmpge(3, 3));
+        System.out.println("if_icmpgt");
+        for (int i = 0; i < left.length; i++) {
+            System.out.println(if_icmpgt(left[i], right[i]));
+        }
+        System.out.println(if_icmpgt(3, 3));
+        System.out.println("if_icmple");
+        for (int i = 0; i < left.length; i++) {
+            System.out.println(if_icmple(left[i], right[i]));
+        }
+        System.out.println(if_icmple(3, 3));
+    }
+
+    static int ifeq(int value) {
+        if (value != 0) {
+            return 0;
+        }
+        return 1;
+    }
+
+    static int ifne(int value) {
+        if (value == 0) {
+            return 0;
+        }
+        return 1;
+    }
+
+    static int iflt(int value) {
+        if (value >= 0) {
+            return 0;
+        }
+        return 1;
+    }
+
+    static int ifge(int value) {
+        if (value < 0) {
+            return 0;
+        }
+        return 1;
+    }
+
+    static int ifgt(int value) {
+        if (value <= 0) {
+            return 0;
+        }
+        return 1;
+    }
+
+    static int ifle(int value) {
+        if (value > 0) {
+            return 0;
+        }
+        return 1;
+    }
+
+    static int if_icmpeq(int a, int b) {
+        if (a != b) {
+            return 0;
+        }
+        return 1;
+    }
+
+    static int if_icmpne(int a, int b) {
+        if (a == b) {
+            return 0;
+        }
+        return 1;
+    }
+
+    static int if_icmplt(int a, int b) {
+        if (a >= b) {
+            return 0;
+        }
+        return 1;
+    }
+
+    static int if_icmpge(int a, int b) {
+        if (a < b) {
+            return 0;
+        }
+        return 1;
+    }
+
+    static int if_icmpgt(int a, int b) {
+        if (a <= b) {
+            return 0;
+        }
+        return 1;
+    }
+
+    static int if_icmple(int a, int b) {
+        if (a > b) {
+            return 0;
+        }
+        return 1;
+    }
+}
+
+class DivideByZero {
+    public static void main(String[] args) {
+        int zero = 0;
+        System.out.println(1 / zero);
+    }
+}
+
+class RemainderByZero {
+    public static void main(String[] args) {
+        int zero = 0;
+        System.out.println(1 % zero);
+    }
+}
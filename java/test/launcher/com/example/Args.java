@@ -0,0 +1,11 @@
+package com.example;
+
+public class Args {
+    public static void main(String[] args) {
+        System.out.println(args.length);
+        for (int i = 0; i < args.length; i++) {
+            System.out.println(args[i]);
+        }
+        System.out.println(args[args.length]);
+    }
+}
@@ -0,0 +1,8 @@
+package com.example;
+
+public class Concat {
+    public static void main(String[] args) {
+        System.out.println(1);
+        System.out.println("arguments: " + args.length);
+    }
+}
@@ -0,0 +1,9 @@
+package com.example;
+
+public class Exit {
+    public static void main(String[] args) {
+        System.out.println(7);
+        System.exit(3);
+        System.out.println(8);
+    }
+}
@@ -0,0 +1,11 @@
+package com.example;
+
+public class NoMain {
+    static void main(String[] args) {
+        System.out.println(1);
+    }
+
+    public static void main(int[] args) {
+        System.out.println(2);
+    }
+}
@@ -0,0 +1,12 @@
+package com.example;
+
+public class Recursion {
+    public static void main(String[] args) {
+        depth(1);
+    }
+
+    static int depth(int n) {
+        System.out.println(n);
+        return depth(n + 1);
+    }
+}
@@ -0,0 +1,11 @@
+package com.example;
+
+public class SystemProperties {
+    public static void main(String[] args) {
+        System.out.println(System.getProperty("greeting"));
+        System.out.println(System.getProperty("empty", "default"));
+        System.out.println(System.getProperty("missing"));
+        System.out.println(System.getProperty("missing", "default"));
+        System.out.println(System.getProperty(""));
+    }
+}
@@ -0,0 +1,128 @@
+public class Longs {
+    public static void main(String[] args) {
+        long zero = 0L;
+        long one = 1L;
+        long big = 5_000_000_000L;
+        long max = Long.MAX_VALUE;
+        long min = Long.MIN_VALUE;
+        long minusOne = -1L;
+
+        // Constants, locals beyond index 3, and arithmetic.
+        System.out.println(zero);
+        System.out.println(one);
+        System.out.println(big + big);
+        System.out.println(big - 7_000_000_000L);
+        System.out.println(big * 3L);
+        System.out.println(big / 7L);
+        System.out.println(-big / 7L);
+        System.out.println(big % 7L);
+        System.out.println(-big % 7L);
+        System.out.println(-big);
+    }
+}
+
+class LongOverflow {
+    public static void main(String[] args) {
+        long one = 1L;
+        long max = Long.MAX_VALUE;
+        long min = Long.MIN_VALUE;
+        long minusOne = -1L;
+
+        // Overflow wraps around.
+        System.out.println(max + one);
+        System.out.println(min - one);
+        System.out.println(max * 2L);
+        System.out.println(min / minusOne);
+        System.out.println(min % minusOne);
+        System.out.println(-min);
+    }
+}
+
+class LongShifts {
+    public static void main(String[] args) {
+        long one = 1L;
+        long big = 5_000_000_000L;
+        long min = Long.MIN_VALUE;
+        long minusOne = -1L;
+
+        // Shifts use the lowest six bits of the distance.
+        System.out.println(one << 40);
+        System.out.println(one << 64);
+        System.out.println(minusOne >> 60);
+        System.out.println(minusOne >>> 60);
+        System.out.println(min >> 63);
+        System.out.println(big << -1);
+    }
+}
+
+class LongBitwise {
+    public static void main(String[] args) {
+        long big = 5_000_000_000L;
+        long minusOne = -1L;
+
+        // Bitwise operations.
+        System.out.println(big & 0xFFFFL);
+        System.out.println(big | 0xFL);
+        System.out.println(big ^ minusOne);
+    }
+}
+
+class LongComparison {
+    public static void main(String[] args) {
+        long big = 5_000_000_000L;
+        long max = Long.MAX_VALUE;
+
+        // lcmp.
+        if (big == 5_000_000_000L) {
+            System.out.println("equal");
+        }
+        if (big == max) {
+            System.out.println("equal");
+        } else {
+            System.out.println("different");
+        }
+    }
+}
+
+class LongValues {
+    static long total;
+
+    public static void main(String[] args) {
+        long one = 1L;
+        long big = 5_000_000_000L;
+        long max = Long.MAX_VALUE;
+
+        // Arguments, return values, fields and array elements.
+        System.out.println(add(big, 3, max));
+        total = add(one, 2, one);
+        System.out.println(total);
+        long[] values = new long[2];
+        values[1] = big;
+        System.out.println(values[1]);
+        Counter counter = new Counter();
+        counter.value = big;
+        System.out.println(counter.value);
+    }
+
+    static long add(long a, int ignored, long b) {
+        return a + b;
+    }
+}
+
+class Counter {
+    long value;
+}
+
+class DivideByZero {
+    public static void main(String[] args) {
+        long zero = 0L;
+        System.out.println(1L / zero);
+    }
+}
+
+class RemainderByZero {
+    public static void main(String[] args) {
+        long zero = 0L;
+        System.out.println(1L % zero);
+    }
+}
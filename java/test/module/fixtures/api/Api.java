@@ -0,0 +1,7 @@
+package fixtures.api;
+
+public class Api {
+    public static String greet(String name) {
+        return "Hello, " + name;
+    }
+}
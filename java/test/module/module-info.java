@@ -0,0 +1,4 @@
+module fixtures {
+    requires java.logging;
+    exports fixtures.api;
+}
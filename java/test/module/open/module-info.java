@@ -0,0 +1,8 @@
+// Compiled with `javac --release 17 --module-version 1.2 -d . module-info.java p/q/R.java`.
+open module m.x {
+    requires transitive java.logging;
+    requires static java.sql;
+    exports p.q to java.base, java.sql;
+    uses java.lang.Runnable;
+    provides java.lang.Runnable with p.q.R;
+}
@@ -0,0 +1,5 @@
+package p.q;
+
+public class R implements Runnable {
+    public void run() {}
+}
@@ -0,0 +1,7 @@
+class Counter {
+    int count;
+
+    int increment() {
+        return ++count;
+    }
+}
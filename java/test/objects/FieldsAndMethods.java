@@ -0,0 +1,7 @@
+public class FieldsAndMethods {
+    public static void main(String[] args) {
+        Point p = new Point(3, 4);
+        System.out.println(p.x);
+        System.out.println(p.lengthSquared());
+    }
+}
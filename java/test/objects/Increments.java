@@ -0,0 +1,11 @@
+public class Increments {
+    public static void main(String[] args) {
+        Counter counter = new Counter();
+        counter.count++;
+        int before = counter.count++;
+        System.out.println(before);
+        System.out.println(counter.count);
+        counter.increment();
+        System.out.println(counter.count);
+    }
+}
@@ -0,0 +1,7 @@
+public class Inheritance {
+    public static void main(String[] args) {
+        Point q = new Point3(1, 2, 3);
+        System.out.println(q.lengthSquared());
+        System.out.println(q.y);
+    }
+}
@@ -0,0 +1,7 @@
+public class Interfaces {
+    public static void main(String[] args) {
+        Shape shape = new Square(5);
+        System.out.println(shape.area());
+        System.out.println(shape.sides());
+    }
+}
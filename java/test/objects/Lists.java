@@ -0,0 +1,6 @@
+public class Lists {
+    public static void main(String[] args) {
+        Node list = new Node(1, new Node(2, new Node(3, null)));
+        System.out.println(list.sum());
+    }
+}
@@ -0,0 +1,16 @@
+class Node {
+    int value;
+    Node next;
+
+    Node(int value, Node next) {
+        this.value = value;
+        this.next = next;
+    }
+
+    int sum() {
+        if (next == null) {
+            return value;
+        }
+        return value + next.sum();
+    }
+}
@@ -0,0 +1,6 @@
+public class NullFields {
+    public static void main(String[] args) {
+        Point missing = null;
+        System.out.println(missing.x);
+    }
+}
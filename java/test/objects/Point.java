@@ -0,0 +1,32 @@
+class Point {
+    int x;
+    int y;
+    String name;
+
+    Point(int x, int y) {
+        this.x = x;
+        this.y = y;
+    }
+
+    int lengthSquared() {
+        return square(x) + square(y);
+    }
+
+    private int square(int value) {
+        return value * value;
+    }
+}
+
+class Point3 extends Point {
+    int z;
+
+    Point3(int x, int y, int z) {
+        super(x, y);
+        this.z = z;
+    }
+
+    @Override
+    int lengthSquared() {
+        return super.lengthSquared() + z * z;
+    }
+}
@@ -0,0 +1,18 @@
+public class Printing {
+    public static void main(String[] args) {
+        // PrintStream.println formats its argument by the overload the descriptor names.
+        System.out.println();
+        System.out.println(true);
+        System.out.println(false);
+        System.out.println('a');
+        char[] word = {'h', 'i'};
+        System.out.println(word);
+        Object text = "text";
+        System.out.println(text);
+        String none = null;
+        System.out.println(none);
+        System.out.println((Object) null);
+        // Printing other objects needs their toString method.
+        System.out.println(new Point(3, 4));
+    }
+}
@@ -0,0 +1,13 @@
+public class References {
+    public static void main(String[] args) {
+        Point p = new Point(3, 4);
+        Point q = new Point3(1, 2, 3);
+        Point same = p;
+        System.out.println(same == p ? 1 : 0);
+        System.out.println(q == p ? 1 : 0);
+        System.out.println(p.name);
+        p.name = "origin";
+        System.out.println(p.name);
+        System.out.println(p.name == "origin" ? 1 : 0);
+    }
+}
@@ -0,0 +1,19 @@
+interface Shape {
+    int area();
+
+    default int sides() {
+        return 4;
+    }
+}
+
+class Square implements Shape {
+    private final int side;
+
+    Square(int side) {
+        this.side = side;
+    }
+
+    public int area() {
+        return side * side;
+    }
+}
@@ -0,0 +1,16 @@
+public class Base {
+    public static int add(int a, int b) {
+        return a + b;
+    }
+
+    public static int add(int a) {
+        return a + 1;
+    }
+
+    public static int fac(int n) {
+        if (n < 2) {
+            return 1;
+        }
+        return n * fac(n - 1);
+    }
+}
@@ -0,0 +1,5 @@
+public interface Greeter {
+    default int hello() {
+        return 8;
+    }
+}
@@ -0,0 +1,5 @@
+public class Impl {
+    public static int hello() {
+        return 7;
+    }
+}
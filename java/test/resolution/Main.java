@@ -0,0 +1,9 @@
+public class Main {
+    public static void main(String[] args) {
+        System.out.println(Sub.fac(5));
+        System.out.println(Base.add(2, 3));
+        System.out.println(Base.add(4));
+        System.out.println(Util.one());
+        System.out.println(Impl.hello());
+    }
+}
@@ -0,0 +1,2 @@
+public class Sub extends Base {
+}
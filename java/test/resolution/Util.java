@@ -0,0 +1,5 @@
+public class Util {
+    public static int one() {
+        return 1;
+    }
+}
@@ -0,0 +1,3 @@
+// Replaces Impl.class: hello() is now only inherited as a default method of Greeter.
+public class Impl implements Greeter {
+}
@@ -0,0 +1,6 @@
+// Replaces Util.class with an interface, so Main's Methodref to Util.one() no longer matches.
+public interface Util {
+    static int one() {
+        return 1;
+    }
+}
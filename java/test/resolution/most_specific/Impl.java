@@ -0,0 +1,3 @@
+// Replaces Impl.class: Loud.hello() overrides Greeter.hello(), so it is the maximally-specific method.
+public class Impl implements Greeter, Loud {
+}
@@ -0,0 +1,5 @@
+public interface Loud extends Greeter {
+    default int hello() {
+        return 9;
+    }
+}
@@ -0,0 +1,7 @@
+public class CircularInitialization {
+    public static void main(String[] args) {
+        // A class whose initialization is in progress is used as it is.
+        System.out.println(A.a);
+        System.out.println(B.b);
+    }
+}
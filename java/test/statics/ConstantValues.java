@@ -0,0 +1,10 @@
+public class ConstantValues {
+    public static void main(String[] args) {
+        // Fields with a ConstantValue attribute.
+        System.out.println(Constants.ANSWER);
+        System.out.println(Constants.BIG);
+        System.out.println(Constants.HALF);
+        System.out.println(Constants.THIRD);
+        System.out.println(Constants.NAME);
+    }
+}
@@ -0,0 +1,15 @@
+/**
+ * Fields set from ConstantValue attributes alone. ConstantValues is compiled against a copy of this
+ * class without the initializers and final modifiers, so that it reads them with getstatic instead
+ * of inlining them:
+ *
+ *     javac --release 17 -d . ConstantValues.java    # with the stub Constants.java next to it
+ *     javac --release 17 -d . Constants.java
+ */
+public class Constants {
+    public static final int ANSWER = 42;
+    public static final long BIG = 1L << 40;
+    public static final float HALF = 0.5f;
+    public static final double THIRD = 1.0 / 3;
+    public static final String NAME = "constant";
+}
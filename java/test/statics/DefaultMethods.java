@@ -0,0 +1,7 @@
+public class DefaultMethods {
+    public static void main(String[] args) {
+        // Interfaces declaring default methods are initialized with an implementing class, others
+        // are not.
+        new Impl();
+    }
+}
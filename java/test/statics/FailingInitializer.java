@@ -0,0 +1,6 @@
+public class FailingInitializer {
+    public static void main(String[] args) {
+        // An exception in a static initializer.
+        System.out.println(Broken.value);
+    }
+}
@@ -0,0 +1,7 @@
+public class InitializationTriggers {
+    public static void main(String[] args) {
+        // new and invokestatic initialize the class.
+        new Lazy();
+        System.out.println(Util.twice(4));
+    }
+}
@@ -0,0 +1,115 @@
+public class Statics {
+    static {
+        System.out.println("Statics");
+    }
+
+    static int counter;
+    static String greeting;
+    static long unsetLong;
+    static double unsetDouble;
+    static Statics unsetObject;
+
+    public static void main(String[] args) {
+        // Default values, and fields written and read with putstatic and getstatic.
+        System.out.println(counter);
+        System.out.println(unsetLong);
+        System.out.println(unsetDouble);
+        System.out.println(unsetObject);
+        counter = 5;
+        greeting = "hello";
+        System.out.println(counter + Counter.next() + Counter.next());
+        System.out.println(greeting);
+    }
+}
+
+class Counter {
+    static int count;
+
+    static int next() {
+        return ++count;
+    }
+}
+
+class Parent {
+    static {
+        System.out.println("Parent");
+    }
+
+    static int parentValue = 7;
+}
+
+class Child extends Parent {
+    static {
+        System.out.println("Child");
+    }
+
+    static int value = parentValue * 2;
+}
+
+class OtherChild extends Parent {
+    static {
+        System.out.println("OtherChild");
+    }
+}
+
+class Lazy {
+    static {
+        System.out.println("Lazy");
+    }
+}
+
+class Util {
+    static {
+        System.out.println("Util");
+    }
+
+    static int twice(int value) {
+        return value * 2;
+    }
+}
+
+interface WithDefault {
+    int ID = Log.log("WithDefault");
+
+    default int id() {
+        return ID;
+    }
+}
+
+interface WithoutDefault {
+    int ID = Log.log("WithoutDefault");
+
+    int id();
+}
+
+class Impl implements WithoutDefault, WithDefault {
+    static {
+        System.out.println("Impl");
+    }
+
+    public int id() {
+        return 0;
+    }
+}
+
+class Log {
+    static int log(String message) {
+        System.out.println(message);
+        return 1;
+    }
+}
+
+class A {
+    static int a = B.b + 1;
+}
+
+class B {
+    static int b = A.a + 10;
+}
+
+class Broken {
+    static Broken instance;
+    static int value = instance.field;
+
+    int field;
+}
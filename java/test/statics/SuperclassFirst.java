@@ -0,0 +1,9 @@
+public class SuperclassFirst {
+    public static void main(String[] args) {
+        // The superclass is initialized first, and each class only once.
+        System.out.println(Child.value);
+        System.out.println(Child.value);
+        // A field of the superclass only initializes the superclass.
+        System.out.println(OtherChild.parentValue);
+    }
+}
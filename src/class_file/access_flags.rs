@@ -0,0 +1,170 @@
+//! Access and property flags (JVMS §4.1, §4.5, §4.6, §4.7.6, §4.7.24, §4.7.25). Every context
+//! gets its own type, since the same bit means different things in different places, e.g.
+//! 0x0020 is `ACC_SUPER` on classes, `ACC_SYNCHRONIZED` on methods and `ACC_OPEN` on modules.
+//! `Display` prints the flags like `javap`: `(0x0021) ACC_PUBLIC, ACC_SUPER`.
+
+use std::fmt;
+
+macro_rules! access_flags {
+    ($(#[$doc:meta])* $name:ident { $($predicate:ident: $mask:literal => $flag:literal),* $(,)? }) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+        pub struct $name(u16);
+
+        impl $name {
+            const NAMES: &'static [(u16, &'static str)] = &[$(($mask, $flag)),*];
+
+            pub fn new(bits: u16) -> $name {
+                $name(bits)
+            }
+
+            pub fn get_bits(&self) -> u16 {
+                self.0
+            }
+
+            /// The names of the set flags, e.g. `ACC_PUBLIC`, in the order of the JVMS tables.
+            /// Bits without a meaning in this context are left out.
+            pub fn get_names(&self) -> Vec<&'static str> {
+                Self::NAMES.iter().filter(|(mask, _flag)| self.0 & mask != 0).map(|(_mask, flag)| *flag).collect()
+            }
+
+            $(
+                pub fn $predicate(&self) -> bool {
+                    self.0 & $mask != 0
+                }
+            )*
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "({:#06x}) {}", self.0, self.get_names().join(", "))
+            }
+        }
+    };
+}
+
+access_flags!(
+    /// Flags of a class or interface.
+    ClassAccessFlags {
+        is_public: 0x0001 => "ACC_PUBLIC",
+        is_final: 0x0010 => "ACC_FINAL",
+        is_super: 0x0020 => "ACC_SUPER",
+        is_interface: 0x0200 => "ACC_INTERFACE",
+        is_abstract: 0x0400 => "ACC_ABSTRACT",
+        is_synthetic: 0x1000 => "ACC_SYNTHETIC",
+        is_annotation: 0x2000 => "ACC_ANNOTATION",
+        is_enum: 0x4000 => "ACC_ENUM",
+        is_module: 0x8000 => "ACC_MODULE",
+    }
+);
+
+access_flags!(
+    FieldAccessFlags {
+        is_public: 0x0001 => "ACC_PUBLIC",
+        is_private: 0x0002 => "ACC_PRIVATE",
+        is_protected: 0x0004 => "ACC_PROTECTED",
+        is_static: 0x0008 => "ACC_STATIC",
+        is_final: 0x0010 => "ACC_FINAL",
+        is_volatile: 0x0040 => "ACC_VOLATILE",
+        is_transient: 0x0080 => "ACC_TRANSIENT",
+        is_synthetic: 0x1000 => "ACC_SYNTHETIC",
+        is_enum: 0x4000 => "ACC_ENUM",
+    }
+);
+
+access_flags!(
+    MethodAccessFlags {
+        is_public: 0x0001 => "ACC_PUBLIC",
+        is_private: 0x0002 => "ACC_PRIVATE",
+        is_protected: 0x0004 => "ACC_PROTECTED",
+        is_static: 0x0008 => "ACC_STATIC",
+        is_final: 0x0010 => "ACC_FINAL",
+        is_synchronized: 0x0020 => "ACC_SYNCHRONIZED",
+        is_bridge: 0x0040 => "ACC_BRIDGE",
+        is_varargs: 0x0080 => "ACC_VARARGS",
+        is_native: 0x0100 => "ACC_NATIVE",
+        is_abstract: 0x0400 => "ACC_ABSTRACT",
+        is_strict: 0x0800 => "ACC_STRICT",
+        is_synthetic: 0x1000 => "ACC_SYNTHETIC",
+    }
+);
+
+access_flags!(
+    /// Flags of an entry of the `InnerClasses` attribute, as declared in the source.
+    InnerClassAccessFlags {
+        is_public: 0x0001 => "ACC_PUBLIC",
+        is_private: 0x0002 => "ACC_PRIVATE",
+        is_protected: 0x0004 => "ACC_PROTECTED",
+        is_static: 0x0008 => "ACC_STATIC",
+        is_final: 0x0010 => "ACC_FINAL",
+        is_interface: 0x0200 => "ACC_INTERFACE",
+        is_abstract: 0x0400 => "ACC_ABSTRACT",
+        is_synthetic: 0x1000 => "ACC_SYNTHETIC",
+        is_annotation: 0x2000 => "ACC_ANNOTATION",
+        is_enum: 0x4000 => "ACC_ENUM",
+    }
+);
+
+access_flags!(
+    /// Flags of an entry of the `MethodParameters` attribute.
+    ParameterAccessFlags {
+        is_final: 0x0010 => "ACC_FINAL",
+        is_synthetic: 0x1000 => "ACC_SYNTHETIC",
+        is_mandated: 0x8000 => "ACC_MANDATED",
+    }
+);
+
+access_flags!(
+    /// Flags of a `Module` attribute and of its `exports` and `opens` entries.
+    ModuleAccessFlags {
+        is_open: 0x0020 => "ACC_OPEN",
+        is_synthetic: 0x1000 => "ACC_SYNTHETIC",
+        is_mandated: 0x8000 => "ACC_MANDATED",
+    }
+);
+
+access_flags!(
+    /// Flags of a `requires` entry of a `Module` attribute.
+    RequiresAccessFlags {
+        is_transitive: 0x0020 => "ACC_TRANSITIVE",
+        is_static_phase: 0x0040 => "ACC_STATIC_PHASE",
+        is_synthetic: 0x1000 => "ACC_SYNTHETIC",
+        is_mandated: 0x8000 => "ACC_MANDATED",
+    }
+);
+
+impl FieldAccessFlags {
+    /// The modifiers as written in Java source, e.g. `["public", "static", "final"]`.
+    pub fn get_modifiers(&self) -> Vec<&'static str> {
+        [(self.is_public(), "public"), (self.is_private(), "private"), (self.is_protected(), "protected"),
+            (self.is_static(), "static"), (self.is_final(), "final"), (self.is_volatile(), "volatile"), (self.is_transient(), "transient")]
+            .iter().filter(|(set, _modifier)| *set).map(|(_set, modifier)| *modifier).collect()
+    }
+}
+
+impl MethodAccessFlags {
+    /// The modifiers as written in Java source, e.g. `["public", "static", "native"]`.
+    pub fn get_modifiers(&self) -> Vec<&'static str> {
+        [(self.is_public(), "public"), (self.is_private(), "private"), (self.is_protected(), "protected"),
+            (self.is_static(), "static"), (self.is_final(), "final"), (self.is_synchronized(), "synchronized"),
+            (self.is_native(), "native"), (self.is_abstract(), "abstract")]
+            .iter().filter(|(set, _modifier)| *set).map(|(_set, modifier)| *modifier).collect()
+    }
+}
+
+impl InnerClassAccessFlags {
+    /// The modifiers as written in Java source. `abstract` is implied for interfaces and left out.
+    pub fn get_modifiers(&self) -> Vec<&'static str> {
+        [(self.is_public(), "public"), (self.is_private(), "private"), (self.is_protected(), "protected"),
+            (self.is_static(), "static"), (self.is_final(), "final"), (self.is_abstract() && !self.is_interface(), "abstract")]
+            .iter().filter(|(set, _modifier)| *set).map(|(_set, modifier)| *modifier).collect()
+    }
+}
+
+impl ParameterAccessFlags {
+    /// The flags as `javap` lists them in its `MethodParameters` table, e.g. `["final", "mandated"]`.
+    pub fn get_modifiers(&self) -> Vec<&'static str> {
+        [(self.is_final(), "final"), (self.is_synthetic(), "synthetic"), (self.is_mandated(), "mandated")]
+            .iter().filter(|(set, _modifier)| *set).map(|(_set, modifier)| *modifier).collect()
+    }
+}
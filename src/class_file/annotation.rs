@@ -0,0 +1,284 @@
+use super::file;
+use super::attribute::Attribute;
+use super::attribute_info::AttributeInfo;
+use super::parse_error::{ParseError, ParseErrorKind};
+use super::ClassFile;
+
+/// An annotation (JVMS §4.7.16). `get_type_index` points to the field descriptor of the annotation interface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    type_index: u16,
+    element_value_pairs: Vec<ElementValuePair>,
+}
+
+impl Annotation {
+    pub fn get_type_index(&self) -> u16 {
+        self.type_index
+    }
+
+    pub fn get_element_value_pairs(&self) -> &Vec<ElementValuePair> {
+        &self.element_value_pairs
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementValuePair {
+    element_name_index: u16,
+    value: ElementValue,
+}
+
+impl ElementValuePair {
+    pub fn get_element_name_index(&self) -> u16 {
+        self.element_name_index
+    }
+
+    pub fn get_value(&self) -> &ElementValue {
+        &self.value
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElementValue {
+    /// A primitive or string constant; the tag is one of `B C D F I J S Z s`.
+    Const { tag: char, const_value_index: u16 },
+    Enum { type_name_index: u16, const_name_index: u16 },
+    /// A class literal; the index points to a return descriptor such as `Ljava/lang/String;` or `V`.
+    Class(u16),
+    Annotation(Annotation),
+    Array(Vec<ElementValue>),
+}
+
+/// An annotation on a type use (JVMS §4.7.20).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAnnotation {
+    target_type: u8,
+    target_info: TargetInfo,
+    target_path: Vec<TypePathEntry>,
+    annotation: Annotation,
+}
+
+impl TypeAnnotation {
+    pub fn get_target_type(&self) -> u8 {
+        self.target_type
+    }
+
+    pub fn get_target_info(&self) -> &TargetInfo {
+        &self.target_info
+    }
+
+    pub fn get_target_path(&self) -> &Vec<TypePathEntry> {
+        &self.target_path
+    }
+
+    pub fn get_annotation(&self) -> &Annotation {
+        &self.annotation
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TargetInfo {
+    TypeParameter { type_parameter_index: u8 },
+    Supertype { supertype_index: u16 },
+    TypeParameterBound { type_parameter_index: u8, bound_index: u8 },
+    Empty,
+    FormalParameter { formal_parameter_index: u8 },
+    Throws { throws_type_index: u16 },
+    Localvar(Vec<LocalvarTarget>),
+    Catch { exception_table_index: u16 },
+    Offset { offset: u16 },
+    TypeArgument { offset: u16, type_argument_index: u8 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalvarTarget {
+    start_pc: u16,
+    length: u16,
+    index: u16,
+}
+
+impl LocalvarTarget {
+    pub fn get_start_pc(&self) -> u16 {
+        self.start_pc
+    }
+
+    pub fn get_length(&self) -> u16 {
+        self.length
+    }
+
+    pub fn get_index(&self) -> u16 {
+        self.index
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypePathEntry {
+    type_path_kind: u8,
+    type_argument_index: u8,
+}
+
+impl TypePathEntry {
+    pub fn get_type_path_kind(&self) -> u8 {
+        self.type_path_kind
+    }
+
+    pub fn get_type_argument_index(&self) -> u8 {
+        self.type_argument_index
+    }
+}
+
+/// All annotation attributes found on one class, field, method or record component.
+#[derive(Debug, Clone, Default)]
+pub struct Annotations {
+    visible: Vec<Annotation>,
+    invisible: Vec<Annotation>,
+    visible_parameters: Vec<Vec<Annotation>>,
+    invisible_parameters: Vec<Vec<Annotation>>,
+    visible_type: Vec<TypeAnnotation>,
+    invisible_type: Vec<TypeAnnotation>,
+    default: Option<ElementValue>,
+}
+
+impl Annotations {
+    /// Annotations with `RetentionPolicy.RUNTIME`.
+    pub fn get_visible(&self) -> &Vec<Annotation> {
+        &self.visible
+    }
+
+    /// Annotations with `RetentionPolicy.CLASS`.
+    pub fn get_invisible(&self) -> &Vec<Annotation> {
+        &self.invisible
+    }
+
+    pub fn get_visible_parameters(&self) -> &Vec<Vec<Annotation>> {
+        &self.visible_parameters
+    }
+
+    pub fn get_invisible_parameters(&self) -> &Vec<Vec<Annotation>> {
+        &self.invisible_parameters
+    }
+
+    pub fn get_visible_type(&self) -> &Vec<TypeAnnotation> {
+        &self.visible_type
+    }
+
+    pub fn get_invisible_type(&self) -> &Vec<TypeAnnotation> {
+        &self.invisible_type
+    }
+
+    /// The default value of an annotation interface element.
+    pub fn get_default(&self) -> Option<&ElementValue> {
+        self.default.as_ref()
+    }
+}
+
+/// Collects the annotation attributes among `attributes`, skipping every other attribute.
+pub fn collect_annotations(class_file: &ClassFile, attributes: &[AttributeInfo]) -> Result<Annotations, ParseError> {
+    let mut annotations = Annotations::default();
+    for attribute_info in attributes {
+        let name = class_file.get_utf8(attribute_info.get_attribute_name_index() as usize)?;
+        if !is_annotation_attribute(&name) {
+            continue;
+        }
+        match attribute_info.to_attribute(class_file)? {
+            Attribute::RuntimeVisibleAnnotations(found) => annotations.visible.extend(found),
+            Attribute::RuntimeInvisibleAnnotations(found) => annotations.invisible.extend(found),
+            Attribute::RuntimeVisibleParameterAnnotations(found) => annotations.visible_parameters.extend(found),
+            Attribute::RuntimeInvisibleParameterAnnotations(found) => annotations.invisible_parameters.extend(found),
+            Attribute::RuntimeVisibleTypeAnnotations(found) => annotations.visible_type.extend(found),
+            Attribute::RuntimeInvisibleTypeAnnotations(found) => annotations.invisible_type.extend(found),
+            Attribute::AnnotationDefault(value) => annotations.default = Some(value),
+            _ => {}
+        }
+    }
+    Ok(annotations)
+}
+
+fn is_annotation_attribute(name: &str) -> bool {
+    matches!(name, "RuntimeVisibleAnnotations" | "RuntimeInvisibleAnnotations"
+        | "RuntimeVisibleParameterAnnotations" | "RuntimeInvisibleParameterAnnotations"
+        | "RuntimeVisibleTypeAnnotations" | "RuntimeInvisibleTypeAnnotations" | "AnnotationDefault")
+}
+
+pub fn parse_file(file: &mut file::File) -> Result<Annotation, ParseError> {
+    let type_index = file.get_u2()?;
+    let num_element_value_pairs = file.get_u2()? as usize;
+    let element_value_pairs = (0..num_element_value_pairs).map(|index| {
+        let element_name_index = file.get_u2()?;
+        let value = parse_element_value(file).map_err(|e| e.within(format!("element #{}", index)))?;
+        Ok(ElementValuePair { element_name_index, value })
+    }).collect::<Result<Vec<ElementValuePair>, ParseError>>()?;
+    Ok(Annotation { type_index, element_value_pairs })
+}
+
+/// Parses a table of annotations preceded by its u2 length.
+pub fn parse_range(file: &mut file::File) -> Result<Vec<Annotation>, ParseError> {
+    let num_annotations = file.get_u2()? as usize;
+    (0..num_annotations).map(|index| {
+        parse_file(file).map_err(|e| e.within(format!("annotation #{}", index)))
+    }).collect()
+}
+
+pub fn parse_parameter_annotations(file: &mut file::File) -> Result<Vec<Vec<Annotation>>, ParseError> {
+    let num_parameters = file.get_u1()? as usize;
+    (0..num_parameters).map(|index| {
+        parse_range(file).map_err(|e| e.within(format!("parameter #{}", index)))
+    }).collect()
+}
+
+pub fn parse_element_value(file: &mut file::File) -> Result<ElementValue, ParseError> {
+    let tag_offset = file.get_pointer();
+    let tag = file.get_u1()?;
+    Ok(match tag {
+        b'B' | b'C' | b'D' | b'F' | b'I' | b'J' | b'S' | b'Z' | b's' => {
+            ElementValue::Const { tag: tag as char, const_value_index: file.get_u2()? }
+        }
+        b'e' => ElementValue::Enum { type_name_index: file.get_u2()?, const_name_index: file.get_u2()? },
+        b'c' => ElementValue::Class(file.get_u2()?),
+        b'@' => ElementValue::Annotation(parse_file(file)?),
+        b'[' => {
+            let num_values = file.get_u2()? as usize;
+            ElementValue::Array((0..num_values).map(|_f| {parse_element_value(file)}).collect::<Result<Vec<ElementValue>, ParseError>>()?)
+        }
+        _ => return Err(ParseError::at(tag_offset, ParseErrorKind::InvalidTag { structure: "element value tag", tag })),
+    })
+}
+
+fn parse_target_info(file: &mut file::File, target_type: u8, target_type_offset: usize) -> Result<TargetInfo, ParseError> {
+    Ok(match target_type {
+        0x00 | 0x01 => TargetInfo::TypeParameter { type_parameter_index: file.get_u1()? },
+        0x10 => TargetInfo::Supertype { supertype_index: file.get_u2()? },
+        0x11 | 0x12 => TargetInfo::TypeParameterBound { type_parameter_index: file.get_u1()?, bound_index: file.get_u1()? },
+        0x13..=0x15 => TargetInfo::Empty,
+        0x16 => TargetInfo::FormalParameter { formal_parameter_index: file.get_u1()? },
+        0x17 => TargetInfo::Throws { throws_type_index: file.get_u2()? },
+        0x40 | 0x41 => {
+            let table_length = file.get_u2()? as usize;
+            TargetInfo::Localvar((0..table_length).map(|_f| {
+                Ok(LocalvarTarget { start_pc: file.get_u2()?, length: file.get_u2()?, index: file.get_u2()? })
+            }).collect::<Result<Vec<LocalvarTarget>, ParseError>>()?)
+        }
+        0x42 => TargetInfo::Catch { exception_table_index: file.get_u2()? },
+        0x43..=0x46 => TargetInfo::Offset { offset: file.get_u2()? },
+        0x47..=0x4B => TargetInfo::TypeArgument { offset: file.get_u2()?, type_argument_index: file.get_u1()? },
+        _ => return Err(ParseError::at(target_type_offset, ParseErrorKind::InvalidTag { structure: "type annotation target type", tag: target_type })),
+    })
+}
+
+pub fn parse_type_annotation(file: &mut file::File) -> Result<TypeAnnotation, ParseError> {
+    let target_type_offset = file.get_pointer();
+    let target_type = file.get_u1()?;
+    let target_info = parse_target_info(file, target_type, target_type_offset)?;
+    let path_length = file.get_u1()? as usize;
+    let target_path = (0..path_length).map(|_f| {
+        Ok(TypePathEntry { type_path_kind: file.get_u1()?, type_argument_index: file.get_u1()? })
+    }).collect::<Result<Vec<TypePathEntry>, ParseError>>()?;
+    Ok(TypeAnnotation { target_type, target_info, target_path, annotation: parse_file(file)? })
+}
+
+/// Parses a table of type annotations preceded by its u2 length.
+pub fn parse_type_annotations(file: &mut file::File) -> Result<Vec<TypeAnnotation>, ParseError> {
+    let num_annotations = file.get_u2()? as usize;
+    (0..num_annotations).map(|index| {
+        parse_type_annotation(file).map_err(|e| e.within(format!("type annotation #{}", index)))
+    }).collect()
+}
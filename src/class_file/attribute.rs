@@ -0,0 +1,493 @@
+use super::file;
+use super::code_attribute;
+use super::annotation;
+use super::access_flags::{InnerClassAccessFlags, ParameterAccessFlags, ModuleAccessFlags, RequiresAccessFlags};
+use super::parse_error::{ParseError, ParseErrorKind};
+
+/// A class file attribute decoded according to its name (JVMS §4.7). Indices are constant pool indices.
+#[derive(Debug, Clone)]
+pub enum Attribute {
+    ConstantValue(u16),
+    Code(code_attribute::CodeAttribute),
+    StackMapTable(Vec<StackMapFrame>),
+    Exceptions(Vec<u16>),
+    InnerClasses(Vec<InnerClass>),
+    EnclosingMethod { class_index: u16, method_index: u16 },
+    Synthetic,
+    Signature(u16),
+    SourceFile(u16),
+    SourceDebugExtension(Vec<u8>),
+    LineNumberTable(Vec<LineNumber>),
+    LocalVariableTable(Vec<LocalVariable>),
+    /// Same layout as `LocalVariableTable`, but `get_descriptor_index` points to a generic signature.
+    LocalVariableTypeTable(Vec<LocalVariable>),
+    Deprecated,
+    RuntimeVisibleAnnotations(Vec<annotation::Annotation>),
+    RuntimeInvisibleAnnotations(Vec<annotation::Annotation>),
+    RuntimeVisibleParameterAnnotations(Vec<Vec<annotation::Annotation>>),
+    RuntimeInvisibleParameterAnnotations(Vec<Vec<annotation::Annotation>>),
+    RuntimeVisibleTypeAnnotations(Vec<annotation::TypeAnnotation>),
+    RuntimeInvisibleTypeAnnotations(Vec<annotation::TypeAnnotation>),
+    AnnotationDefault(annotation::ElementValue),
+    BootstrapMethods(Vec<BootstrapMethod>),
+    MethodParameters(Vec<MethodParameter>),
+    Module(Module),
+    ModulePackages(Vec<u16>),
+    ModuleMainClass(u16),
+    NestHost(u16),
+    NestMembers(Vec<u16>),
+    Record(Vec<RecordComponent>),
+    PermittedSubclasses(Vec<u16>),
+    /// An attribute this parser doesn't know, kept as its name and raw bytes.
+    Unknown { name: String, info: Vec<u8> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationType {
+    Top,
+    Integer,
+    Float,
+    Double,
+    Long,
+    Null,
+    UninitializedThis,
+    Object(u16),
+    /// An object created by the `new` instruction at the given offset that has not been initialized yet.
+    Uninitialized(u16),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StackMapFrame {
+    Same { offset_delta: u16 },
+    SameLocals1StackItem { offset_delta: u16, stack: VerificationType },
+    /// Frame type 247: like `SameLocals1StackItem`, with an explicit offset_delta.
+    SameLocals1StackItemExtended { offset_delta: u16, stack: VerificationType },
+    Chop { offset_delta: u16, chopped: u8 },
+    /// Frame type 251: like `Same`, with an explicit offset_delta.
+    SameExtended { offset_delta: u16 },
+    Append { offset_delta: u16, locals: Vec<VerificationType> },
+    Full { offset_delta: u16, locals: Vec<VerificationType>, stack: Vec<VerificationType> },
+}
+
+impl StackMapFrame {
+    pub fn get_offset_delta(&self) -> u16 {
+        match self {
+            StackMapFrame::Same { offset_delta }
+            | StackMapFrame::SameLocals1StackItem { offset_delta, .. }
+            | StackMapFrame::SameLocals1StackItemExtended { offset_delta, .. }
+            | StackMapFrame::Chop { offset_delta, .. }
+            | StackMapFrame::SameExtended { offset_delta }
+            | StackMapFrame::Append { offset_delta, .. }
+            | StackMapFrame::Full { offset_delta, .. } => *offset_delta,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InnerClass {
+    inner_class_info_index: u16,
+    outer_class_info_index: u16,
+    inner_name_index: u16,
+    inner_class_access_flags: u16,
+}
+
+impl InnerClass {
+    pub fn get_inner_class_info_index(&self) -> u16 {
+        self.inner_class_info_index
+    }
+
+    /// Zero if the class is not a member of another class.
+    pub fn get_outer_class_info_index(&self) -> u16 {
+        self.outer_class_info_index
+    }
+
+    /// Zero for anonymous classes.
+    pub fn get_inner_name_index(&self) -> u16 {
+        self.inner_name_index
+    }
+
+    pub fn get_inner_class_access_flags(&self) -> InnerClassAccessFlags {
+        InnerClassAccessFlags::new(self.inner_class_access_flags)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LineNumber {
+    start_pc: u16,
+    line_number: u16,
+}
+
+impl LineNumber {
+    pub fn get_start_pc(&self) -> u16 {
+        self.start_pc
+    }
+
+    pub fn get_line_number(&self) -> u16 {
+        self.line_number
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalVariable {
+    start_pc: u16,
+    length: u16,
+    name_index: u16,
+    descriptor_index: u16,
+    index: u16,
+}
+
+impl LocalVariable {
+    pub fn get_start_pc(&self) -> u16 {
+        self.start_pc
+    }
+
+    pub fn get_length(&self) -> u16 {
+        self.length
+    }
+
+    pub fn get_name_index(&self) -> u16 {
+        self.name_index
+    }
+
+    pub fn get_descriptor_index(&self) -> u16 {
+        self.descriptor_index
+    }
+
+    pub fn get_index(&self) -> u16 {
+        self.index
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BootstrapMethod {
+    bootstrap_method_ref: u16,
+    bootstrap_arguments: Vec<u16>,
+}
+
+impl BootstrapMethod {
+    pub fn get_bootstrap_method_ref(&self) -> u16 {
+        self.bootstrap_method_ref
+    }
+
+    pub fn get_bootstrap_arguments(&self) -> &Vec<u16> {
+        &self.bootstrap_arguments
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MethodParameter {
+    name_index: u16,
+    access_flags: u16,
+}
+
+impl MethodParameter {
+    /// Zero for parameters without a name.
+    pub fn get_name_index(&self) -> u16 {
+        self.name_index
+    }
+
+    pub fn get_access_flags(&self) -> ParameterAccessFlags {
+        ParameterAccessFlags::new(self.access_flags)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordComponent {
+    name_index: u16,
+    descriptor_index: u16,
+    attributes: Vec<super::attribute_info::AttributeInfo>,
+}
+
+impl RecordComponent {
+    pub fn get_name_index(&self) -> u16 {
+        self.name_index
+    }
+
+    pub fn get_descriptor_index(&self) -> u16 {
+        self.descriptor_index
+    }
+
+    pub fn get_attributes(&self) -> &Vec<super::attribute_info::AttributeInfo> {
+        &self.attributes
+    }
+
+    pub fn get_annotations(&self, class_file: &super::ClassFile) -> Result<annotation::Annotations, ParseError> {
+        annotation::collect_annotations(class_file, &self.attributes)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Module {
+    module_name_index: u16,
+    module_flags: u16,
+    module_version_index: u16,
+    requires: Vec<ModuleRequires>,
+    exports: Vec<ModuleExports>,
+    opens: Vec<ModuleExports>,
+    uses: Vec<u16>,
+    provides: Vec<ModuleProvides>,
+}
+
+impl Module {
+    pub fn get_module_name_index(&self) -> u16 {
+        self.module_name_index
+    }
+
+    pub fn get_module_flags(&self) -> ModuleAccessFlags {
+        ModuleAccessFlags::new(self.module_flags)
+    }
+
+    pub fn get_module_version_index(&self) -> u16 {
+        self.module_version_index
+    }
+
+    pub fn get_requires(&self) -> &Vec<ModuleRequires> {
+        &self.requires
+    }
+
+    pub fn get_exports(&self) -> &Vec<ModuleExports> {
+        &self.exports
+    }
+
+    /// Same layout as `get_exports`.
+    pub fn get_opens(&self) -> &Vec<ModuleExports> {
+        &self.opens
+    }
+
+    pub fn get_uses(&self) -> &Vec<u16> {
+        &self.uses
+    }
+
+    pub fn get_provides(&self) -> &Vec<ModuleProvides> {
+        &self.provides
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleRequires {
+    requires_index: u16,
+    requires_flags: u16,
+    requires_version_index: u16,
+}
+
+impl ModuleRequires {
+    pub fn get_requires_index(&self) -> u16 {
+        self.requires_index
+    }
+
+    pub fn get_requires_flags(&self) -> RequiresAccessFlags {
+        RequiresAccessFlags::new(self.requires_flags)
+    }
+
+    pub fn get_requires_version_index(&self) -> u16 {
+        self.requires_version_index
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleExports {
+    package_index: u16,
+    flags: u16,
+    to_indices: Vec<u16>,
+}
+
+impl ModuleExports {
+    pub fn get_package_index(&self) -> u16 {
+        self.package_index
+    }
+
+    pub fn get_flags(&self) -> ModuleAccessFlags {
+        ModuleAccessFlags::new(self.flags)
+    }
+
+    pub fn get_to_indices(&self) -> &Vec<u16> {
+        &self.to_indices
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleProvides {
+    provides_index: u16,
+    provides_with_indices: Vec<u16>,
+}
+
+impl ModuleProvides {
+    pub fn get_provides_index(&self) -> u16 {
+        self.provides_index
+    }
+
+    pub fn get_provides_with_indices(&self) -> &Vec<u16> {
+        &self.provides_with_indices
+    }
+}
+
+fn parse_u2_table(file: &mut file::File) -> Result<Vec<u16>, ParseError> {
+    let length = file.get_u2()? as usize;
+    file.get_range_u2(length)
+}
+
+fn parse_table<T>(file: &mut file::File, entry: &str, parse: fn(&mut file::File) -> Result<T, ParseError>) -> Result<Vec<T>, ParseError> {
+    let length = file.get_u2()? as usize;
+    (0..length).map(|index| {
+        parse(file).map_err(|e| e.within(format!("{} #{}", entry, index)))
+    }).collect()
+}
+
+fn parse_verification_type(file: &mut file::File) -> Result<VerificationType, ParseError> {
+    let tag_offset = file.get_pointer();
+    let tag = file.get_u1()?;
+    Ok(match tag {
+        0 => VerificationType::Top,
+        1 => VerificationType::Integer,
+        2 => VerificationType::Float,
+        3 => VerificationType::Double,
+        4 => VerificationType::Long,
+        5 => VerificationType::Null,
+        6 => VerificationType::UninitializedThis,
+        7 => VerificationType::Object(file.get_u2()?),
+        8 => VerificationType::Uninitialized(file.get_u2()?),
+        _ => return Err(ParseError::at(tag_offset, ParseErrorKind::InvalidTag { structure: "verification type", tag })),
+    })
+}
+
+fn parse_verification_types(file: &mut file::File, count: usize) -> Result<Vec<VerificationType>, ParseError> {
+    (0..count).map(|_f| {parse_verification_type(file)}).collect()
+}
+
+fn parse_stack_map_frame(file: &mut file::File) -> Result<StackMapFrame, ParseError> {
+    let frame_type_offset = file.get_pointer();
+    let frame_type = file.get_u1()?;
+    Ok(match frame_type {
+        0..=63 => StackMapFrame::Same { offset_delta: frame_type as u16 },
+        64..=127 => StackMapFrame::SameLocals1StackItem { offset_delta: (frame_type - 64) as u16, stack: parse_verification_type(file)? },
+        247 => StackMapFrame::SameLocals1StackItemExtended { offset_delta: file.get_u2()?, stack: parse_verification_type(file)? },
+        248..=250 => StackMapFrame::Chop { offset_delta: file.get_u2()?, chopped: 251 - frame_type },
+        251 => StackMapFrame::SameExtended { offset_delta: file.get_u2()? },
+        252..=254 => {
+            let offset_delta = file.get_u2()?;
+            StackMapFrame::Append { offset_delta, locals: parse_verification_types(file, (frame_type - 251) as usize)? }
+        }
+        255 => {
+            let offset_delta = file.get_u2()?;
+            let number_of_locals = file.get_u2()? as usize;
+            let locals = parse_verification_types(file, number_of_locals)?;
+            let number_of_stack_items = file.get_u2()? as usize;
+            let stack = parse_verification_types(file, number_of_stack_items)?;
+            StackMapFrame::Full { offset_delta, locals, stack }
+        }
+        _ => return Err(ParseError::at(frame_type_offset, ParseErrorKind::InvalidTag { structure: "stack map frame type", tag: frame_type })),
+    })
+}
+
+fn parse_inner_class(file: &mut file::File) -> Result<InnerClass, ParseError> {
+    Ok(InnerClass {
+        inner_class_info_index: file.get_u2()?,
+        outer_class_info_index: file.get_u2()?,
+        inner_name_index: file.get_u2()?,
+        inner_class_access_flags: file.get_u2()?,
+    })
+}
+
+fn parse_line_number(file: &mut file::File) -> Result<LineNumber, ParseError> {
+    Ok(LineNumber { start_pc: file.get_u2()?, line_number: file.get_u2()? })
+}
+
+fn parse_local_variable(file: &mut file::File) -> Result<LocalVariable, ParseError> {
+    Ok(LocalVariable {
+        start_pc: file.get_u2()?,
+        length: file.get_u2()?,
+        name_index: file.get_u2()?,
+        descriptor_index: file.get_u2()?,
+        index: file.get_u2()?,
+    })
+}
+
+fn parse_bootstrap_method(file: &mut file::File) -> Result<BootstrapMethod, ParseError> {
+    let bootstrap_method_ref = file.get_u2()?;
+    Ok(BootstrapMethod { bootstrap_method_ref, bootstrap_arguments: parse_u2_table(file)? })
+}
+
+fn parse_method_parameter(file: &mut file::File) -> Result<MethodParameter, ParseError> {
+    Ok(MethodParameter { name_index: file.get_u2()?, access_flags: file.get_u2()? })
+}
+
+fn parse_record_component(file: &mut file::File) -> Result<RecordComponent, ParseError> {
+    let name_index = file.get_u2()?;
+    let descriptor_index = file.get_u2()?;
+    let attributes_count = file.get_u2()? as usize;
+    Ok(RecordComponent { name_index, descriptor_index, attributes: super::attribute_info::parse_range(file, attributes_count)? })
+}
+
+fn parse_module_requires(file: &mut file::File) -> Result<ModuleRequires, ParseError> {
+    Ok(ModuleRequires { requires_index: file.get_u2()?, requires_flags: file.get_u2()?, requires_version_index: file.get_u2()? })
+}
+
+fn parse_module_exports(file: &mut file::File) -> Result<ModuleExports, ParseError> {
+    let package_index = file.get_u2()?;
+    let flags = file.get_u2()?;
+    Ok(ModuleExports { package_index, flags, to_indices: parse_u2_table(file)? })
+}
+
+fn parse_module_provides(file: &mut file::File) -> Result<ModuleProvides, ParseError> {
+    let provides_index = file.get_u2()?;
+    Ok(ModuleProvides { provides_index, provides_with_indices: parse_u2_table(file)? })
+}
+
+fn parse_module(file: &mut file::File) -> Result<Module, ParseError> {
+    Ok(Module {
+        module_name_index: file.get_u2()?,
+        module_flags: file.get_u2()?,
+        module_version_index: file.get_u2()?,
+        requires: parse_table(file, "requires", parse_module_requires)?,
+        exports: parse_table(file, "exports", parse_module_exports)?,
+        opens: parse_table(file, "opens", parse_module_exports)?,
+        uses: parse_u2_table(file)?,
+        provides: parse_table(file, "provides", parse_module_provides)?,
+    })
+}
+
+/// Decodes the body of the attribute called `name`. Attributes with unknown names are returned as `Attribute::Unknown`.
+pub fn parse_file(file: &mut file::File, name: &str) -> Result<Attribute, ParseError> {
+    let attribute = match name {
+        "ConstantValue" => Attribute::ConstantValue(file.get_u2()?),
+        "Code" => Attribute::Code(code_attribute::parse_file(file)?),
+        "StackMapTable" => Attribute::StackMapTable(parse_table(file, "frame", parse_stack_map_frame)?),
+        "Exceptions" => Attribute::Exceptions(parse_u2_table(file)?),
+        "InnerClasses" => Attribute::InnerClasses(parse_table(file, "inner class", parse_inner_class)?),
+        "EnclosingMethod" => Attribute::EnclosingMethod { class_index: file.get_u2()?, method_index: file.get_u2()? },
+        "Synthetic" => Attribute::Synthetic,
+        "Signature" => Attribute::Signature(file.get_u2()?),
+        "SourceFile" => Attribute::SourceFile(file.get_u2()?),
+        "SourceDebugExtension" => Attribute::SourceDebugExtension(file.get_remaining()),
+        "LineNumberTable" => Attribute::LineNumberTable(parse_table(file, "line number", parse_line_number)?),
+        "LocalVariableTable" => Attribute::LocalVariableTable(parse_table(file, "local variable", parse_local_variable)?),
+        "LocalVariableTypeTable" => Attribute::LocalVariableTypeTable(parse_table(file, "local variable", parse_local_variable)?),
+        "Deprecated" => Attribute::Deprecated,
+        "RuntimeVisibleAnnotations" => Attribute::RuntimeVisibleAnnotations(annotation::parse_range(file)?),
+        "RuntimeInvisibleAnnotations" => Attribute::RuntimeInvisibleAnnotations(annotation::parse_range(file)?),
+        "RuntimeVisibleParameterAnnotations" => Attribute::RuntimeVisibleParameterAnnotations(annotation::parse_parameter_annotations(file)?),
+        "RuntimeInvisibleParameterAnnotations" => Attribute::RuntimeInvisibleParameterAnnotations(annotation::parse_parameter_annotations(file)?),
+        "RuntimeVisibleTypeAnnotations" => Attribute::RuntimeVisibleTypeAnnotations(annotation::parse_type_annotations(file)?),
+        "RuntimeInvisibleTypeAnnotations" => Attribute::RuntimeInvisibleTypeAnnotations(annotation::parse_type_annotations(file)?),
+        "AnnotationDefault" => Attribute::AnnotationDefault(annotation::parse_element_value(file)?),
+        "BootstrapMethods" => Attribute::BootstrapMethods(parse_table(file, "bootstrap method", parse_bootstrap_method)?),
+        "MethodParameters" => {
+            let parameters_count = file.get_u1()? as usize;
+            Attribute::MethodParameters((0..parameters_count).map(|index| {
+                parse_method_parameter(file).map_err(|e| e.within(format!("parameter #{}", index)))
+            }).collect::<Result<Vec<MethodParameter>, ParseError>>()?)
+        }
+        "Module" => Attribute::Module(parse_module(file)?),
+        "ModulePackages" => Attribute::ModulePackages(parse_u2_table(file)?),
+        "ModuleMainClass" => Attribute::ModuleMainClass(file.get_u2()?),
+        "NestHost" => Attribute::NestHost(file.get_u2()?),
+        "NestMembers" => Attribute::NestMembers(parse_u2_table(file)?),
+        "Record" => Attribute::Record(parse_table(file, "record component", parse_record_component)?),
+        "PermittedSubclasses" => Attribute::PermittedSubclasses(parse_u2_table(file)?),
+        _ => Attribute::Unknown { name: name.to_string(), info: file.get_remaining() },
+    };
+    if file.has_next() {
+        return Err(ParseError::at(file.get_pointer(), ParseErrorKind::TrailingBytes(file.remaining())));
+    }
+    Ok(attribute)
+}
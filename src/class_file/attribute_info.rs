@@ -1,29 +1,65 @@
 use super::file;
 use super::code_attribute;
+use super::attribute;
+use super::parse_error::ParseError;
+use super::ClassFile;
 
 #[derive(Debug, Clone)]
 pub struct AttributeInfo {
     attribute_name_index: u16,
     info: Vec<u8>,
+    info_offset: usize,
 }
 
 
 impl AttributeInfo {
+    pub fn new(attribute_name_index: u16, info: Vec<u8>) -> AttributeInfo {
+        AttributeInfo { attribute_name_index, info, info_offset: 0 }
+    }
+
+    pub fn get_info(&self) -> &Vec<u8> {
+        &self.info
+    }
+
+    pub fn write_file(&self, file: &mut file::File) {
+        file.put_u2(self.attribute_name_index);
+        file.put_u4(self.info.len() as u32);
+        file.put_range(&self.info);
+    }
+
     pub fn get_attribute_name_index(&self) -> u16 {
         self.attribute_name_index
     }
 
-    pub fn to_code_attribute(&self) -> code_attribute::CodeAttribute {
-        code_attribute::parse_file(&mut file::File::new(self.info.clone()))
+    pub fn to_code_attribute(&self) -> Result<code_attribute::CodeAttribute, ParseError> {
+        code_attribute::parse_file(&mut file::File::new_at(self.info.clone(), self.info_offset))
+            .map_err(|e| e.within("Code attribute"))
     }
+
+    /// Decodes this attribute according to its name, resolved through the constant pool of `class_file`.
+    pub fn to_attribute(&self, class_file: &ClassFile) -> Result<attribute::Attribute, ParseError> {
+        let name = class_file.get_utf8(self.attribute_name_index as usize)?;
+        attribute::parse_file(&mut file::File::new_at(self.info.clone(), self.info_offset), &name)
+            .map_err(|e| e.within(format!("{} attribute", name)))
+    }
+}
+
+pub fn parse_file(file: &mut file::File) -> Result<AttributeInfo, ParseError> {
+    let attribute_name_index = file.get_u2()?;
+    let info_length = file.get_u4()? as usize;
+    let info_offset = file.get_pointer();
+    Ok(AttributeInfo { attribute_name_index, info: file.get_range(info_length)?, info_offset })
 }
 
-pub fn parse_file(file: &mut file::File) -> AttributeInfo {
-    let attribute_name_index = file.get_u2();
-    let info_length = file.get_u4() as isize;
-    AttributeInfo { attribute_name_index, info: file.get_range(info_length) }
+pub fn write_range(file: &mut file::File, attributes: &[AttributeInfo]) {
+    file.put_u2(attributes.len() as u16);
+    for attribute in attributes {
+        attribute.write_file(file);
+    }
 }
 
-pub fn parse_range(file: &mut file::File, range: usize) -> Vec<AttributeInfo> {
-    (0..range).into_iter().map(|_f| {parse_file(file)}).collect()
-}
\ No newline at end of file
+pub fn parse_range(file: &mut file::File, range: usize) -> Result<Vec<AttributeInfo>, ParseError> {
+    (0..range).map(|index| {
+        parse_file(file).map_err(|e| e.within(format!("attribute #{}", index)))
+    }).collect()
+}
@@ -0,0 +1,82 @@
+use std::error::Error;
+use std::fmt;
+use super::parse_error::ParseError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassFormatErrorKind {
+    InvalidMagic(u32),
+    UnsupportedVersion { major_version: u16, minor_version: u16 },
+    /// The constant pool entry at `index` has a tag that this class file may not use: it is too
+    /// new for `major_version`, or a module constant outside of a module.
+    UnsupportedConstant { index: usize, tag: u8, major_version: u16 },
+    InvalidSuperclass(&'static str),
+    /// `kind` describes what is named, e.g. `class` or `method`.
+    InvalidName { kind: &'static str, name: String },
+    InvalidReferenceKind(u8),
+    IllegalAccessFlags { flags: u16, reason: &'static str },
+    /// A second field or method with the same name and descriptor.
+    Duplicate(String),
+    /// A method descriptor with parameters taking more than 255 local variable slots.
+    TooManyParameters(usize),
+    CodeAttributeCount { expected: usize, found: usize },
+    /// A constant pool reference to an entry of the wrong kind, or an attribute that does not decode.
+    Parse(ParseError),
+}
+
+impl fmt::Display for ClassFormatErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClassFormatErrorKind::InvalidMagic(magic) => write!(f, "incompatible magic value {:#010x}", magic),
+            ClassFormatErrorKind::UnsupportedVersion { major_version, minor_version } => {
+                write!(f, "unsupported class file version {}.{}", major_version, minor_version)
+            }
+            ClassFormatErrorKind::UnsupportedConstant { index, tag, major_version } => {
+                write!(f, "tag {} of entry #{} is not allowed in this class file (version {})", tag, index, major_version)
+            }
+            ClassFormatErrorKind::InvalidSuperclass(reason) => write!(f, "invalid super_class: {}", reason),
+            ClassFormatErrorKind::InvalidName { kind, name } => write!(f, "illegal {} name {:?}", kind, name),
+            ClassFormatErrorKind::InvalidReferenceKind(reference_kind) => write!(f, "invalid method handle reference kind {}", reference_kind),
+            ClassFormatErrorKind::IllegalAccessFlags { flags, reason } => write!(f, "illegal access flags {:#06x}: {}", flags, reason),
+            ClassFormatErrorKind::Duplicate(member) => write!(f, "duplicate {}", member),
+            ClassFormatErrorKind::TooManyParameters(slots) => write!(f, "parameters take {} slots, more than 255", slots),
+            ClassFormatErrorKind::CodeAttributeCount { expected, found } => write!(f, "expected {} Code attributes, found {}", expected, found),
+            ClassFormatErrorKind::Parse(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// A violation of the format checks of JVMS §4.8. Corresponds to `java.lang.ClassFormatError`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassFormatError {
+    location: String,
+    kind: ClassFormatErrorKind,
+}
+
+impl ClassFormatError {
+    pub fn new<S: Into<String>>(location: S, kind: ClassFormatErrorKind) -> ClassFormatError {
+        ClassFormatError { location: location.into(), kind }
+    }
+
+    /// The structure the error is in, e.g. `constant #7` or `method main([Ljava/lang/String;)V`.
+    pub fn get_location(&self) -> &str {
+        &self.location
+    }
+
+    pub fn get_kind(&self) -> &ClassFormatErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ClassFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "java.lang.ClassFormatError: {}: {}", self.location, self.kind)
+    }
+}
+
+impl Error for ClassFormatError {}
+
+impl From<ParseError> for ClassFormatErrorKind {
+    fn from(error: ParseError) -> ClassFormatErrorKind {
+        ClassFormatErrorKind::Parse(error)
+    }
+}
@@ -1,8 +1,10 @@
 use super::exception_table_entry;
 use super::attribute_info;
 use super::file;
+use super::parse_error::ParseError;
+use super::instruction;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CodeAttribute {
     max_stack: u16,
     max_locals: u16,
@@ -12,10 +14,38 @@ pub struct CodeAttribute {
 }
 
 impl CodeAttribute {
+    pub fn new(max_stack: u16, max_locals: u16, code: Vec<u8>, exception_table: Vec<exception_table_entry::ExceptionTableEntry>, attributes: Vec<attribute_info::AttributeInfo>) -> CodeAttribute {
+        CodeAttribute { max_stack, max_locals, code, exception_table, attributes }
+    }
+
+    pub fn write_file(&self, file: &mut file::File) {
+        file.put_u2(self.max_stack);
+        file.put_u2(self.max_locals);
+        file.put_u4(self.code.len() as u32);
+        file.put_range(&self.code);
+        file.put_u2(self.exception_table.len() as u16);
+        for entry in &self.exception_table {
+            entry.write_file(file);
+        }
+        attribute_info::write_range(file, &self.attributes);
+    }
+
+    /// Serializes the attribute body, ready to be wrapped in an `AttributeInfo` named `Code`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut file = file::File::new(Vec::new());
+        self.write_file(&mut file);
+        file.into_data()
+    }
+
     pub fn get_code(&self) -> Vec<u8> {
         self.code.clone()
     }
 
+    /// Decodes the code array, see `instruction::parse_code`.
+    pub fn get_instructions(&self) -> Result<Vec<(usize, instruction::Insn)>, ParseError> {
+        instruction::parse_code(&self.code)
+    }
+
     pub fn get_max_locals(&self) -> u16 {
         self.max_locals
     }
@@ -25,7 +55,6 @@ impl CodeAttribute {
         self.max_stack
     }
 
-    #[allow(dead_code)]
     pub fn get_exception_table(&self) -> Vec<exception_table_entry::ExceptionTableEntry> {
         self.exception_table.clone()
     }
@@ -36,15 +65,15 @@ impl CodeAttribute {
     }
 }
 
-pub fn parse_file(file: &mut file::File) -> CodeAttribute {
-    let max_stack = file.get_u2();
-    let max_locals = file.get_u2();
-    let code_length = file.get_u4() as usize;
-    let code = file.get_range(code_length as isize);
-    let execption_table_length = file.get_u2() as usize;
-    let exception_table = exception_table_entry::parse_range(file, execption_table_length);
-    let attributes_count = file.get_u2() as usize;
-    let attributes = attribute_info::parse_range(file, attributes_count);
-
-    CodeAttribute { max_stack, max_locals, code, exception_table, attributes }
-}
\ No newline at end of file
+pub fn parse_file(file: &mut file::File) -> Result<CodeAttribute, ParseError> {
+    let max_stack = file.get_u2()?;
+    let max_locals = file.get_u2()?;
+    let code_length = file.get_u4()? as usize;
+    let code = file.get_range(code_length)?;
+    let execption_table_length = file.get_u2()? as usize;
+    let exception_table = exception_table_entry::parse_range(file, execption_table_length)?;
+    let attributes_count = file.get_u2()? as usize;
+    let attributes = attribute_info::parse_range(file, attributes_count)?;
+
+    Ok(CodeAttribute { max_stack, max_locals, code, exception_table, attributes })
+}
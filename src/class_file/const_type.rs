@@ -1,4 +1,5 @@
 use super::file;
+use super::parse_error::{ParseError, ParseErrorKind};
 
 #[derive(Debug, Clone)]
 pub enum ConstType {
@@ -15,33 +16,123 @@ pub enum ConstType {
     ConstantUtf8(Vec<u8>),
     ConstantMethodHandle(u8, u16),
     ConstantMethodType(u16),
+    ConstantDynamic(u16, u16),
     ConstantInvokeDynamic(u16, u16),
+    ConstantModule(u16),
+    ConstantPackage(u16),
+    /// The slot following a `ConstantLong` or `ConstantDouble`, which the JVM spec marks as unusable.
+    Unusable,
 }
 
-pub fn parse_file(file: &mut file::File) -> ConstType {
-    let tag = file.get_u1();
-    match tag {
-        7 => {ConstType::ConstantClass(file.get_u2())}
-        9 => {ConstType::ConstantFieldref(file.get_u2(), file.get_u2())}
-        10 => {ConstType::ConstantMethodref(file.get_u2(), file.get_u2())}
-        11 => {ConstType::ConstantInterfaceMethodref(file.get_u2(), file.get_u2())}
-        8 => {ConstType::ConstantString(file.get_u2())}
-        3 => {ConstType::ConstantInteger(file.get_u4())}
-        4 => {ConstType::ConstantFloat(file.get_u4())}
-        5 => {ConstType::ConstantLong(file.get_u4(), file.get_u4())}
-        6 => {ConstType::ConstantDouble(file.get_u4(), file.get_u4())}
-        12 => {ConstType::ConstantNameAndType(file.get_u2(), file.get_u2())}
-        1 => {
-            let string_length = file.get_u2() as isize;
-            ConstType::ConstantUtf8(file.get_range(string_length))
+impl ConstType {
+    /// Long and double constants occupy two constant pool indices.
+    pub fn is_wide(&self) -> bool {
+        matches!(self, ConstType::ConstantLong(_, _) | ConstType::ConstantDouble(_, _))
+    }
+}
+
+impl ConstType {
+    pub fn get_tag(&self) -> Option<u8> {
+        match self {
+            ConstType::ConstantUtf8(_) => Some(1),
+            ConstType::ConstantInteger(_) => Some(3),
+            ConstType::ConstantFloat(_) => Some(4),
+            ConstType::ConstantLong(_, _) => Some(5),
+            ConstType::ConstantDouble(_, _) => Some(6),
+            ConstType::ConstantClass(_) => Some(7),
+            ConstType::ConstantString(_) => Some(8),
+            ConstType::ConstantFieldref(_, _) => Some(9),
+            ConstType::ConstantMethodref(_, _) => Some(10),
+            ConstType::ConstantInterfaceMethodref(_, _) => Some(11),
+            ConstType::ConstantNameAndType(_, _) => Some(12),
+            ConstType::ConstantMethodHandle(_, _) => Some(15),
+            ConstType::ConstantMethodType(_) => Some(16),
+            ConstType::ConstantDynamic(_, _) => Some(17),
+            ConstType::ConstantInvokeDynamic(_, _) => Some(18),
+            ConstType::ConstantModule(_) => Some(19),
+            ConstType::ConstantPackage(_) => Some(20),
+            ConstType::Unusable => None,
+        }
+    }
+
+    /// Writes the entry in class file format. The unusable slot has no representation and writes nothing.
+    pub fn write_file(&self, file: &mut file::File) {
+        if let Some(tag) = self.get_tag() {
+            file.put_u1(tag);
+        }
+        match self {
+            ConstType::ConstantClass(index)
+            | ConstType::ConstantString(index)
+            | ConstType::ConstantMethodType(index)
+            | ConstType::ConstantModule(index)
+            | ConstType::ConstantPackage(index) => file.put_u2(*index),
+            ConstType::ConstantFieldref(first, second)
+            | ConstType::ConstantMethodref(first, second)
+            | ConstType::ConstantInterfaceMethodref(first, second)
+            | ConstType::ConstantNameAndType(first, second)
+            | ConstType::ConstantDynamic(first, second)
+            | ConstType::ConstantInvokeDynamic(first, second) => {
+                file.put_u2(*first);
+                file.put_u2(*second);
+            }
+            ConstType::ConstantInteger(bytes) | ConstType::ConstantFloat(bytes) => file.put_u4(*bytes),
+            ConstType::ConstantLong(high_bytes, low_bytes) | ConstType::ConstantDouble(high_bytes, low_bytes) => {
+                file.put_u4(*high_bytes);
+                file.put_u4(*low_bytes);
+            }
+            ConstType::ConstantUtf8(bytes) => {
+                file.put_u2(bytes.len() as u16);
+                file.put_range(bytes);
+            }
+            ConstType::ConstantMethodHandle(reference_kind, reference_index) => {
+                file.put_u1(*reference_kind);
+                file.put_u2(*reference_index);
+            }
+            ConstType::Unusable => {}
         }
-        15 => {ConstType::ConstantMethodHandle(file.get_u1(), file.get_u2())}
-        16 => {ConstType::ConstantMethodType(file.get_u2())}
-        18 => {ConstType::ConstantInvokeDynamic(file.get_u2(), file.get_u2())}
-        _ => {panic!();}
     }
 }
 
-pub fn parse_range(file: &mut file::File, range: usize) -> Vec<ConstType> {
-    (1..range).into_iter().map(|_f| {parse_file(file)}).collect()
-}
\ No newline at end of file
+pub fn parse_file(file: &mut file::File) -> Result<ConstType, ParseError> {
+    let tag_offset = file.get_pointer();
+    let tag = file.get_u1()?;
+    Ok(match tag {
+        7 => {ConstType::ConstantClass(file.get_u2()?)}
+        9 => {ConstType::ConstantFieldref(file.get_u2()?, file.get_u2()?)}
+        10 => {ConstType::ConstantMethodref(file.get_u2()?, file.get_u2()?)}
+        11 => {ConstType::ConstantInterfaceMethodref(file.get_u2()?, file.get_u2()?)}
+        8 => {ConstType::ConstantString(file.get_u2()?)}
+        3 => {ConstType::ConstantInteger(file.get_u4()?)}
+        4 => {ConstType::ConstantFloat(file.get_u4()?)}
+        5 => {ConstType::ConstantLong(file.get_u4()?, file.get_u4()?)}
+        6 => {ConstType::ConstantDouble(file.get_u4()?, file.get_u4()?)}
+        12 => {ConstType::ConstantNameAndType(file.get_u2()?, file.get_u2()?)}
+        1 => {
+            let string_length = file.get_u2()? as usize;
+            ConstType::ConstantUtf8(file.get_range(string_length)?)
+        }
+        15 => {ConstType::ConstantMethodHandle(file.get_u1()?, file.get_u2()?)}
+        16 => {ConstType::ConstantMethodType(file.get_u2()?)}
+        17 => {ConstType::ConstantDynamic(file.get_u2()?, file.get_u2()?)}
+        18 => {ConstType::ConstantInvokeDynamic(file.get_u2()?, file.get_u2()?)}
+        19 => {ConstType::ConstantModule(file.get_u2()?)}
+        20 => {ConstType::ConstantPackage(file.get_u2()?)}
+        _ => {return Err(ParseError::at(tag_offset, ParseErrorKind::UnknownConstantTag(tag)));}
+    })
+}
+
+pub fn parse_range(file: &mut file::File, range: usize) -> Result<Vec<ConstType>, ParseError> {
+    let mut constant_pool = Vec::with_capacity(range);
+    let mut index = 1;
+    while index < range {
+        let constant = parse_file(file).map_err(|e| e.within(format!("constant pool entry #{}", index)))?;
+        let is_wide = constant.is_wide();
+        constant_pool.push(constant);
+        index += 1;
+        if is_wide && index < range {
+            constant_pool.push(ConstType::Unusable);
+            index += 1;
+        }
+    }
+    Ok(constant_pool)
+}
@@ -0,0 +1,569 @@
+use std::fmt;
+use super::parse_error::{ParseError, ParseErrorKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseType {
+    Byte,
+    Char,
+    Double,
+    Float,
+    Int,
+    Long,
+    Short,
+    Boolean,
+}
+
+impl BaseType {
+    fn from_char(c: char) -> Option<BaseType> {
+        match c {
+            'B' => Some(BaseType::Byte),
+            'C' => Some(BaseType::Char),
+            'D' => Some(BaseType::Double),
+            'F' => Some(BaseType::Float),
+            'I' => Some(BaseType::Int),
+            'J' => Some(BaseType::Long),
+            'S' => Some(BaseType::Short),
+            'Z' => Some(BaseType::Boolean),
+            _ => None,
+        }
+    }
+
+    pub fn get_descriptor(&self) -> char {
+        match self {
+            BaseType::Byte => 'B',
+            BaseType::Char => 'C',
+            BaseType::Double => 'D',
+            BaseType::Float => 'F',
+            BaseType::Int => 'I',
+            BaseType::Long => 'J',
+            BaseType::Short => 'S',
+            BaseType::Boolean => 'Z',
+        }
+    }
+
+    /// Local variable and operand stack slots taken by a value of this type.
+    pub fn get_slot_count(&self) -> usize {
+        match self {
+            BaseType::Long | BaseType::Double => 2,
+            _ => 1,
+        }
+    }
+
+    /// Whether the type is represented as an `int` on the operand stack.
+    pub fn is_int_like(&self) -> bool {
+        matches!(self, BaseType::Byte | BaseType::Char | BaseType::Int | BaseType::Short | BaseType::Boolean)
+    }
+}
+
+impl fmt::Display for BaseType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            BaseType::Byte => "byte",
+            BaseType::Char => "char",
+            BaseType::Double => "double",
+            BaseType::Float => "float",
+            BaseType::Int => "int",
+            BaseType::Long => "long",
+            BaseType::Short => "short",
+            BaseType::Boolean => "boolean",
+        })
+    }
+}
+
+/// A field descriptor (JVMS §4.3.2). Object types hold the binary name in internal form, e.g. `java/lang/String`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldType {
+    Base(BaseType),
+    Object(String),
+    Array(Box<FieldType>),
+}
+
+impl FieldType {
+    pub fn get_slot_count(&self) -> usize {
+        match self {
+            FieldType::Base(base_type) => base_type.get_slot_count(),
+            _ => 1,
+        }
+    }
+
+    pub fn get_descriptor(&self) -> String {
+        match self {
+            FieldType::Base(base_type) => base_type.get_descriptor().to_string(),
+            FieldType::Object(class_name) => format!("L{};", class_name),
+            FieldType::Array(component) => format!("[{}", component.get_descriptor()),
+        }
+    }
+}
+
+/// Formats the type in Java source syntax, e.g. `java.lang.String[][]`.
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldType::Base(base_type) => write!(f, "{}", base_type),
+            FieldType::Object(class_name) => f.write_str(&class_name.replace('/', ".")),
+            FieldType::Array(component) => write!(f, "{}[]", component),
+        }
+    }
+}
+
+/// A method descriptor (JVMS §4.3.3). A `None` return type stands for `void`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodDescriptor {
+    parameters: Vec<FieldType>,
+    return_type: Option<FieldType>,
+}
+
+impl MethodDescriptor {
+    pub fn get_parameters(&self) -> &Vec<FieldType> {
+        &self.parameters
+    }
+
+    pub fn get_return_type(&self) -> Option<&FieldType> {
+        self.return_type.as_ref()
+    }
+
+    /// Local variable slots taken by the arguments, not counting `this`.
+    pub fn get_argument_slots(&self) -> usize {
+        self.parameters.iter().map(|parameter| parameter.get_slot_count()).sum()
+    }
+
+    /// Operand stack slots taken by the return value.
+    pub fn get_return_slots(&self) -> usize {
+        self.return_type.as_ref().map(|return_type| return_type.get_slot_count()).unwrap_or(0)
+    }
+}
+
+/// A reference type in a generic signature (JVMS §4.7.9.1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReferenceTypeSignature {
+    Class(ClassTypeSignature),
+    TypeVariable(String),
+    Array(Box<JavaTypeSignature>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JavaTypeSignature {
+    Base(BaseType),
+    Reference(ReferenceTypeSignature),
+}
+
+/// A possibly parameterized class type. The first segment carries the package in internal form,
+/// the following ones are the inner classes, e.g. `java/util/Map` and `Entry`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassTypeSignature {
+    segments: Vec<SimpleClassTypeSignature>,
+}
+
+impl ClassTypeSignature {
+    pub fn get_segments(&self) -> &Vec<SimpleClassTypeSignature> {
+        &self.segments
+    }
+
+    /// The binary name of the class in internal form, e.g. `java/util/Map$Entry`.
+    pub fn get_class_name(&self) -> String {
+        self.segments.iter().map(|segment| segment.name.as_str()).collect::<Vec<&str>>().join("$")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimpleClassTypeSignature {
+    name: String,
+    type_arguments: Vec<TypeArgument>,
+}
+
+impl SimpleClassTypeSignature {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_type_arguments(&self) -> &Vec<TypeArgument> {
+        &self.type_arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeArgument {
+    /// The unbounded wildcard `?`.
+    Any,
+    Exact(ReferenceTypeSignature),
+    Extends(ReferenceTypeSignature),
+    Super(ReferenceTypeSignature),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeParameter {
+    name: String,
+    class_bound: Option<ReferenceTypeSignature>,
+    interface_bounds: Vec<ReferenceTypeSignature>,
+}
+
+impl TypeParameter {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_class_bound(&self) -> Option<&ReferenceTypeSignature> {
+        self.class_bound.as_ref()
+    }
+
+    pub fn get_interface_bounds(&self) -> &Vec<ReferenceTypeSignature> {
+        &self.interface_bounds
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassSignature {
+    type_parameters: Vec<TypeParameter>,
+    superclass: ClassTypeSignature,
+    interfaces: Vec<ClassTypeSignature>,
+}
+
+impl ClassSignature {
+    pub fn get_type_parameters(&self) -> &Vec<TypeParameter> {
+        &self.type_parameters
+    }
+
+    pub fn get_superclass(&self) -> &ClassTypeSignature {
+        &self.superclass
+    }
+
+    pub fn get_interfaces(&self) -> &Vec<ClassTypeSignature> {
+        &self.interfaces
+    }
+}
+
+/// A generic method signature. A `None` result stands for `void`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodSignature {
+    type_parameters: Vec<TypeParameter>,
+    parameters: Vec<JavaTypeSignature>,
+    result: Option<JavaTypeSignature>,
+    throws: Vec<ReferenceTypeSignature>,
+}
+
+impl MethodSignature {
+    pub fn get_type_parameters(&self) -> &Vec<TypeParameter> {
+        &self.type_parameters
+    }
+
+    pub fn get_parameters(&self) -> &Vec<JavaTypeSignature> {
+        &self.parameters
+    }
+
+    pub fn get_result(&self) -> Option<&JavaTypeSignature> {
+        self.result.as_ref()
+    }
+
+    pub fn get_throws(&self) -> &Vec<ReferenceTypeSignature> {
+        &self.throws
+    }
+}
+
+impl fmt::Display for ReferenceTypeSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReferenceTypeSignature::Class(class_type) => write!(f, "{}", class_type),
+            ReferenceTypeSignature::TypeVariable(name) => f.write_str(name),
+            ReferenceTypeSignature::Array(component) => write!(f, "{}[]", component),
+        }
+    }
+}
+
+impl fmt::Display for JavaTypeSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JavaTypeSignature::Base(base_type) => write!(f, "{}", base_type),
+            JavaTypeSignature::Reference(reference) => write!(f, "{}", reference),
+        }
+    }
+}
+
+impl fmt::Display for ClassTypeSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, segment) in self.segments.iter().enumerate() {
+            if index == 0 {
+                f.write_str(&segment.name.replace('/', "."))?;
+            } else {
+                write!(f, ".{}", segment.name)?;
+            }
+            if !segment.type_arguments.is_empty() {
+                let arguments: Vec<String> = segment.type_arguments.iter().map(|argument| argument.to_string()).collect();
+                write!(f, "<{}>", arguments.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TypeArgument {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TypeArgument::Any => f.write_str("?"),
+            TypeArgument::Exact(reference) => write!(f, "{}", reference),
+            TypeArgument::Extends(reference) => write!(f, "? extends {}", reference),
+            TypeArgument::Super(reference) => write!(f, "? super {}", reference),
+        }
+    }
+}
+
+/// Formats the parameter with its bounds, e.g. `T extends java.lang.Comparable<T>`. A lone
+/// `java.lang.Object` bound is left out.
+impl fmt::Display for TypeParameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bounds: Vec<String> = self.class_bound.iter().chain(&self.interface_bounds)
+            .map(|bound| bound.to_string())
+            .filter(|bound| bound != "java.lang.Object")
+            .collect();
+        if bounds.is_empty() {
+            f.write_str(&self.name)
+        } else {
+            write!(f, "{} extends {}", self.name, bounds.join(" & "))
+        }
+    }
+}
+
+struct Reader<'a> {
+    text: &'a str,
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(text: &'a str) -> Reader<'a> {
+        Reader { text, chars: text.chars().collect(), position: 0 }
+    }
+
+    fn error(&self) -> ParseError {
+        ParseError::new(ParseErrorKind::InvalidDescriptor { descriptor: self.text.to_string(), position: self.position })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Result<char, ParseError> {
+        let c = self.peek().ok_or_else(|| self.error())?;
+        self.position += 1;
+        Ok(c)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        if self.peek() != Some(expected) {
+            return Err(self.error());
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn end(&self) -> Result<(), ParseError> {
+        if self.position != self.chars.len() {
+            return Err(self.error());
+        }
+        Ok(())
+    }
+
+    /// Reads characters up to, but not including, one of `terminators`. Fails on an empty result.
+    fn identifier(&mut self, terminators: &[char]) -> Result<String, ParseError> {
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            if terminators.contains(&c) {
+                break;
+            }
+            self.position += 1;
+        }
+        if self.position == start || self.peek().is_none() {
+            return Err(self.error());
+        }
+        Ok(self.chars[start..self.position].iter().collect())
+    }
+
+    fn field_type(&mut self) -> Result<FieldType, ParseError> {
+        let c = self.next()?;
+        if let Some(base_type) = BaseType::from_char(c) {
+            return Ok(FieldType::Base(base_type));
+        }
+        match c {
+            'L' => {
+                let class_name = self.identifier(&[';', '.', '[', '<', '>'])?;
+                self.expect(';')?;
+                Ok(FieldType::Object(class_name))
+            }
+            '[' => Ok(FieldType::Array(Box::new(self.field_type()?))),
+            _ => {
+                self.position -= 1;
+                Err(self.error())
+            }
+        }
+    }
+
+    fn java_type_signature(&mut self) -> Result<JavaTypeSignature, ParseError> {
+        match self.peek().and_then(BaseType::from_char) {
+            Some(base_type) => {
+                self.position += 1;
+                Ok(JavaTypeSignature::Base(base_type))
+            }
+            None => Ok(JavaTypeSignature::Reference(self.reference_type_signature()?)),
+        }
+    }
+
+    fn reference_type_signature(&mut self) -> Result<ReferenceTypeSignature, ParseError> {
+        match self.peek() {
+            Some('L') => Ok(ReferenceTypeSignature::Class(self.class_type_signature()?)),
+            Some('T') => {
+                self.position += 1;
+                let name = self.identifier(&[';', '.', '/', '[', '<', '>', ':'])?;
+                self.expect(';')?;
+                Ok(ReferenceTypeSignature::TypeVariable(name))
+            }
+            Some('[') => {
+                self.position += 1;
+                Ok(ReferenceTypeSignature::Array(Box::new(self.java_type_signature()?)))
+            }
+            _ => Err(self.error()),
+        }
+    }
+
+    fn class_type_signature(&mut self) -> Result<ClassTypeSignature, ParseError> {
+        self.expect('L')?;
+        let mut segments = vec![self.simple_class_type_signature(true)?];
+        while self.peek() == Some('.') {
+            self.position += 1;
+            segments.push(self.simple_class_type_signature(false)?);
+        }
+        self.expect(';')?;
+        Ok(ClassTypeSignature { segments })
+    }
+
+    fn simple_class_type_signature(&mut self, with_package: bool) -> Result<SimpleClassTypeSignature, ParseError> {
+        let name = if with_package {
+            self.identifier(&[';', '.', '[', '<', '>', ':'])?
+        } else {
+            self.identifier(&[';', '.', '/', '[', '<', '>', ':'])?
+        };
+        let mut type_arguments = Vec::new();
+        if self.peek() == Some('<') {
+            self.position += 1;
+            while self.peek() != Some('>') {
+                type_arguments.push(match self.peek() {
+                    Some('*') => {
+                        self.position += 1;
+                        TypeArgument::Any
+                    }
+                    Some('+') => {
+                        self.position += 1;
+                        TypeArgument::Extends(self.reference_type_signature()?)
+                    }
+                    Some('-') => {
+                        self.position += 1;
+                        TypeArgument::Super(self.reference_type_signature()?)
+                    }
+                    _ => TypeArgument::Exact(self.reference_type_signature()?),
+                });
+            }
+            self.position += 1;
+            if type_arguments.is_empty() {
+                return Err(self.error());
+            }
+        }
+        Ok(SimpleClassTypeSignature { name, type_arguments })
+    }
+
+    fn type_parameters(&mut self) -> Result<Vec<TypeParameter>, ParseError> {
+        let mut type_parameters = Vec::new();
+        if self.peek() != Some('<') {
+            return Ok(type_parameters);
+        }
+        self.position += 1;
+        while self.peek() != Some('>') {
+            let name = self.identifier(&[';', '.', '/', '[', '<', '>', ':'])?;
+            self.expect(':')?;
+            let class_bound = match self.peek() {
+                Some('L') | Some('T') | Some('[') => Some(self.reference_type_signature()?),
+                _ => None,
+            };
+            let mut interface_bounds = Vec::new();
+            while self.peek() == Some(':') {
+                self.position += 1;
+                interface_bounds.push(self.reference_type_signature()?);
+            }
+            type_parameters.push(TypeParameter { name, class_bound, interface_bounds });
+        }
+        self.position += 1;
+        if type_parameters.is_empty() {
+            return Err(self.error());
+        }
+        Ok(type_parameters)
+    }
+}
+
+/// Parses a field descriptor such as `[[Ljava/lang/String;`.
+pub fn parse_field_descriptor(descriptor: &str) -> Result<FieldType, ParseError> {
+    let mut reader = Reader::new(descriptor);
+    let field_type = reader.field_type()?;
+    reader.end()?;
+    Ok(field_type)
+}
+
+/// Parses a method descriptor such as `(IJ[Ljava/lang/Object;)V`.
+pub fn parse_method_descriptor(descriptor: &str) -> Result<MethodDescriptor, ParseError> {
+    let mut reader = Reader::new(descriptor);
+    reader.expect('(')?;
+    let mut parameters = Vec::new();
+    while reader.peek() != Some(')') {
+        parameters.push(reader.field_type()?);
+    }
+    reader.position += 1;
+    let return_type = if reader.peek() == Some('V') {
+        reader.position += 1;
+        None
+    } else {
+        Some(reader.field_type()?)
+    };
+    reader.end()?;
+    Ok(MethodDescriptor { parameters, return_type })
+}
+
+/// Parses the `Signature` attribute of a class.
+pub fn parse_class_signature(signature: &str) -> Result<ClassSignature, ParseError> {
+    let mut reader = Reader::new(signature);
+    let type_parameters = reader.type_parameters()?;
+    let superclass = reader.class_type_signature()?;
+    let mut interfaces = Vec::new();
+    while reader.peek().is_some() {
+        interfaces.push(reader.class_type_signature()?);
+    }
+    Ok(ClassSignature { type_parameters, superclass, interfaces })
+}
+
+/// Parses the `Signature` attribute of a method.
+pub fn parse_method_signature(signature: &str) -> Result<MethodSignature, ParseError> {
+    let mut reader = Reader::new(signature);
+    let type_parameters = reader.type_parameters()?;
+    reader.expect('(')?;
+    let mut parameters = Vec::new();
+    while reader.peek() != Some(')') {
+        parameters.push(reader.java_type_signature()?);
+    }
+    reader.position += 1;
+    let result = if reader.peek() == Some('V') {
+        reader.position += 1;
+        None
+    } else {
+        Some(reader.java_type_signature()?)
+    };
+    let mut throws = Vec::new();
+    while reader.peek() == Some('^') {
+        reader.position += 1;
+        throws.push(reader.reference_type_signature()?);
+    }
+    reader.end()?;
+    Ok(MethodSignature { type_parameters, parameters, result, throws })
+}
+
+/// Parses the `Signature` attribute of a field, record component or local variable.
+pub fn parse_field_signature(signature: &str) -> Result<ReferenceTypeSignature, ParseError> {
+    let mut reader = Reader::new(signature);
+    let reference = reader.reference_type_signature()?;
+    reader.end()?;
+    Ok(reference)
+}
@@ -1,4 +1,5 @@
 use super::file;
+use super::parse_error::ParseError;
 
 #[derive(Debug, Clone)]
 pub struct ExceptionTableEntry {
@@ -9,32 +10,40 @@ pub struct ExceptionTableEntry {
 }
 
 impl ExceptionTableEntry {
+    pub fn new(start_pc: u16, end_pc: u16, handler_pc: u16, catch_type: u16) -> ExceptionTableEntry {
+        ExceptionTableEntry { start_pc, end_pc, handler_pc, catch_type }
+    }
+
+    pub fn write_file(&self, file: &mut file::File) {
+        file.put_u2(self.start_pc);
+        file.put_u2(self.end_pc);
+        file.put_u2(self.handler_pc);
+        file.put_u2(self.catch_type);
+    }
 
-    #[allow(dead_code)]
-    fn get_start_pc(&self) -> u16 {
+    pub fn get_start_pc(&self) -> u16 {
         self.start_pc
     }
 
-    #[allow(dead_code)]
-    fn get_end_pc(&self) -> u16 {
+    pub fn get_end_pc(&self) -> u16 {
         self.end_pc
     }
 
-    #[allow(dead_code)]
-    fn get_handler_pc(&self) -> u16 {
+    pub fn get_handler_pc(&self) -> u16 {
         self.handler_pc
     }
 
-    #[allow(dead_code)]
-    fn get_catch_type(&self) -> u16 {
+    pub fn get_catch_type(&self) -> u16 {
         self.catch_type
     }
 }
 
-pub fn parse_file(file: &mut file::File) -> ExceptionTableEntry {
-    ExceptionTableEntry { start_pc: file.get_u2(), end_pc: file.get_u2(), handler_pc: file.get_u2(), catch_type: file.get_u2() }
+pub fn parse_file(file: &mut file::File) -> Result<ExceptionTableEntry, ParseError> {
+    Ok(ExceptionTableEntry { start_pc: file.get_u2()?, end_pc: file.get_u2()?, handler_pc: file.get_u2()?, catch_type: file.get_u2()? })
 }
 
-pub fn parse_range(file: &mut file::File, range: usize) -> Vec<ExceptionTableEntry> {
-    (0..range).into_iter().map(|_f| {parse_file(file)}).collect()
-}
\ No newline at end of file
+pub fn parse_range(file: &mut file::File, range: usize) -> Result<Vec<ExceptionTableEntry>, ParseError> {
+    (0..range).map(|index| {
+        parse_file(file).map_err(|e| e.within(format!("exception table entry #{}", index)))
+    }).collect()
+}
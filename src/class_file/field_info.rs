@@ -1,5 +1,9 @@
 use super::file;
+use super::parse_error::ParseError;
+use super::annotation;
+use super::ClassFile;
 use super::attribute_info;
+use super::access_flags::FieldAccessFlags;
 
 #[derive(Debug)]
 pub struct FieldInfo {
@@ -10,10 +14,17 @@ pub struct FieldInfo {
 }
 
 impl FieldInfo {
+    pub fn write_file(&self, file: &mut file::File) {
+        file.put_u2(self.access_flags);
+        file.put_u2(self.name_index);
+        file.put_u2(self.descriptor_index);
+        attribute_info::write_range(file, &self.attributes);
+    }
+
 
     #[allow(dead_code)]
-    pub fn get_access_flags(&self) -> u16 {
-        self.access_flags
+    pub fn get_access_flags(&self) -> FieldAccessFlags {
+        FieldAccessFlags::new(self.access_flags)
     }
 
     #[allow(dead_code)]
@@ -30,16 +41,22 @@ impl FieldInfo {
     pub fn get_attributes(&self) -> Vec<attribute_info::AttributeInfo> {
         self.attributes.clone()
     }
+
+    pub fn get_annotations(&self, class_file: &ClassFile) -> Result<annotation::Annotations, ParseError> {
+        annotation::collect_annotations(class_file, &self.attributes)
+    }
 }
 
-pub fn parse_file(file: &mut file::File) -> FieldInfo {
-    let access_flags = file.get_u2();
-    let name_index = file.get_u2();
-    let descriptor_index = file.get_u2();
-    let attributes_length = file.get_u2() as usize;
-    FieldInfo { access_flags, name_index, descriptor_index, attributes: attribute_info::parse_range(file, attributes_length) }
+pub fn parse_file(file: &mut file::File) -> Result<FieldInfo, ParseError> {
+    let access_flags = file.get_u2()?;
+    let name_index = file.get_u2()?;
+    let descriptor_index = file.get_u2()?;
+    let attributes_length = file.get_u2()? as usize;
+    Ok(FieldInfo { access_flags, name_index, descriptor_index, attributes: attribute_info::parse_range(file, attributes_length)? })
 }
 
-pub fn parse_range(file: &mut file::File, range: usize) -> Vec<FieldInfo> {
-    (0..range).into_iter().map(|_f| {parse_file(file)}).collect()
-}
\ No newline at end of file
+pub fn parse_range(file: &mut file::File, range: usize) -> Result<Vec<FieldInfo>, ParseError> {
+    (0..range).map(|index| {
+        parse_file(file).map_err(|e| e.within(format!("field #{}", index)))
+    }).collect()
+}
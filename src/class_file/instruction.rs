@@ -0,0 +1,957 @@
+use super::file;
+use super::parse_error::{ParseError, ParseErrorKind};
+
+/// A decoded bytecode instruction (JVMS §6.5). Operands are kept as they appear in the code:
+/// constant pool indices, local variable indices and branch offsets relative to the
+/// instruction's own pc. `wide` wraps the instruction it modifies.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Insn {
+    Nop,
+    AconstNull,
+    IconstM1,
+    Iconst0,
+    Iconst1,
+    Iconst2,
+    Iconst3,
+    Iconst4,
+    Iconst5,
+    Lconst0,
+    Lconst1,
+    Fconst0,
+    Fconst1,
+    Fconst2,
+    Dconst0,
+    Dconst1,
+    Bipush(i8),
+    Sipush(i16),
+    Ldc(u16),
+    LdcW(u16),
+    Ldc2W(u16),
+    Iload(u16),
+    Lload(u16),
+    Fload(u16),
+    Dload(u16),
+    Aload(u16),
+    Iload0,
+    Iload1,
+    Iload2,
+    Iload3,
+    Lload0,
+    Lload1,
+    Lload2,
+    Lload3,
+    Fload0,
+    Fload1,
+    Fload2,
+    Fload3,
+    Dload0,
+    Dload1,
+    Dload2,
+    Dload3,
+    Aload0,
+    Aload1,
+    Aload2,
+    Aload3,
+    Iaload,
+    Laload,
+    Faload,
+    Daload,
+    Aaload,
+    Baload,
+    Caload,
+    Saload,
+    Istore(u16),
+    Lstore(u16),
+    Fstore(u16),
+    Dstore(u16),
+    Astore(u16),
+    Istore0,
+    Istore1,
+    Istore2,
+    Istore3,
+    Lstore0,
+    Lstore1,
+    Lstore2,
+    Lstore3,
+    Fstore0,
+    Fstore1,
+    Fstore2,
+    Fstore3,
+    Dstore0,
+    Dstore1,
+    Dstore2,
+    Dstore3,
+    Astore0,
+    Astore1,
+    Astore2,
+    Astore3,
+    Iastore,
+    Lastore,
+    Fastore,
+    Dastore,
+    Aastore,
+    Bastore,
+    Castore,
+    Sastore,
+    Pop,
+    Pop2,
+    Dup,
+    DupX1,
+    DupX2,
+    Dup2,
+    Dup2X1,
+    Dup2X2,
+    Swap,
+    Iadd,
+    Ladd,
+    Fadd,
+    Dadd,
+    Isub,
+    Lsub,
+    Fsub,
+    Dsub,
+    Imul,
+    Lmul,
+    Fmul,
+    Dmul,
+    Idiv,
+    Ldiv,
+    Fdiv,
+    Ddiv,
+    Irem,
+    Lrem,
+    Frem,
+    Drem,
+    Ineg,
+    Lneg,
+    Fneg,
+    Dneg,
+    Ishl,
+    Lshl,
+    Ishr,
+    Lshr,
+    Iushr,
+    Lushr,
+    Iand,
+    Land,
+    Ior,
+    Lor,
+    Ixor,
+    Lxor,
+    Iinc { index: u16, constant: i16 },
+    I2l,
+    I2f,
+    I2d,
+    L2i,
+    L2f,
+    L2d,
+    F2i,
+    F2l,
+    F2d,
+    D2i,
+    D2l,
+    D2f,
+    I2b,
+    I2c,
+    I2s,
+    Lcmp,
+    Fcmpl,
+    Fcmpg,
+    Dcmpl,
+    Dcmpg,
+    Ifeq(i16),
+    Ifne(i16),
+    Iflt(i16),
+    Ifge(i16),
+    Ifgt(i16),
+    Ifle(i16),
+    IfIcmpeq(i16),
+    IfIcmpne(i16),
+    IfIcmplt(i16),
+    IfIcmpge(i16),
+    IfIcmpgt(i16),
+    IfIcmple(i16),
+    IfAcmpeq(i16),
+    IfAcmpne(i16),
+    Goto(i16),
+    Jsr(i16),
+    Ret(u16),
+    Tableswitch { default: i32, low: i32, high: i32, offsets: Vec<i32> },
+    Lookupswitch { default: i32, pairs: Vec<(i32, i32)> },
+    Ireturn,
+    Lreturn,
+    Freturn,
+    Dreturn,
+    Areturn,
+    Return,
+    Getstatic(u16),
+    Putstatic(u16),
+    Getfield(u16),
+    Putfield(u16),
+    Invokevirtual(u16),
+    Invokespecial(u16),
+    Invokestatic(u16),
+    Invokeinterface { index: u16, count: u8 },
+    Invokedynamic(u16),
+    New(u16),
+    Newarray(u8),
+    Anewarray(u16),
+    Arraylength,
+    Athrow,
+    Checkcast(u16),
+    Instanceof(u16),
+    Monitorenter,
+    Monitorexit,
+    Wide(Box<Insn>),
+    Multianewarray { index: u16, dimensions: u8 },
+    Ifnull(i16),
+    Ifnonnull(i16),
+    GotoW(i32),
+    JsrW(i32),
+}
+
+impl Insn {
+    pub fn get_opcode(&self) -> u8 {
+        match self {
+            Insn::Nop => 0,
+            Insn::AconstNull => 1,
+            Insn::IconstM1 => 2,
+            Insn::Iconst0 => 3,
+            Insn::Iconst1 => 4,
+            Insn::Iconst2 => 5,
+            Insn::Iconst3 => 6,
+            Insn::Iconst4 => 7,
+            Insn::Iconst5 => 8,
+            Insn::Lconst0 => 9,
+            Insn::Lconst1 => 10,
+            Insn::Fconst0 => 11,
+            Insn::Fconst1 => 12,
+            Insn::Fconst2 => 13,
+            Insn::Dconst0 => 14,
+            Insn::Dconst1 => 15,
+            Insn::Bipush(_) => 16,
+            Insn::Sipush(_) => 17,
+            Insn::Ldc(_) => 18,
+            Insn::LdcW(_) => 19,
+            Insn::Ldc2W(_) => 20,
+            Insn::Iload(_) => 21,
+            Insn::Lload(_) => 22,
+            Insn::Fload(_) => 23,
+            Insn::Dload(_) => 24,
+            Insn::Aload(_) => 25,
+            Insn::Iload0 => 26,
+            Insn::Iload1 => 27,
+            Insn::Iload2 => 28,
+            Insn::Iload3 => 29,
+            Insn::Lload0 => 30,
+            Insn::Lload1 => 31,
+            Insn::Lload2 => 32,
+            Insn::Lload3 => 33,
+            Insn::Fload0 => 34,
+            Insn::Fload1 => 35,
+            Insn::Fload2 => 36,
+            Insn::Fload3 => 37,
+            Insn::Dload0 => 38,
+            Insn::Dload1 => 39,
+            Insn::Dload2 => 40,
+            Insn::Dload3 => 41,
+            Insn::Aload0 => 42,
+            Insn::Aload1 => 43,
+            Insn::Aload2 => 44,
+            Insn::Aload3 => 45,
+            Insn::Iaload => 46,
+            Insn::Laload => 47,
+            Insn::Faload => 48,
+            Insn::Daload => 49,
+            Insn::Aaload => 50,
+            Insn::Baload => 51,
+            Insn::Caload => 52,
+            Insn::Saload => 53,
+            Insn::Istore(_) => 54,
+            Insn::Lstore(_) => 55,
+            Insn::Fstore(_) => 56,
+            Insn::Dstore(_) => 57,
+            Insn::Astore(_) => 58,
+            Insn::Istore0 => 59,
+            Insn::Istore1 => 60,
+            Insn::Istore2 => 61,
+            Insn::Istore3 => 62,
+            Insn::Lstore0 => 63,
+            Insn::Lstore1 => 64,
+            Insn::Lstore2 => 65,
+            Insn::Lstore3 => 66,
+            Insn::Fstore0 => 67,
+            Insn::Fstore1 => 68,
+            Insn::Fstore2 => 69,
+            Insn::Fstore3 => 70,
+            Insn::Dstore0 => 71,
+            Insn::Dstore1 => 72,
+            Insn::Dstore2 => 73,
+            Insn::Dstore3 => 74,
+            Insn::Astore0 => 75,
+            Insn::Astore1 => 76,
+            Insn::Astore2 => 77,
+            Insn::Astore3 => 78,
+            Insn::Iastore => 79,
+            Insn::Lastore => 80,
+            Insn::Fastore => 81,
+            Insn::Dastore => 82,
+            Insn::Aastore => 83,
+            Insn::Bastore => 84,
+            Insn::Castore => 85,
+            Insn::Sastore => 86,
+            Insn::Pop => 87,
+            Insn::Pop2 => 88,
+            Insn::Dup => 89,
+            Insn::DupX1 => 90,
+            Insn::DupX2 => 91,
+            Insn::Dup2 => 92,
+            Insn::Dup2X1 => 93,
+            Insn::Dup2X2 => 94,
+            Insn::Swap => 95,
+            Insn::Iadd => 96,
+            Insn::Ladd => 97,
+            Insn::Fadd => 98,
+            Insn::Dadd => 99,
+            Insn::Isub => 100,
+            Insn::Lsub => 101,
+            Insn::Fsub => 102,
+            Insn::Dsub => 103,
+            Insn::Imul => 104,
+            Insn::Lmul => 105,
+            Insn::Fmul => 106,
+            Insn::Dmul => 107,
+            Insn::Idiv => 108,
+            Insn::Ldiv => 109,
+            Insn::Fdiv => 110,
+            Insn::Ddiv => 111,
+            Insn::Irem => 112,
+            Insn::Lrem => 113,
+            Insn::Frem => 114,
+            Insn::Drem => 115,
+            Insn::Ineg => 116,
+            Insn::Lneg => 117,
+            Insn::Fneg => 118,
+            Insn::Dneg => 119,
+            Insn::Ishl => 120,
+            Insn::Lshl => 121,
+            Insn::Ishr => 122,
+            Insn::Lshr => 123,
+            Insn::Iushr => 124,
+            Insn::Lushr => 125,
+            Insn::Iand => 126,
+            Insn::Land => 127,
+            Insn::Ior => 128,
+            Insn::Lor => 129,
+            Insn::Ixor => 130,
+            Insn::Lxor => 131,
+            Insn::Iinc { .. } => 132,
+            Insn::I2l => 133,
+            Insn::I2f => 134,
+            Insn::I2d => 135,
+            Insn::L2i => 136,
+            Insn::L2f => 137,
+            Insn::L2d => 138,
+            Insn::F2i => 139,
+            Insn::F2l => 140,
+            Insn::F2d => 141,
+            Insn::D2i => 142,
+            Insn::D2l => 143,
+            Insn::D2f => 144,
+            Insn::I2b => 145,
+            Insn::I2c => 146,
+            Insn::I2s => 147,
+            Insn::Lcmp => 148,
+            Insn::Fcmpl => 149,
+            Insn::Fcmpg => 150,
+            Insn::Dcmpl => 151,
+            Insn::Dcmpg => 152,
+            Insn::Ifeq(_) => 153,
+            Insn::Ifne(_) => 154,
+            Insn::Iflt(_) => 155,
+            Insn::Ifge(_) => 156,
+            Insn::Ifgt(_) => 157,
+            Insn::Ifle(_) => 158,
+            Insn::IfIcmpeq(_) => 159,
+            Insn::IfIcmpne(_) => 160,
+            Insn::IfIcmplt(_) => 161,
+            Insn::IfIcmpge(_) => 162,
+            Insn::IfIcmpgt(_) => 163,
+            Insn::IfIcmple(_) => 164,
+            Insn::IfAcmpeq(_) => 165,
+            Insn::IfAcmpne(_) => 166,
+            Insn::Goto(_) => 167,
+            Insn::Jsr(_) => 168,
+            Insn::Ret(_) => 169,
+            Insn::Tableswitch { .. } => 170,
+            Insn::Lookupswitch { .. } => 171,
+            Insn::Ireturn => 172,
+            Insn::Lreturn => 173,
+            Insn::Freturn => 174,
+            Insn::Dreturn => 175,
+            Insn::Areturn => 176,
+            Insn::Return => 177,
+            Insn::Getstatic(_) => 178,
+            Insn::Putstatic(_) => 179,
+            Insn::Getfield(_) => 180,
+            Insn::Putfield(_) => 181,
+            Insn::Invokevirtual(_) => 182,
+            Insn::Invokespecial(_) => 183,
+            Insn::Invokestatic(_) => 184,
+            Insn::Invokeinterface { .. } => 185,
+            Insn::Invokedynamic(_) => 186,
+            Insn::New(_) => 187,
+            Insn::Newarray(_) => 188,
+            Insn::Anewarray(_) => 189,
+            Insn::Arraylength => 190,
+            Insn::Athrow => 191,
+            Insn::Checkcast(_) => 192,
+            Insn::Instanceof(_) => 193,
+            Insn::Monitorenter => 194,
+            Insn::Monitorexit => 195,
+            Insn::Wide(_) => 196,
+            Insn::Multianewarray { .. } => 197,
+            Insn::Ifnull(_) => 198,
+            Insn::Ifnonnull(_) => 199,
+            Insn::GotoW(_) => 200,
+            Insn::JsrW(_) => 201,
+        }
+    }
+
+    pub fn get_mnemonic(&self) -> &'static str {
+        match self {
+            Insn::Nop => "nop",
+            Insn::AconstNull => "aconst_null",
+            Insn::IconstM1 => "iconst_m1",
+            Insn::Iconst0 => "iconst_0",
+            Insn::Iconst1 => "iconst_1",
+            Insn::Iconst2 => "iconst_2",
+            Insn::Iconst3 => "iconst_3",
+            Insn::Iconst4 => "iconst_4",
+            Insn::Iconst5 => "iconst_5",
+            Insn::Lconst0 => "lconst_0",
+            Insn::Lconst1 => "lconst_1",
+            Insn::Fconst0 => "fconst_0",
+            Insn::Fconst1 => "fconst_1",
+            Insn::Fconst2 => "fconst_2",
+            Insn::Dconst0 => "dconst_0",
+            Insn::Dconst1 => "dconst_1",
+            Insn::Bipush(_) => "bipush",
+            Insn::Sipush(_) => "sipush",
+            Insn::Ldc(_) => "ldc",
+            Insn::LdcW(_) => "ldc_w",
+            Insn::Ldc2W(_) => "ldc2_w",
+            Insn::Iload(_) => "iload",
+            Insn::Lload(_) => "lload",
+            Insn::Fload(_) => "fload",
+            Insn::Dload(_) => "dload",
+            Insn::Aload(_) => "aload",
+            Insn::Iload0 => "iload_0",
+            Insn::Iload1 => "iload_1",
+            Insn::Iload2 => "iload_2",
+            Insn::Iload3 => "iload_3",
+            Insn::Lload0 => "lload_0",
+            Insn::Lload1 => "lload_1",
+            Insn::Lload2 => "lload_2",
+            Insn::Lload3 => "lload_3",
+            Insn::Fload0 => "fload_0",
+            Insn::Fload1 => "fload_1",
+            Insn::Fload2 => "fload_2",
+            Insn::Fload3 => "fload_3",
+            Insn::Dload0 => "dload_0",
+            Insn::Dload1 => "dload_1",
+            Insn::Dload2 => "dload_2",
+            Insn::Dload3 => "dload_3",
+            Insn::Aload0 => "aload_0",
+            Insn::Aload1 => "aload_1",
+            Insn::Aload2 => "aload_2",
+            Insn::Aload3 => "aload_3",
+            Insn::Iaload => "iaload",
+            Insn::Laload => "laload",
+            Insn::Faload => "faload",
+            Insn::Daload => "daload",
+            Insn::Aaload => "aaload",
+            Insn::Baload => "baload",
+            Insn::Caload => "caload",
+            Insn::Saload => "saload",
+            Insn::Istore(_) => "istore",
+            Insn::Lstore(_) => "lstore",
+            Insn::Fstore(_) => "fstore",
+            Insn::Dstore(_) => "dstore",
+            Insn::Astore(_) => "astore",
+            Insn::Istore0 => "istore_0",
+            Insn::Istore1 => "istore_1",
+            Insn::Istore2 => "istore_2",
+            Insn::Istore3 => "istore_3",
+            Insn::Lstore0 => "lstore_0",
+            Insn::Lstore1 => "lstore_1",
+            Insn::Lstore2 => "lstore_2",
+            Insn::Lstore3 => "lstore_3",
+            Insn::Fstore0 => "fstore_0",
+            Insn::Fstore1 => "fstore_1",
+            Insn::Fstore2 => "fstore_2",
+            Insn::Fstore3 => "fstore_3",
+            Insn::Dstore0 => "dstore_0",
+            Insn::Dstore1 => "dstore_1",
+            Insn::Dstore2 => "dstore_2",
+            Insn::Dstore3 => "dstore_3",
+            Insn::Astore0 => "astore_0",
+            Insn::Astore1 => "astore_1",
+            Insn::Astore2 => "astore_2",
+            Insn::Astore3 => "astore_3",
+            Insn::Iastore => "iastore",
+            Insn::Lastore => "lastore",
+            Insn::Fastore => "fastore",
+            Insn::Dastore => "dastore",
+            Insn::Aastore => "aastore",
+            Insn::Bastore => "bastore",
+            Insn::Castore => "castore",
+            Insn::Sastore => "sastore",
+            Insn::Pop => "pop",
+            Insn::Pop2 => "pop2",
+            Insn::Dup => "dup",
+            Insn::DupX1 => "dup_x1",
+            Insn::DupX2 => "dup_x2",
+            Insn::Dup2 => "dup2",
+            Insn::Dup2X1 => "dup2_x1",
+            Insn::Dup2X2 => "dup2_x2",
+            Insn::Swap => "swap",
+            Insn::Iadd => "iadd",
+            Insn::Ladd => "ladd",
+            Insn::Fadd => "fadd",
+            Insn::Dadd => "dadd",
+            Insn::Isub => "isub",
+            Insn::Lsub => "lsub",
+            Insn::Fsub => "fsub",
+            Insn::Dsub => "dsub",
+            Insn::Imul => "imul",
+            Insn::Lmul => "lmul",
+            Insn::Fmul => "fmul",
+            Insn::Dmul => "dmul",
+            Insn::Idiv => "idiv",
+            Insn::Ldiv => "ldiv",
+            Insn::Fdiv => "fdiv",
+            Insn::Ddiv => "ddiv",
+            Insn::Irem => "irem",
+            Insn::Lrem => "lrem",
+            Insn::Frem => "frem",
+            Insn::Drem => "drem",
+            Insn::Ineg => "ineg",
+            Insn::Lneg => "lneg",
+            Insn::Fneg => "fneg",
+            Insn::Dneg => "dneg",
+            Insn::Ishl => "ishl",
+            Insn::Lshl => "lshl",
+            Insn::Ishr => "ishr",
+            Insn::Lshr => "lshr",
+            Insn::Iushr => "iushr",
+            Insn::Lushr => "lushr",
+            Insn::Iand => "iand",
+            Insn::Land => "land",
+            Insn::Ior => "ior",
+            Insn::Lor => "lor",
+            Insn::Ixor => "ixor",
+            Insn::Lxor => "lxor",
+            Insn::Iinc { .. } => "iinc",
+            Insn::I2l => "i2l",
+            Insn::I2f => "i2f",
+            Insn::I2d => "i2d",
+            Insn::L2i => "l2i",
+            Insn::L2f => "l2f",
+            Insn::L2d => "l2d",
+            Insn::F2i => "f2i",
+            Insn::F2l => "f2l",
+            Insn::F2d => "f2d",
+            Insn::D2i => "d2i",
+            Insn::D2l => "d2l",
+            Insn::D2f => "d2f",
+            Insn::I2b => "i2b",
+            Insn::I2c => "i2c",
+            Insn::I2s => "i2s",
+            Insn::Lcmp => "lcmp",
+            Insn::Fcmpl => "fcmpl",
+            Insn::Fcmpg => "fcmpg",
+            Insn::Dcmpl => "dcmpl",
+            Insn::Dcmpg => "dcmpg",
+            Insn::Ifeq(_) => "ifeq",
+            Insn::Ifne(_) => "ifne",
+            Insn::Iflt(_) => "iflt",
+            Insn::Ifge(_) => "ifge",
+            Insn::Ifgt(_) => "ifgt",
+            Insn::Ifle(_) => "ifle",
+            Insn::IfIcmpeq(_) => "if_icmpeq",
+            Insn::IfIcmpne(_) => "if_icmpne",
+            Insn::IfIcmplt(_) => "if_icmplt",
+            Insn::IfIcmpge(_) => "if_icmpge",
+            Insn::IfIcmpgt(_) => "if_icmpgt",
+            Insn::IfIcmple(_) => "if_icmple",
+            Insn::IfAcmpeq(_) => "if_acmpeq",
+            Insn::IfAcmpne(_) => "if_acmpne",
+            Insn::Goto(_) => "goto",
+            Insn::Jsr(_) => "jsr",
+            Insn::Ret(_) => "ret",
+            Insn::Tableswitch { .. } => "tableswitch",
+            Insn::Lookupswitch { .. } => "lookupswitch",
+            Insn::Ireturn => "ireturn",
+            Insn::Lreturn => "lreturn",
+            Insn::Freturn => "freturn",
+            Insn::Dreturn => "dreturn",
+            Insn::Areturn => "areturn",
+            Insn::Return => "return",
+            Insn::Getstatic(_) => "getstatic",
+            Insn::Putstatic(_) => "putstatic",
+            Insn::Getfield(_) => "getfield",
+            Insn::Putfield(_) => "putfield",
+            Insn::Invokevirtual(_) => "invokevirtual",
+            Insn::Invokespecial(_) => "invokespecial",
+            Insn::Invokestatic(_) => "invokestatic",
+            Insn::Invokeinterface { .. } => "invokeinterface",
+            Insn::Invokedynamic(_) => "invokedynamic",
+            Insn::New(_) => "new",
+            Insn::Newarray(_) => "newarray",
+            Insn::Anewarray(_) => "anewarray",
+            Insn::Arraylength => "arraylength",
+            Insn::Athrow => "athrow",
+            Insn::Checkcast(_) => "checkcast",
+            Insn::Instanceof(_) => "instanceof",
+            Insn::Monitorenter => "monitorenter",
+            Insn::Monitorexit => "monitorexit",
+            Insn::Wide(_) => "wide",
+            Insn::Multianewarray { .. } => "multianewarray",
+            Insn::Ifnull(_) => "ifnull",
+            Insn::Ifnonnull(_) => "ifnonnull",
+            Insn::GotoW(_) => "goto_w",
+            Insn::JsrW(_) => "jsr_w",
+        }
+    }
+
+    /// The local variable the instruction reads or writes, including the implicit index of `iload_1` and friends.
+    pub fn get_local_index(&self) -> Option<u16> {
+        match self {
+            Insn::Iload(index) | Insn::Lload(index) | Insn::Fload(index) | Insn::Dload(index) | Insn::Aload(index)
+            | Insn::Istore(index) | Insn::Lstore(index) | Insn::Fstore(index) | Insn::Dstore(index) | Insn::Astore(index)
+            | Insn::Ret(index) | Insn::Iinc { index, .. } => Some(*index),
+            Insn::Iload0 | Insn::Lload0 | Insn::Fload0 | Insn::Dload0 | Insn::Aload0
+            | Insn::Istore0 | Insn::Lstore0 | Insn::Fstore0 | Insn::Dstore0 | Insn::Astore0 => Some(0),
+            Insn::Iload1 | Insn::Lload1 | Insn::Fload1 | Insn::Dload1 | Insn::Aload1
+            | Insn::Istore1 | Insn::Lstore1 | Insn::Fstore1 | Insn::Dstore1 | Insn::Astore1 => Some(1),
+            Insn::Iload2 | Insn::Lload2 | Insn::Fload2 | Insn::Dload2 | Insn::Aload2
+            | Insn::Istore2 | Insn::Lstore2 | Insn::Fstore2 | Insn::Dstore2 | Insn::Astore2 => Some(2),
+            Insn::Iload3 | Insn::Lload3 | Insn::Fload3 | Insn::Dload3 | Insn::Aload3
+            | Insn::Istore3 | Insn::Lstore3 | Insn::Fstore3 | Insn::Dstore3 | Insn::Astore3 => Some(3),
+            Insn::Wide(insn) => insn.get_local_index(),
+            _ => None,
+        }
+    }
+
+    /// The constant pool entry the instruction refers to.
+    pub fn get_constant_index(&self) -> Option<u16> {
+        match self {
+            Insn::Ldc(index) | Insn::LdcW(index) | Insn::Ldc2W(index)
+            | Insn::Getstatic(index) | Insn::Putstatic(index) | Insn::Getfield(index) | Insn::Putfield(index)
+            | Insn::Invokevirtual(index) | Insn::Invokespecial(index) | Insn::Invokestatic(index)
+            | Insn::Invokeinterface { index, .. } | Insn::Invokedynamic(index)
+            | Insn::New(index) | Insn::Anewarray(index) | Insn::Checkcast(index) | Insn::Instanceof(index)
+            | Insn::Multianewarray { index, .. } => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// The branch offset of conditional branches, `goto` and `jsr`, relative to the instruction's pc.
+    pub fn get_branch_offset(&self) -> Option<i32> {
+        match self {
+            Insn::Ifeq(offset) | Insn::Ifne(offset) | Insn::Iflt(offset) | Insn::Ifge(offset) | Insn::Ifgt(offset) | Insn::Ifle(offset)
+            | Insn::IfIcmpeq(offset) | Insn::IfIcmpne(offset) | Insn::IfIcmplt(offset) | Insn::IfIcmpge(offset)
+            | Insn::IfIcmpgt(offset) | Insn::IfIcmple(offset) | Insn::IfAcmpeq(offset) | Insn::IfAcmpne(offset)
+            | Insn::Goto(offset) | Insn::Jsr(offset) | Insn::Ifnull(offset) | Insn::Ifnonnull(offset) => Some(*offset as i32),
+            Insn::GotoW(offset) | Insn::JsrW(offset) => Some(*offset),
+            _ => None,
+        }
+    }
+
+    /// Every pc the instruction at `pc` can jump to, not counting the fall through to the next instruction.
+    pub fn get_branch_targets(&self, pc: usize) -> Vec<i64> {
+        let target = |offset: i32| pc as i64 + offset as i64;
+        match self {
+            Insn::Tableswitch { default, offsets, .. } => {
+                offsets.iter().chain(std::iter::once(default)).map(|offset| target(*offset)).collect()
+            }
+            Insn::Lookupswitch { default, pairs } => {
+                pairs.iter().map(|(_key, offset)| offset).chain(std::iter::once(default)).map(|offset| target(*offset)).collect()
+            }
+            _ => self.get_branch_offset().map(target).into_iter().collect(),
+        }
+    }
+}
+
+/// Decodes the instruction at the file pointer. Switch padding is aligned relative to the start of
+/// the file, which therefore has to start at the first byte of the code array.
+pub fn parse_file(file: &mut file::File) -> Result<Insn, ParseError> {
+    let opcode_offset = file.get_pointer();
+    let opcode = file.get_u1()?;
+    Ok(match opcode {
+        0 => Insn::Nop,
+        1 => Insn::AconstNull,
+        2 => Insn::IconstM1,
+        3 => Insn::Iconst0,
+        4 => Insn::Iconst1,
+        5 => Insn::Iconst2,
+        6 => Insn::Iconst3,
+        7 => Insn::Iconst4,
+        8 => Insn::Iconst5,
+        9 => Insn::Lconst0,
+        10 => Insn::Lconst1,
+        11 => Insn::Fconst0,
+        12 => Insn::Fconst1,
+        13 => Insn::Fconst2,
+        14 => Insn::Dconst0,
+        15 => Insn::Dconst1,
+        19 => Insn::LdcW(file.get_u2()?),
+        20 => Insn::Ldc2W(file.get_u2()?),
+        21 => Insn::Iload(file.get_u1()? as u16),
+        22 => Insn::Lload(file.get_u1()? as u16),
+        23 => Insn::Fload(file.get_u1()? as u16),
+        24 => Insn::Dload(file.get_u1()? as u16),
+        25 => Insn::Aload(file.get_u1()? as u16),
+        26 => Insn::Iload0,
+        27 => Insn::Iload1,
+        28 => Insn::Iload2,
+        29 => Insn::Iload3,
+        30 => Insn::Lload0,
+        31 => Insn::Lload1,
+        32 => Insn::Lload2,
+        33 => Insn::Lload3,
+        34 => Insn::Fload0,
+        35 => Insn::Fload1,
+        36 => Insn::Fload2,
+        37 => Insn::Fload3,
+        38 => Insn::Dload0,
+        39 => Insn::Dload1,
+        40 => Insn::Dload2,
+        41 => Insn::Dload3,
+        42 => Insn::Aload0,
+        43 => Insn::Aload1,
+        44 => Insn::Aload2,
+        45 => Insn::Aload3,
+        46 => Insn::Iaload,
+        47 => Insn::Laload,
+        48 => Insn::Faload,
+        49 => Insn::Daload,
+        50 => Insn::Aaload,
+        51 => Insn::Baload,
+        52 => Insn::Caload,
+        53 => Insn::Saload,
+        54 => Insn::Istore(file.get_u1()? as u16),
+        55 => Insn::Lstore(file.get_u1()? as u16),
+        56 => Insn::Fstore(file.get_u1()? as u16),
+        57 => Insn::Dstore(file.get_u1()? as u16),
+        58 => Insn::Astore(file.get_u1()? as u16),
+        59 => Insn::Istore0,
+        60 => Insn::Istore1,
+        61 => Insn::Istore2,
+        62 => Insn::Istore3,
+        63 => Insn::Lstore0,
+        64 => Insn::Lstore1,
+        65 => Insn::Lstore2,
+        66 => Insn::Lstore3,
+        67 => Insn::Fstore0,
+        68 => Insn::Fstore1,
+        69 => Insn::Fstore2,
+        70 => Insn::Fstore3,
+        71 => Insn::Dstore0,
+        72 => Insn::Dstore1,
+        73 => Insn::Dstore2,
+        74 => Insn::Dstore3,
+        75 => Insn::Astore0,
+        76 => Insn::Astore1,
+        77 => Insn::Astore2,
+        78 => Insn::Astore3,
+        79 => Insn::Iastore,
+        80 => Insn::Lastore,
+        81 => Insn::Fastore,
+        82 => Insn::Dastore,
+        83 => Insn::Aastore,
+        84 => Insn::Bastore,
+        85 => Insn::Castore,
+        86 => Insn::Sastore,
+        87 => Insn::Pop,
+        88 => Insn::Pop2,
+        89 => Insn::Dup,
+        90 => Insn::DupX1,
+        91 => Insn::DupX2,
+        92 => Insn::Dup2,
+        93 => Insn::Dup2X1,
+        94 => Insn::Dup2X2,
+        95 => Insn::Swap,
+        96 => Insn::Iadd,
+        97 => Insn::Ladd,
+        98 => Insn::Fadd,
+        99 => Insn::Dadd,
+        100 => Insn::Isub,
+        101 => Insn::Lsub,
+        102 => Insn::Fsub,
+        103 => Insn::Dsub,
+        104 => Insn::Imul,
+        105 => Insn::Lmul,
+        106 => Insn::Fmul,
+        107 => Insn::Dmul,
+        108 => Insn::Idiv,
+        109 => Insn::Ldiv,
+        110 => Insn::Fdiv,
+        111 => Insn::Ddiv,
+        112 => Insn::Irem,
+        113 => Insn::Lrem,
+        114 => Insn::Frem,
+        115 => Insn::Drem,
+        116 => Insn::Ineg,
+        117 => Insn::Lneg,
+        118 => Insn::Fneg,
+        119 => Insn::Dneg,
+        120 => Insn::Ishl,
+        121 => Insn::Lshl,
+        122 => Insn::Ishr,
+        123 => Insn::Lshr,
+        124 => Insn::Iushr,
+        125 => Insn::Lushr,
+        126 => Insn::Iand,
+        127 => Insn::Land,
+        128 => Insn::Ior,
+        129 => Insn::Lor,
+        130 => Insn::Ixor,
+        131 => Insn::Lxor,
+        133 => Insn::I2l,
+        134 => Insn::I2f,
+        135 => Insn::I2d,
+        136 => Insn::L2i,
+        137 => Insn::L2f,
+        138 => Insn::L2d,
+        139 => Insn::F2i,
+        140 => Insn::F2l,
+        141 => Insn::F2d,
+        142 => Insn::D2i,
+        143 => Insn::D2l,
+        144 => Insn::D2f,
+        145 => Insn::I2b,
+        146 => Insn::I2c,
+        147 => Insn::I2s,
+        148 => Insn::Lcmp,
+        149 => Insn::Fcmpl,
+        150 => Insn::Fcmpg,
+        151 => Insn::Dcmpl,
+        152 => Insn::Dcmpg,
+        153 => Insn::Ifeq(file.get_u2()? as i16),
+        154 => Insn::Ifne(file.get_u2()? as i16),
+        155 => Insn::Iflt(file.get_u2()? as i16),
+        156 => Insn::Ifge(file.get_u2()? as i16),
+        157 => Insn::Ifgt(file.get_u2()? as i16),
+        158 => Insn::Ifle(file.get_u2()? as i16),
+        159 => Insn::IfIcmpeq(file.get_u2()? as i16),
+        160 => Insn::IfIcmpne(file.get_u2()? as i16),
+        161 => Insn::IfIcmplt(file.get_u2()? as i16),
+        162 => Insn::IfIcmpge(file.get_u2()? as i16),
+        163 => Insn::IfIcmpgt(file.get_u2()? as i16),
+        164 => Insn::IfIcmple(file.get_u2()? as i16),
+        165 => Insn::IfAcmpeq(file.get_u2()? as i16),
+        166 => Insn::IfAcmpne(file.get_u2()? as i16),
+        167 => Insn::Goto(file.get_u2()? as i16),
+        168 => Insn::Jsr(file.get_u2()? as i16),
+        169 => Insn::Ret(file.get_u1()? as u16),
+        172 => Insn::Ireturn,
+        173 => Insn::Lreturn,
+        174 => Insn::Freturn,
+        175 => Insn::Dreturn,
+        176 => Insn::Areturn,
+        177 => Insn::Return,
+        178 => Insn::Getstatic(file.get_u2()?),
+        179 => Insn::Putstatic(file.get_u2()?),
+        180 => Insn::Getfield(file.get_u2()?),
+        181 => Insn::Putfield(file.get_u2()?),
+        182 => Insn::Invokevirtual(file.get_u2()?),
+        183 => Insn::Invokespecial(file.get_u2()?),
+        184 => Insn::Invokestatic(file.get_u2()?),
+        187 => Insn::New(file.get_u2()?),
+        189 => Insn::Anewarray(file.get_u2()?),
+        190 => Insn::Arraylength,
+        191 => Insn::Athrow,
+        192 => Insn::Checkcast(file.get_u2()?),
+        193 => Insn::Instanceof(file.get_u2()?),
+        194 => Insn::Monitorenter,
+        195 => Insn::Monitorexit,
+        198 => Insn::Ifnull(file.get_u2()? as i16),
+        199 => Insn::Ifnonnull(file.get_u2()? as i16),
+        16 => Insn::Bipush(file.get_u1()? as i8),
+        17 => Insn::Sipush(file.get_u2()? as i16),
+        18 => Insn::Ldc(file.get_u1()? as u16),
+        132 => Insn::Iinc { index: file.get_u1()? as u16, constant: file.get_u1()? as i8 as i16 },
+        170 => {
+            skip_padding(file)?;
+            let default = file.get_u4()? as i32;
+            let low = file.get_u4()? as i32;
+            let high = file.get_u4()? as i32;
+            let offsets = (low as i64..=high as i64).map(|_key| Ok(file.get_u4()? as i32)).collect::<Result<Vec<i32>, ParseError>>()?;
+            Insn::Tableswitch { default, low, high, offsets }
+        }
+        171 => {
+            skip_padding(file)?;
+            let default = file.get_u4()? as i32;
+            let npairs = file.get_u4()? as i32;
+            let pairs = (0..npairs.max(0)).map(|_pair| Ok((file.get_u4()? as i32, file.get_u4()? as i32))).collect::<Result<Vec<(i32, i32)>, ParseError>>()?;
+            Insn::Lookupswitch { default, pairs }
+        }
+        185 => {
+            let index = file.get_u2()?;
+            let count = file.get_u1()?;
+            file.get_u1()?;
+            Insn::Invokeinterface { index, count }
+        }
+        186 => {
+            let index = file.get_u2()?;
+            file.get_u2()?;
+            Insn::Invokedynamic(index)
+        }
+        188 => Insn::Newarray(file.get_u1()?),
+        196 => {
+            let modified_offset = file.get_pointer();
+            let modified = file.get_u1()?;
+            let index = file.get_u2()?;
+            Insn::Wide(Box::new(match modified {
+                21 => Insn::Iload(index),
+                22 => Insn::Lload(index),
+                23 => Insn::Fload(index),
+                24 => Insn::Dload(index),
+                25 => Insn::Aload(index),
+                54 => Insn::Istore(index),
+                55 => Insn::Lstore(index),
+                56 => Insn::Fstore(index),
+                57 => Insn::Dstore(index),
+                58 => Insn::Astore(index),
+                169 => Insn::Ret(index),
+                132 => Insn::Iinc { index, constant: file.get_u2()? as i16 },
+                _ => return Err(ParseError::at(modified_offset, ParseErrorKind::InvalidTag { structure: "opcode after wide", tag: modified })),
+            }))
+        }
+        197 => Insn::Multianewarray { index: file.get_u2()?, dimensions: file.get_u1()? },
+        200 => Insn::GotoW(file.get_u4()? as i32),
+        201 => Insn::JsrW(file.get_u4()? as i32),
+        _ => return Err(ParseError::at(opcode_offset, ParseErrorKind::InvalidTag { structure: "opcode", tag: opcode })),
+    })
+}
+
+fn skip_padding(file: &mut file::File) -> Result<(), ParseError> {
+    while !file.get_pointer().is_multiple_of(4) {
+        file.get_u1()?;
+    }
+    Ok(())
+}
+
+/// Decodes a whole code array into its instructions, each paired with its pc.
+pub fn parse_code(code: &[u8]) -> Result<Vec<(usize, Insn)>, ParseError> {
+    let mut file = file::File::new(code.to_vec());
+    let mut instructions = Vec::new();
+    while file.has_next() {
+        let pc = file.get_pointer();
+        let insn = parse_file(&mut file).map_err(|e| e.within(format!("instruction at pc {}", pc)))?;
+        instructions.push((pc, insn));
+    }
+    Ok(instructions)
+}
@@ -1,11 +1,21 @@
 use super::util::file;
 use super::jvm::ContainsMethods;
 pub mod const_type;
+pub mod access_flags;
 pub mod attribute_info;
+pub mod attribute;
+pub mod annotation;
 pub mod field_info;
 pub mod method_info;
 pub mod exception_table_entry;
 pub mod code_attribute;
+pub mod instruction;
+pub mod parse_error;
+pub mod class_format_error;
+pub mod validate;
+pub mod mutf8;
+pub mod descriptor;
+use parse_error::{ParseError, ParseErrorKind};
 
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -24,57 +34,186 @@ pub struct ClassFile {
 }
 
 impl ClassFile {
-    pub fn get_constant(&self, constant_index: usize) -> &const_type::ConstType {
-        &self.constant_pool[constant_index - 1]
+    pub fn get_constant(&self, constant_index: usize) -> Result<&const_type::ConstType, ParseError> {
+        if constant_index == 0 {
+            return Err(ParseError::new(ParseErrorKind::InvalidConstantIndex(constant_index)));
+        }
+        self.constant_pool.get(constant_index - 1)
+            .ok_or_else(|| ParseError::new(ParseErrorKind::InvalidConstantIndex(constant_index)))
+    }
+
+    /// Serializes the class file. Unmodified parsed class files are written back byte for byte.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut file = file::File::new(Vec::new());
+        file.put_u4(self.magic);
+        file.put_u2(self.minor_version);
+        file.put_u2(self.major_version);
+        file.put_u2(self.constant_pool.len() as u16 + 1);
+        for constant in &self.constant_pool {
+            constant.write_file(&mut file);
+        }
+        file.put_u2(self.access_flags);
+        file.put_u2(self.this_class);
+        file.put_u2(self.super_class);
+        file.put_u2(self.interfaces.len() as u16);
+        file.put_range_u2(&self.interfaces);
+        file.put_u2(self.fields.len() as u16);
+        for field in &self.fields {
+            field.write_file(&mut file);
+        }
+        file.put_u2(self.methods.len() as u16);
+        for method in &self.methods {
+            method.write_file(&mut file);
+        }
+        attribute_info::write_range(&mut file, &self.attributes);
+        file.into_data()
+    }
+
+    pub fn get_magic(&self) -> u32 {
+        self.magic
+    }
+
+    pub fn get_minor_version(&self) -> u16 {
+        self.minor_version
+    }
+
+    pub fn get_major_version(&self) -> u16 {
+        self.major_version
+    }
+
+    /// The constant pool without the unused entry #0, so entry #n is at position n - 1.
+    pub fn get_constant_pool(&self) -> &Vec<const_type::ConstType> {
+        &self.constant_pool
+    }
+
+    pub fn get_access_flags(&self) -> access_flags::ClassAccessFlags {
+        access_flags::ClassAccessFlags::new(self.access_flags)
+    }
+
+    pub fn get_this_class(&self) -> u16 {
+        self.this_class
+    }
+
+    /// Zero for `java/lang/Object` and module descriptors.
+    pub fn get_super_class(&self) -> u16 {
+        self.super_class
+    }
+
+    pub fn get_interfaces(&self) -> &Vec<u16> {
+        &self.interfaces
+    }
+
+    pub fn get_fields(&self) -> &Vec<field_info::FieldInfo> {
+        &self.fields
+    }
+
+    pub fn get_methods(&self) -> &Vec<method_info::MethodInfo> {
+        &self.methods
+    }
+
+    pub fn get_attributes(&self) -> &Vec<attribute_info::AttributeInfo> {
+        &self.attributes
     }
 
-    pub fn get_name_of_class(&self, class_index: usize) -> String {
-        let class = &self.constant_pool[class_index-1];
-        match class {
+    pub fn get_annotations(&self) -> Result<annotation::Annotations, ParseError> {
+        annotation::collect_annotations(self, &self.attributes)
+    }
+
+    pub fn get_utf8(&self, utf8_index: usize) -> Result<String, ParseError> {
+        match self.get_constant(utf8_index)? {
+            const_type::ConstType::ConstantUtf8(bytes) => {
+                mutf8::decode(bytes).map_err(|e| e.within(format!("constant pool entry #{}", utf8_index)))
+            }
+            _ => Err(ParseError::new(ParseErrorKind::UnexpectedConstant { index: utf8_index, expected: "utf8" }))
+        }
+    }
+
+    /// Like `get_utf8`, but returns UTF-16 code units, so unpaired surrogates are kept.
+    pub fn get_utf16(&self, utf8_index: usize) -> Result<Vec<u16>, ParseError> {
+        match self.get_constant(utf8_index)? {
+            const_type::ConstType::ConstantUtf8(bytes) => {
+                mutf8::decode_utf16(bytes).map_err(|e| e.within(format!("constant pool entry #{}", utf8_index)))
+            }
+            _ => Err(ParseError::new(ParseErrorKind::UnexpectedConstant { index: utf8_index, expected: "utf8" }))
+        }
+    }
+
+    pub fn get_name_of_class(&self, class_index: usize) -> Result<String, ParseError> {
+        match self.get_constant(class_index)? {
             const_type::ConstType::ConstantClass(name_index) => {
-                let class_name = &self.constant_pool[(*name_index-1) as usize];
-                match class_name {
-                    const_type::ConstType::ConstantUtf8(bytes) => {
-                        String::from_utf8(bytes.clone()).unwrap()
-                    }
-                    _ => panic!("Given class name is no utf8 constant!")
-                }
+                self.get_utf8(*name_index as usize)
             }
-            _ => {
-                panic!("Given index is no class constant!");
+            _ => Err(ParseError::new(ParseErrorKind::UnexpectedConstant { index: class_index, expected: "class" }))
+        }
+    }
+
+    pub fn get_name_of_module(&self, module_index: usize) -> Result<String, ParseError> {
+        match self.get_constant(module_index)? {
+            const_type::ConstType::ConstantModule(name_index) => {
+                self.get_utf8(*name_index as usize)
             }
+            _ => Err(ParseError::new(ParseErrorKind::UnexpectedConstant { index: module_index, expected: "module" }))
         }
     }
 
-    pub fn get_name_of_member(&self, name_and_type_index: usize) -> String {
-        let name_and_type = &self.constant_pool[name_and_type_index-1];
-        match name_and_type {
+    pub fn get_name_of_package(&self, package_index: usize) -> Result<String, ParseError> {
+        match self.get_constant(package_index)? {
+            const_type::ConstType::ConstantPackage(name_index) => {
+                self.get_utf8(*name_index as usize)
+            }
+            _ => Err(ParseError::new(ParseErrorKind::UnexpectedConstant { index: package_index, expected: "package" }))
+        }
+    }
+
+    /// Returns the bootstrap method attribute index and the name and type index of a dynamically-computed constant.
+    pub fn get_dynamic(&self, dynamic_index: usize) -> Result<(u16, u16), ParseError> {
+        match self.get_constant(dynamic_index)? {
+            const_type::ConstType::ConstantDynamic(bootstrap_method_attr_index, name_and_type_index) => {
+                Ok((*bootstrap_method_attr_index, *name_and_type_index))
+            }
+            _ => Err(ParseError::new(ParseErrorKind::UnexpectedConstant { index: dynamic_index, expected: "dynamic" }))
+        }
+    }
+
+    /// Returns the bootstrap method attribute index and the name and type index of an invokedynamic call site.
+    pub fn get_invoke_dynamic(&self, invoke_dynamic_index: usize) -> Result<(u16, u16), ParseError> {
+        match self.get_constant(invoke_dynamic_index)? {
+            const_type::ConstType::ConstantInvokeDynamic(bootstrap_method_attr_index, name_and_type_index) => {
+                Ok((*bootstrap_method_attr_index, *name_and_type_index))
+            }
+            _ => Err(ParseError::new(ParseErrorKind::UnexpectedConstant { index: invoke_dynamic_index, expected: "invoke dynamic" }))
+        }
+    }
+
+    pub fn get_name_of_member(&self, name_and_type_index: usize) -> Result<String, ParseError> {
+        match self.get_constant(name_and_type_index)? {
             const_type::ConstType::ConstantNameAndType(name_index, _descriptior_index) => {
-                let member_name = &self.constant_pool[(*name_index-1) as  usize];
-                match member_name {
-                    const_type::ConstType::ConstantUtf8(bytes) => {
-                        String::from_utf8(bytes.clone()).unwrap()
-                    }
-                    _ => {panic!("Given index is no utf8 constant!")}
-                }
+                self.get_utf8(*name_index as usize)
             }
-            _ => {panic!("Given index is no name and type constant!");}
+            _ => Err(ParseError::new(ParseErrorKind::UnexpectedConstant { index: name_and_type_index, expected: "name and type" }))
         }
     }
 
-    pub fn get_description_of_member(&self, name_and_type_index: usize) -> String {
-        let name_and_type = &self.constant_pool[name_and_type_index-1];
-        match name_and_type {
+    /// Class name, member name and descriptor of the `Fieldref`, `Methodref` or `InterfaceMethodref` at `member_index`.
+    pub fn get_member_ref(&self, member_index: usize) -> Result<(String, String, String), ParseError> {
+        match self.get_constant(member_index)? {
+            const_type::ConstType::ConstantFieldref(class_index, name_and_type_index)
+            | const_type::ConstType::ConstantMethodref(class_index, name_and_type_index)
+            | const_type::ConstType::ConstantInterfaceMethodref(class_index, name_and_type_index) => Ok((
+                self.get_name_of_class(*class_index as usize)?,
+                self.get_name_of_member(*name_and_type_index as usize)?,
+                self.get_description_of_member(*name_and_type_index as usize)?,
+            )),
+            _ => Err(ParseError::new(ParseErrorKind::UnexpectedConstant { index: member_index, expected: "member reference" }))
+        }
+    }
+
+    pub fn get_description_of_member(&self, name_and_type_index: usize) -> Result<String, ParseError> {
+        match self.get_constant(name_and_type_index)? {
             const_type::ConstType::ConstantNameAndType(_name_index, descriptior_index) => {
-                let member_name = &self.constant_pool[(*descriptior_index-1) as  usize];
-                match member_name {
-                    const_type::ConstType::ConstantUtf8(bytes) => {
-                        String::from_utf8(bytes.clone()).unwrap()
-                    }
-                    _ => {panic!("Given index is no utf8 constant!")}
-                }
+                self.get_utf8(*descriptior_index as usize)
             }
-            _ => {panic!("Given index is no name and type constant!");}
+            _ => Err(ParseError::new(ParseErrorKind::UnexpectedConstant { index: name_and_type_index, expected: "name and type" }))
         }
     }
 }
@@ -87,24 +226,24 @@ impl ContainsMethods for ClassFile {
     }
 }
 
-pub fn parse_file(file: &mut file::File) -> ClassFile {
-    let magic = file.get_u4();
-    let minor_version = file.get_u2();
-    let major_version = file.get_u2();
-    let constant_count = file.get_u2() as usize;
-    let constant_pool = const_type::parse_range(file, constant_count);
-    let access_flags = file.get_u2();
-    let this_class = file.get_u2();
-    let super_class = file.get_u2();
-    let interface_count = file.get_u2() as usize;
-    let interfaces = file.get_range_u2(interface_count);
-    let field_count = file.get_u2() as usize;
-    let fields = field_info::parse_range(file, field_count);
-    let methods_count = file.get_u2() as usize;
-    let methods = method_info::parse_range(file, methods_count);
-    let attributes_count = file.get_u2() as usize;
-    let attributes = attribute_info::parse_range(file, attributes_count);
-    ClassFile {
+pub fn parse_file(file: &mut file::File) -> Result<ClassFile, ParseError> {
+    let magic = file.get_u4().map_err(|e| e.within("class header"))?;
+    let minor_version = file.get_u2().map_err(|e| e.within("class header"))?;
+    let major_version = file.get_u2().map_err(|e| e.within("class header"))?;
+    let constant_count = file.get_u2().map_err(|e| e.within("class header"))? as usize;
+    let constant_pool = const_type::parse_range(file, constant_count)?;
+    let access_flags = file.get_u2().map_err(|e| e.within("class header"))?;
+    let this_class = file.get_u2().map_err(|e| e.within("class header"))?;
+    let super_class = file.get_u2().map_err(|e| e.within("class header"))?;
+    let interface_count = file.get_u2().map_err(|e| e.within("class header"))? as usize;
+    let interfaces = file.get_range_u2(interface_count).map_err(|e| e.within("interfaces"))?;
+    let field_count = file.get_u2().map_err(|e| e.within("fields"))? as usize;
+    let fields = field_info::parse_range(file, field_count)?;
+    let methods_count = file.get_u2().map_err(|e| e.within("methods"))? as usize;
+    let methods = method_info::parse_range(file, methods_count)?;
+    let attributes_count = file.get_u2().map_err(|e| e.within("class attributes"))? as usize;
+    let attributes = attribute_info::parse_range(file, attributes_count).map_err(|e| e.within("class"))?;
+    Ok(ClassFile {
         magic,
         minor_version,
         major_version,
@@ -115,5 +254,5 @@ pub fn parse_file(file: &mut file::File) -> ClassFile {
         interfaces,
         fields,
         methods,
-        attributes }
-}
\ No newline at end of file
+        attributes })
+}
@@ -0,0 +1,69 @@
+//! Java's "modified UTF-8" as used by `CONSTANT_Utf8` entries (JVMS §4.4.7): NUL is encoded
+//! as `C0 80` and supplementary characters as two separately encoded UTF-16 surrogates.
+
+use super::parse_error::{ParseError, ParseErrorKind};
+
+fn invalid(position: usize) -> ParseError {
+    ParseError::new(ParseErrorKind::InvalidModifiedUtf8 { position })
+}
+
+fn continuation(bytes: &[u8], position: usize) -> Result<u16, ParseError> {
+    match bytes.get(position) {
+        Some(byte) if byte & 0xC0 == 0x80 => Ok((byte & 0x3F) as u16),
+        _ => Err(invalid(position)),
+    }
+}
+
+/// Decodes modified UTF-8 into UTF-16 code units, keeping unpaired surrogates.
+pub fn decode_utf16(bytes: &[u8]) -> Result<Vec<u16>, ParseError> {
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut position = 0;
+    while position < bytes.len() {
+        let first = bytes[position];
+        if first & 0x80 == 0 {
+            if first == 0 {
+                return Err(invalid(position));
+            }
+            units.push(first as u16);
+            position += 1;
+        } else if first & 0xE0 == 0xC0 {
+            units.push(((first & 0x1F) as u16) << 6 | continuation(bytes, position + 1)?);
+            position += 2;
+        } else if first & 0xF0 == 0xE0 {
+            units.push(((first & 0x0F) as u16) << 12 | continuation(bytes, position + 1)? << 6 | continuation(bytes, position + 2)?);
+            position += 3;
+        } else {
+            return Err(invalid(position));
+        }
+    }
+    Ok(units)
+}
+
+/// Decodes modified UTF-8 into a Rust string. Unpaired surrogates, which Java strings may
+/// contain but Rust strings can't, are replaced by U+FFFD.
+pub fn decode(bytes: &[u8]) -> Result<String, ParseError> {
+    Ok(String::from_utf16_lossy(&decode_utf16(bytes)?))
+}
+
+/// Encodes UTF-16 code units as modified UTF-8.
+pub fn encode_utf16(units: &[u16]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(units.len());
+    for &unit in units {
+        if unit != 0 && unit < 0x80 {
+            bytes.push(unit as u8);
+        } else if unit < 0x800 {
+            bytes.push(0xC0 | (unit >> 6) as u8);
+            bytes.push(0x80 | (unit & 0x3F) as u8);
+        } else {
+            bytes.push(0xE0 | (unit >> 12) as u8);
+            bytes.push(0x80 | ((unit >> 6) & 0x3F) as u8);
+            bytes.push(0x80 | (unit & 0x3F) as u8);
+        }
+    }
+    bytes
+}
+
+/// Encodes a Rust string as modified UTF-8.
+pub fn encode(string: &str) -> Vec<u8> {
+    encode_utf16(&string.encode_utf16().collect::<Vec<u16>>())
+}
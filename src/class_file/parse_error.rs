@@ -0,0 +1,87 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    UnexpectedEof { needed: usize, available: usize },
+    UnknownConstantTag(u8),
+    InvalidConstantIndex(usize),
+    UnexpectedConstant { index: usize, expected: &'static str },
+    InvalidModifiedUtf8 { position: usize },
+    InvalidTag { structure: &'static str, tag: u8 },
+    TrailingBytes(usize),
+    InvalidBranchTarget { pc: usize, target: i64 },
+    InvalidDescriptor { descriptor: String, position: usize },
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::UnexpectedEof { needed, available } => {
+                write!(f, "unexpected end of data (needed {} bytes, {} available)", needed, available)
+            }
+            ParseErrorKind::UnknownConstantTag(tag) => write!(f, "unknown constant tag {}", tag),
+            ParseErrorKind::InvalidConstantIndex(index) => write!(f, "invalid constant pool index #{}", index),
+            ParseErrorKind::UnexpectedConstant { index, expected } => {
+                write!(f, "constant pool entry #{} is no {} constant", index, expected)
+            }
+            ParseErrorKind::InvalidModifiedUtf8 { position } => write!(f, "invalid modified utf8 at byte {} of string", position),
+            ParseErrorKind::InvalidTag { structure, tag } => write!(f, "invalid {} {}", structure, tag),
+            ParseErrorKind::TrailingBytes(count) => write!(f, "{} unexpected bytes after the end of the structure", count),
+            ParseErrorKind::InvalidBranchTarget { pc, target } => write!(f, "branch at pc {} targets {}, which is no instruction", pc, target),
+            ParseErrorKind::InvalidDescriptor { descriptor, position } => write!(f, "invalid descriptor {:?} at character {}", descriptor, position),
+        }
+    }
+}
+
+/// Error produced while reading a class file. Carries the absolute byte offset
+/// (if known), the chain of structures being parsed, outermost first, and the reason.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    offset: Option<usize>,
+    context: Vec<String>,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind) -> ParseError {
+        ParseError { offset: None, context: vec![], kind }
+    }
+
+    pub fn at(offset: usize, kind: ParseErrorKind) -> ParseError {
+        ParseError { offset: Some(offset), context: vec![], kind }
+    }
+
+    /// Records that the error happened while parsing `structure`.
+    pub fn within<S: Into<String>>(mut self, structure: S) -> ParseError {
+        self.context.insert(0, structure.into());
+        self
+    }
+
+    pub fn get_offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    pub fn get_context(&self) -> &Vec<String> {
+        &self.context
+    }
+
+    pub fn get_kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.context.is_empty() {
+            write!(f, "{}: ", self.context.join(" "))?;
+        }
+        write!(f, "{}", self.kind)?;
+        if let Some(offset) = self.offset {
+            write!(f, " at offset {:#x}", offset)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,396 @@
+//! Format checking (JVMS §4.8): the constraints on a class file that can be checked without
+//! looking at the bytecode or at other classes. The bytecode is checked by the verifier.
+
+use std::collections::HashSet;
+use super::ClassFile;
+use super::attribute::Attribute;
+use super::attribute_info::AttributeInfo;
+use super::class_format_error::{ClassFormatError, ClassFormatErrorKind};
+use super::const_type::ConstType;
+use super::descriptor::{self, FieldType};
+use super::field_info::FieldInfo;
+use super::method_info::MethodInfo;
+use super::parse_error::{ParseError, ParseErrorKind};
+
+pub const MAGIC: u32 = 0xCAFEBABE;
+/// JDK 1.0.2.
+pub const MIN_MAJOR_VERSION: u16 = 45;
+/// Java SE 17.
+pub const MAX_MAJOR_VERSION: u16 = 61;
+
+impl ClassFile {
+    /// Runs the format checks of JVMS §4.8 and returns every violation found, in the order of the
+    /// class file. An empty list means the class file is well-formed.
+    pub fn validate(&self) -> Vec<ClassFormatError> {
+        let mut validator = Validator { class_file: self, errors: vec![] };
+        validator.check_header();
+        validator.check_constant_pool();
+        validator.check_fields();
+        validator.check_methods();
+        validator.check_attributes("class", self.get_attributes());
+        validator.errors
+    }
+}
+
+struct Validator<'a> {
+    class_file: &'a ClassFile,
+    errors: Vec<ClassFormatError>,
+}
+
+impl Validator<'_> {
+    fn report<T>(&mut self, location: &str, result: Result<T, ClassFormatErrorKind>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(kind) => {
+                self.errors.push(ClassFormatError::new(location, kind));
+                None
+            }
+        }
+    }
+
+    fn is_module(&self) -> bool {
+        self.class_file.get_major_version() >= 53 && self.class_file.get_access_flags().is_module()
+    }
+
+    fn is_interface(&self) -> bool {
+        self.class_file.get_access_flags().is_interface()
+    }
+
+    fn check_header(&mut self) {
+        let class_file = self.class_file;
+        let (major_version, minor_version) = (class_file.get_major_version(), class_file.get_minor_version());
+        if class_file.get_magic() != MAGIC {
+            self.report::<()>("class header", Err(ClassFormatErrorKind::InvalidMagic(class_file.get_magic())));
+        }
+        let preview = major_version >= 56 && minor_version != 0 && minor_version != 0xFFFF;
+        if !(MIN_MAJOR_VERSION..=MAX_MAJOR_VERSION).contains(&major_version) || preview {
+            self.report::<()>("class header", Err(ClassFormatErrorKind::UnsupportedVersion { major_version, minor_version }));
+        }
+
+        let access_flags = class_file.get_access_flags();
+        let result = check_flags(access_flags.get_bits(), &[
+            (self.is_module() && access_flags.get_bits() != 0x8000, "a module has no other flags"),
+            (!self.is_module() && access_flags.is_interface() && !access_flags.is_abstract(), "an interface must be abstract"),
+            (access_flags.is_interface() && (access_flags.is_final() || access_flags.is_super() || access_flags.is_enum()),
+                "an interface cannot be final, super or enum"),
+            (!access_flags.is_interface() && access_flags.is_annotation(), "an annotation must be an interface"),
+            (access_flags.is_final() && access_flags.is_abstract(), "a class cannot be final and abstract"),
+        ]);
+        self.report("class header", result);
+
+        let this_class = self.report("this_class", class_file.get_name_of_class(class_file.get_this_class() as usize).map_err(Into::into));
+        let result = match (class_file.get_super_class(), this_class.as_deref()) {
+            (0, _) if self.is_module() => Ok(()),
+            (_, _) if self.is_module() => Err(ClassFormatErrorKind::InvalidSuperclass("a module has no superclass")),
+            (0, Some("java/lang/Object")) => Ok(()),
+            (0, _) => Err(ClassFormatErrorKind::InvalidSuperclass("only java/lang/Object has no superclass")),
+            (super_class, _) => match class_file.get_name_of_class(super_class as usize) {
+                Ok(super_name) if self.is_interface() && super_name != "java/lang/Object" => {
+                    Err(ClassFormatErrorKind::InvalidSuperclass("the superclass of an interface must be java/lang/Object"))
+                }
+                Ok(super_name) if super_name.starts_with('[') => Err(ClassFormatErrorKind::InvalidSuperclass("an array class cannot be extended")),
+                Ok(_) => Ok(()),
+                Err(error) => Err(error.into()),
+            },
+        };
+        self.report("super_class", result);
+        for (position, interface) in class_file.get_interfaces().iter().enumerate() {
+            let result = class_file.get_name_of_class(*interface as usize).map_err(Into::into);
+            self.report(&format!("interface #{}", position), result);
+        }
+    }
+
+    fn check_constant_pool(&mut self) {
+        for (position, constant) in self.class_file.get_constant_pool().iter().enumerate() {
+            let index = position + 1;
+            let result = self.check_constant(index, constant);
+            self.report(&format!("constant #{}", index), result);
+        }
+    }
+
+    fn check_constant(&self, index: usize, constant: &ConstType) -> Result<(), ClassFormatErrorKind> {
+        let class_file = self.class_file;
+        let major_version = class_file.get_major_version();
+        let tag = match constant.get_tag() {
+            Some(tag) => tag,
+            None => return Ok(()),
+        };
+        let allowed = match tag {
+            15 | 16 | 18 => major_version >= 51,
+            17 => major_version >= 55,
+            19 | 20 => self.is_module(),
+            _ => true,
+        };
+        if !allowed {
+            return Err(ClassFormatErrorKind::UnsupportedConstant { index, tag, major_version });
+        }
+        match constant {
+            ConstType::ConstantUtf8(_) => {
+                class_file.get_utf8(index)?;
+            }
+            ConstType::ConstantClass(name_index) => {
+                let name = class_file.get_utf8(*name_index as usize)?;
+                check_class_name(&name)?;
+            }
+            ConstType::ConstantString(string_index) => {
+                class_file.get_utf8(*string_index as usize)?;
+            }
+            ConstType::ConstantNameAndType(name_index, descriptor_index) => {
+                class_file.get_utf8(*name_index as usize)?;
+                class_file.get_utf8(*descriptor_index as usize)?;
+            }
+            ConstType::ConstantFieldref(class_index, name_and_type_index) => {
+                class_file.get_name_of_class(*class_index as usize)?;
+                let name = class_file.get_name_of_member(*name_and_type_index as usize)?;
+                check_name("field", &name, is_unqualified_name(&name))?;
+                check_field_descriptor(&class_file.get_description_of_member(*name_and_type_index as usize)?)?;
+            }
+            ConstType::ConstantMethodref(class_index, name_and_type_index)
+            | ConstType::ConstantInterfaceMethodref(class_index, name_and_type_index) => {
+                class_file.get_name_of_class(*class_index as usize)?;
+                let name = class_file.get_name_of_member(*name_and_type_index as usize)?;
+                let method_descriptor = check_method_descriptor(&class_file.get_description_of_member(*name_and_type_index as usize)?)?;
+                let is_constructor = name == "<init>" && matches!(constant, ConstType::ConstantMethodref(..)) && method_descriptor.get_return_type().is_none();
+                check_name("method", &name, is_constructor || is_plain_method_name(&name))?;
+            }
+            ConstType::ConstantMethodHandle(reference_kind, reference_index) => {
+                self.check_method_handle(*reference_kind, *reference_index as usize)?;
+            }
+            ConstType::ConstantMethodType(descriptor_index) => {
+                check_method_descriptor(&class_file.get_utf8(*descriptor_index as usize)?)?;
+            }
+            ConstType::ConstantDynamic(_bootstrap_method_attr_index, name_and_type_index) => {
+                let name = class_file.get_name_of_member(*name_and_type_index as usize)?;
+                check_name("dynamic constant", &name, is_unqualified_name(&name))?;
+                check_field_descriptor(&class_file.get_description_of_member(*name_and_type_index as usize)?)?;
+            }
+            ConstType::ConstantInvokeDynamic(_bootstrap_method_attr_index, name_and_type_index) => {
+                let name = class_file.get_name_of_member(*name_and_type_index as usize)?;
+                check_name("method", &name, is_plain_method_name(&name))?;
+                check_method_descriptor(&class_file.get_description_of_member(*name_and_type_index as usize)?)?;
+            }
+            ConstType::ConstantModule(name_index) => {
+                class_file.get_utf8(*name_index as usize)?;
+            }
+            ConstType::ConstantPackage(name_index) => {
+                let name = class_file.get_utf8(*name_index as usize)?;
+                check_name("package", &name, is_binary_name(&name))?;
+            }
+            ConstType::ConstantInteger(_) | ConstType::ConstantFloat(_) | ConstType::ConstantLong(_, _)
+            | ConstType::ConstantDouble(_, _) | ConstType::Unusable => {}
+        }
+        Ok(())
+    }
+
+    /// The kind of member a method handle refers to depends on its reference kind (JVMS §4.4.8).
+    fn check_method_handle(&self, reference_kind: u8, reference_index: usize) -> Result<(), ClassFormatErrorKind> {
+        let class_file = self.class_file;
+        let reference = class_file.get_constant(reference_index)?;
+        let (matches, expected) = match reference_kind {
+            1..=4 => (matches!(reference, ConstType::ConstantFieldref(..)), "field reference"),
+            5 | 8 => (matches!(reference, ConstType::ConstantMethodref(..)), "method reference"),
+            6 | 7 if class_file.get_major_version() >= 52 => {
+                (matches!(reference, ConstType::ConstantMethodref(..) | ConstType::ConstantInterfaceMethodref(..)), "method reference")
+            }
+            6 | 7 => (matches!(reference, ConstType::ConstantMethodref(..)), "method reference"),
+            9 => (matches!(reference, ConstType::ConstantInterfaceMethodref(..)), "interface method reference"),
+            _ => return Err(ClassFormatErrorKind::InvalidReferenceKind(reference_kind)),
+        };
+        if !matches {
+            return Err(ParseError::new(ParseErrorKind::UnexpectedConstant { index: reference_index, expected }).into());
+        }
+        if let ConstType::ConstantMethodref(_, name_and_type_index) | ConstType::ConstantInterfaceMethodref(_, name_and_type_index) = reference {
+            let name = class_file.get_name_of_member(*name_and_type_index as usize)?;
+            let valid = if reference_kind == 8 { name == "<init>" } else { is_plain_method_name(&name) };
+            check_name("method handle target", &name, valid)?;
+        }
+        Ok(())
+    }
+
+    fn check_fields(&mut self) {
+        let mut seen = HashSet::new();
+        for field in self.class_file.get_fields() {
+            let name = self.class_file.get_utf8(field.get_name_index() as usize);
+            let field_descriptor = self.class_file.get_utf8(field.get_descriptor_index() as usize);
+            let (name, field_descriptor) = match (name, field_descriptor) {
+                (Ok(name), Ok(field_descriptor)) => (name, field_descriptor),
+                (Err(error), _) | (_, Err(error)) => {
+                    self.report::<()>("field", Err(error.into()));
+                    continue;
+                }
+            };
+            let location = format!("field {}:{}", name, field_descriptor);
+            self.report(&location, check_name("field", &name, is_unqualified_name(&name)));
+            self.report(&location, check_field_descriptor(&field_descriptor));
+            let result = self.check_field_flags(field);
+            self.report(&location, result);
+            if !seen.insert((name, field_descriptor)) {
+                self.report::<()>(&location, Err(ClassFormatErrorKind::Duplicate(location.clone())));
+            }
+            self.check_attributes(&location, &field.get_attributes());
+        }
+    }
+
+    fn check_field_flags(&self, field: &FieldInfo) -> Result<(), ClassFormatErrorKind> {
+        let access_flags = field.get_access_flags();
+        let visibility = [access_flags.is_public(), access_flags.is_private(), access_flags.is_protected()].iter().filter(|set| **set).count();
+        let interface_field = access_flags.is_public() && access_flags.is_static() && access_flags.is_final()
+            && access_flags.get_bits() & !(0x0001 | 0x0008 | 0x0010 | 0x1000) == 0;
+        check_flags(access_flags.get_bits(), &[
+            (visibility > 1, "at most one of public, private and protected"),
+            (access_flags.is_final() && access_flags.is_volatile(), "a field cannot be final and volatile"),
+            (self.is_interface() && !interface_field, "an interface field must be public static final"),
+        ])
+    }
+
+    fn check_methods(&mut self) {
+        let mut seen = HashSet::new();
+        for method in self.class_file.get_methods() {
+            let name = self.class_file.get_utf8(method.get_name_index() as usize);
+            let method_descriptor = self.class_file.get_utf8(method.get_descriptor_index() as usize);
+            let (name, method_descriptor) = match (name, method_descriptor) {
+                (Ok(name), Ok(method_descriptor)) => (name, method_descriptor),
+                (Err(error), _) | (_, Err(error)) => {
+                    self.report::<()>("method", Err(error.into()));
+                    continue;
+                }
+            };
+            let location = format!("method {}{}", name, method_descriptor);
+            let valid_name = is_plain_method_name(&name) || name == "<clinit>" || (name == "<init>" && !self.is_interface());
+            self.report(&location, check_name("method", &name, valid_name));
+            if let Some(parsed) = self.report(&location, check_method_descriptor(&method_descriptor)) {
+                let special = (name == "<init>" && parsed.get_return_type().is_some()) || (name == "<clinit>" && method_descriptor != "()V");
+                if special {
+                    self.report::<()>(&location, Err(ParseError::new(ParseErrorKind::InvalidDescriptor { descriptor: method_descriptor.clone(), position: 0 }).into()));
+                }
+                let slots = parsed.get_argument_slots() + if method.get_access_flags().is_static() { 0 } else { 1 };
+                if slots > 255 {
+                    self.report::<()>(&location, Err(ClassFormatErrorKind::TooManyParameters(slots)));
+                }
+            }
+            let result = self.check_method_flags(method, &name);
+            self.report(&location, result);
+            if !seen.insert((name, method_descriptor)) {
+                self.report::<()>(&location, Err(ClassFormatErrorKind::Duplicate(location.clone())));
+            }
+
+            let found = self.check_attributes(&location, &method.get_attributes());
+            let access_flags = method.get_access_flags();
+            let expected = if access_flags.is_native() || access_flags.is_abstract() { 0 } else { 1 };
+            if found != expected {
+                self.report::<()>(&location, Err(ClassFormatErrorKind::CodeAttributeCount { expected, found }));
+            }
+        }
+    }
+
+    fn check_method_flags(&self, method: &MethodInfo, name: &str) -> Result<(), ClassFormatErrorKind> {
+        let access_flags = method.get_access_flags();
+        let bits = access_flags.get_bits();
+        let major_version = self.class_file.get_major_version();
+        if name == "<clinit>" {
+            // Apart from ACC_STATIC, the flags of a class initializer are ignored.
+            return check_flags(bits, &[(major_version >= 51 && !access_flags.is_static(), "a class initializer must be static")]);
+        }
+        let visibility = [access_flags.is_public(), access_flags.is_private(), access_flags.is_protected()].iter().filter(|set| **set).count();
+        // ACC_PUBLIC | ACC_PRIVATE | ACC_PROTECTED | ACC_VARARGS | ACC_STRICT | ACC_SYNTHETIC
+        let constructor_flags = 0x0001 | 0x0002 | 0x0004 | 0x0080 | 0x0800 | 0x1000;
+        // ACC_PUBLIC | ACC_ABSTRACT | ACC_BRIDGE | ACC_VARARGS | ACC_SYNTHETIC
+        let old_interface_flags = 0x0001 | 0x0400 | 0x0040 | 0x0080 | 0x1000;
+        let is_interface = self.is_interface();
+        check_flags(bits, &[
+            (visibility > 1, "at most one of public, private and protected"),
+            (name == "<init>" && bits & !constructor_flags != 0, "a constructor can only have visibility, varargs, strict and synthetic flags"),
+            (is_interface && major_version < 52 && (!access_flags.is_public() || !access_flags.is_abstract() || bits & !old_interface_flags != 0),
+                "an interface method must be public abstract"),
+            (is_interface && major_version >= 52 && access_flags.is_public() == access_flags.is_private(),
+                "an interface method must be either public or private"),
+            (is_interface && (access_flags.is_protected() || access_flags.is_final() || access_flags.is_synchronized() || access_flags.is_native()),
+                "an interface method cannot be protected, final, synchronized or native"),
+            (access_flags.is_abstract() && (access_flags.is_private() || access_flags.is_static() || access_flags.is_final()
+                || access_flags.is_synchronized() || access_flags.is_native()), "an abstract method cannot be private, static, final, synchronized or native"),
+            (access_flags.is_abstract() && access_flags.is_strict() && (46..61).contains(&major_version), "an abstract method cannot be strict"),
+        ])
+    }
+
+    /// Decodes every attribute, reporting those that are malformed, and returns the number of `Code`
+    /// attributes among them.
+    fn check_attributes(&mut self, location: &str, attributes: &[AttributeInfo]) -> usize {
+        let mut code_attributes = 0;
+        for attribute_info in attributes {
+            match attribute_info.to_attribute(self.class_file) {
+                Ok(Attribute::Code(code_attribute)) => {
+                    code_attributes += 1;
+                    self.check_attributes(&format!("Code attribute of {}", location), &code_attribute.get_attributes());
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    self.report::<()>(location, Err(error.into()));
+                }
+            }
+        }
+        code_attributes
+    }
+}
+
+/// The first reason whose condition holds, as an error.
+fn check_flags(flags: u16, rules: &[(bool, &'static str)]) -> Result<(), ClassFormatErrorKind> {
+    match rules.iter().find(|(violated, _reason)| *violated) {
+        Some((_violated, reason)) => Err(ClassFormatErrorKind::IllegalAccessFlags { flags, reason }),
+        None => Ok(()),
+    }
+}
+
+fn check_name(kind: &'static str, name: &str, valid: bool) -> Result<(), ClassFormatErrorKind> {
+    if valid {
+        Ok(())
+    } else {
+        Err(ClassFormatErrorKind::InvalidName { kind, name: name.to_string() })
+    }
+}
+
+/// Field and method names (JVMS §4.2.2).
+fn is_unqualified_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['.', ';', '[', '/'])
+}
+
+/// Method names other than `<init>` and `<clinit>`.
+fn is_plain_method_name(name: &str) -> bool {
+    is_unqualified_name(name) && !name.contains(['<', '>'])
+}
+
+/// Class and package names in internal form, e.g. `java/lang/Object` (JVMS §4.2.1).
+fn is_binary_name(name: &str) -> bool {
+    name.split('/').all(is_unqualified_name)
+}
+
+/// The name of a `CONSTANT_Class`: a binary name, or the descriptor of an array class.
+fn check_class_name(name: &str) -> Result<(), ClassFormatErrorKind> {
+    if name.starts_with('[') {
+        check_field_descriptor(name).map(|_field_type| ())
+    } else {
+        check_name("class", name, is_binary_name(name))
+    }
+}
+
+fn check_field_type(field_type: &FieldType, dimensions: usize) -> Result<(), ClassFormatErrorKind> {
+    match field_type {
+        FieldType::Base(_) => Ok(()),
+        FieldType::Object(class_name) => check_name("class", class_name, is_binary_name(class_name)),
+        FieldType::Array(_) if dimensions == 255 => Err(ClassFormatErrorKind::InvalidName { kind: "array class", name: field_type.get_descriptor() }),
+        FieldType::Array(component) => check_field_type(component, dimensions + 1),
+    }
+}
+
+fn check_field_descriptor(field_descriptor: &str) -> Result<FieldType, ClassFormatErrorKind> {
+    let field_type = descriptor::parse_field_descriptor(field_descriptor)?;
+    check_field_type(&field_type, 0)?;
+    Ok(field_type)
+}
+
+fn check_method_descriptor(method_descriptor: &str) -> Result<descriptor::MethodDescriptor, ClassFormatErrorKind> {
+    let parsed = descriptor::parse_method_descriptor(method_descriptor)?;
+    for field_type in parsed.get_parameters().iter().chain(parsed.get_return_type()) {
+        check_field_type(field_type, 0)?;
+    }
+    Ok(parsed)
+}
@@ -0,0 +1,1013 @@
+//! Prints class files in the format of `javap -c -v`, so the output can be diffed against it.
+
+use std::fs;
+use std::error::Error;
+use std::time::UNIX_EPOCH;
+use crate::class_file::{ClassFile, const_type::ConstType, descriptor};
+use crate::class_file::attribute::{Attribute, StackMapFrame, VerificationType, LocalVariable};
+use crate::class_file::attribute_info::AttributeInfo;
+use crate::class_file::annotation::{Annotation, ElementValue, TypeAnnotation, TargetInfo};
+use crate::class_file::code_attribute::CodeAttribute;
+use crate::class_file::instruction::Insn;
+use crate::class_file::parse_error::ParseError;
+use crate::util::{number_format, sha256};
+
+const COMMENT_COLUMN: usize = 40;
+
+fn modifiers(modifiers: Vec<&str>) -> String {
+    modifiers.iter().map(|modifier| format!("{} ", modifier)).collect()
+}
+
+/// Options of the `disasm` mode.
+pub struct Options {
+    /// Also print private members, like `javap -p`.
+    pub show_private: bool,
+}
+
+struct Printer<'a> {
+    class_file: &'a ClassFile,
+    out: String,
+}
+
+impl<'a> Printer<'a> {
+    fn line(&mut self, indent: usize, text: &str) {
+        let line = format!("{}{}", " ".repeat(indent), text);
+        self.out.push_str(line.trim_end());
+        self.out.push('\n');
+    }
+
+    fn line_with_comment(&mut self, indent: usize, text: &str, comment: &str) {
+        let mut line = format!("{}{}", " ".repeat(indent), text);
+        let length = line.chars().count();
+        if length < indent + COMMENT_COLUMN {
+            line.push_str(&" ".repeat(indent + COMMENT_COLUMN - length));
+        } else {
+            line.push(' ');
+        }
+        line.push_str("// ");
+        line.push_str(comment);
+        self.line(0, &line);
+    }
+
+    /// A version index line of the Module attribute, `#0` when there is no version.
+    fn version_line(&mut self, indent: usize, version_index: u16) -> Result<(), ParseError> {
+        if version_index == 0 {
+            self.line(indent, "#0");
+        } else {
+            let version = self.utf8(version_index)?;
+            self.line_with_comment(indent, &format!("#{}", version_index), &version);
+        }
+        Ok(())
+    }
+
+    fn utf8(&self, index: u16) -> Result<String, ParseError> {
+        self.class_file.get_utf8(index as usize)
+    }
+
+    /// The value of the `Signature` attribute among `attributes`, if there is one.
+    fn signature(&self, attributes: &[AttributeInfo]) -> Result<Option<String>, ParseError> {
+        for attribute_info in attributes {
+            if let Attribute::Signature(signature_index) = attribute_info.to_attribute(self.class_file)? {
+                return Ok(Some(self.utf8(signature_index)?));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The type of a field or record component, generic if it has a `Signature` attribute.
+    fn field_type(&self, field_descriptor: &str, attributes: &[AttributeInfo]) -> Result<String, ParseError> {
+        Ok(match self.signature(attributes)? {
+            Some(signature) => descriptor::parse_field_signature(&signature)?.to_string(),
+            None => descriptor::parse_field_descriptor(field_descriptor)?.to_string(),
+        })
+    }
+
+    fn this_class_name(&self) -> Result<String, ParseError> {
+        self.class_file.get_name_of_class(self.class_file.get_this_class() as usize)
+    }
+
+    fn name_and_type_comment(&self, name_and_type_index: u16) -> Result<String, ParseError> {
+        let name = self.class_file.get_name_of_member(name_and_type_index as usize)?;
+        let descriptor = self.class_file.get_description_of_member(name_and_type_index as usize)?;
+        Ok(format!("{}:{}", check_name(&name), descriptor))
+    }
+
+    /// The `// ...` comment javap prints next to constant pool entries.
+    fn constant_comment(&self, index: u16) -> Result<Option<String>, ParseError> {
+        Ok(match self.class_file.get_constant(index as usize)? {
+            ConstType::ConstantClass(name_index) => Some(check_name(&self.utf8(*name_index)?)),
+            ConstType::ConstantFieldref(class_index, name_and_type_index)
+            | ConstType::ConstantMethodref(class_index, name_and_type_index)
+            | ConstType::ConstantInterfaceMethodref(class_index, name_and_type_index) => {
+                let class_name = self.class_file.get_name_of_class(*class_index as usize)?;
+                Some(format!("{}.{}", check_name(&class_name), self.name_and_type_comment(*name_and_type_index)?))
+            }
+            ConstType::ConstantNameAndType(_name_index, _descriptor_index) => Some(self.name_and_type_comment(index)?),
+            ConstType::ConstantString(string_index) => Some(escape(&self.utf8(*string_index)?)),
+            ConstType::ConstantMethodHandle(reference_kind, reference_index) => {
+                let reference = self.constant_comment(*reference_index)?.unwrap_or_default();
+                Some(format!("{} {}", reference_kind_name(*reference_kind), reference))
+            }
+            ConstType::ConstantMethodType(descriptor_index) => Some(format!(" {}", self.utf8(*descriptor_index)?)),
+            ConstType::ConstantDynamic(bootstrap_method_attr_index, name_and_type_index)
+            | ConstType::ConstantInvokeDynamic(bootstrap_method_attr_index, name_and_type_index) => {
+                Some(format!("#{}:{}", bootstrap_method_attr_index, self.name_and_type_comment(*name_and_type_index)?))
+            }
+            ConstType::ConstantModule(name_index) | ConstType::ConstantPackage(name_index) => Some(check_name(&self.utf8(*name_index)?)),
+            _ => None,
+        })
+    }
+
+    /// Text of a loadable constant as javap prints it after `ldc`, in `ConstantValue` and bootstrap arguments.
+    fn constant_value(&self, index: u16) -> Result<String, ParseError> {
+        Ok(match self.class_file.get_constant(index as usize)? {
+            ConstType::ConstantInteger(bytes) => format!("int {}", *bytes as i32),
+            ConstType::ConstantFloat(bytes) => format!("float {}f", number_format::float_to_string(f32::from_bits(*bytes))),
+            ConstType::ConstantLong(high_bytes, low_bytes) => format!("long {}l", ((*high_bytes as u64) << 32 | *low_bytes as u64) as i64),
+            ConstType::ConstantDouble(high_bytes, low_bytes) => {
+                format!("double {}d", number_format::double_to_string(f64::from_bits((*high_bytes as u64) << 32 | *low_bytes as u64)))
+            }
+            ConstType::ConstantString(_) => format!("String {}", self.constant_comment(index)?.unwrap_or_default()),
+            ConstType::ConstantClass(_) => format!("class {}", self.constant_comment(index)?.unwrap_or_default()),
+            ConstType::ConstantMethodType(_) => format!("MethodType{}", self.constant_comment(index)?.unwrap_or_default()),
+            ConstType::ConstantMethodHandle(_, _) => format!("MethodHandle {}", self.constant_comment(index)?.unwrap_or_default()),
+            ConstType::ConstantDynamic(_, _) => format!("Dynamic {}", self.constant_comment(index)?.unwrap_or_default()),
+            _ => self.constant_comment(index)?.unwrap_or_default(),
+        })
+    }
+
+    /// The comment javap prints next to instructions referencing the constant pool. Members of the
+    /// class being printed are shown without their class name.
+    fn instruction_comment(&self, index: u16) -> Result<String, ParseError> {
+        let this_class = self.this_class_name()?;
+        let member = |kind: &str, class_index: u16, name_and_type_index: u16| -> Result<String, ParseError> {
+            let class_name = self.class_file.get_name_of_class(class_index as usize)?;
+            let name_and_type = self.name_and_type_comment(name_and_type_index)?;
+            if class_name == this_class {
+                Ok(format!("{} {}", kind, name_and_type))
+            } else {
+                Ok(format!("{} {}.{}", kind, check_name(&class_name), name_and_type))
+            }
+        };
+        match self.class_file.get_constant(index as usize)? {
+            ConstType::ConstantFieldref(class_index, name_and_type_index) => member("Field", *class_index, *name_and_type_index),
+            ConstType::ConstantMethodref(class_index, name_and_type_index) => member("Method", *class_index, *name_and_type_index),
+            ConstType::ConstantInterfaceMethodref(class_index, name_and_type_index) => member("InterfaceMethod", *class_index, *name_and_type_index),
+            ConstType::ConstantInvokeDynamic(_, _) => Ok(format!("InvokeDynamic {}", self.constant_comment(index)?.unwrap_or_default())),
+            _ => self.constant_value(index),
+        }
+    }
+
+    fn print_header(&mut self, path: &str, bytes: &[u8]) -> Result<(), Box<dyn Error>> {
+        let canonical = fs::canonicalize(path)?;
+        self.line(0, &format!("Classfile {}", canonical.display()));
+        let modified = fs::metadata(path)?.modified()?.duration_since(UNIX_EPOCH)?.as_secs();
+        self.line(2, &format!("Last modified {}; size {} bytes", format_date(modified), bytes.len()));
+        self.line(2, &format!("SHA-256 checksum {}", sha256::hex_digest(bytes)));
+        for attribute_info in self.class_file.get_attributes() {
+            if let Attribute::SourceFile(source_file_index) = attribute_info.to_attribute(self.class_file)? {
+                self.line(2, &format!("Compiled from \"{}\"", self.utf8(source_file_index)?));
+            }
+        }
+        Ok(())
+    }
+
+    fn print_class_declaration(&mut self) -> Result<(), ParseError> {
+        let class_file = self.class_file;
+        let flags = class_file.get_access_flags();
+        let name = java_name(&self.this_class_name()?);
+        let mut declaration = String::new();
+        // Like javap, name a module after its Module attribute rather than after `module-info`.
+        let module = class_file.get_attributes().iter().find_map(|attribute_info| match attribute_info.to_attribute(class_file) {
+            Ok(Attribute::Module(module)) if flags.is_module() => Some(module),
+            _ => None,
+        });
+        if let Some(module) = &module {
+            if module.get_module_flags().is_open() {
+                declaration.push_str("open ");
+            }
+            declaration.push_str(&format!("module {}", class_file.get_name_of_module(module.get_module_name_index() as usize)?));
+            if module.get_module_version_index() != 0 {
+                declaration.push_str(&format!("@{}", self.utf8(module.get_module_version_index())?));
+            }
+        } else if flags.is_module() {
+            declaration.push_str(&format!("module {}", name));
+        } else {
+            if flags.is_public() {
+                declaration.push_str("public ");
+            }
+            let signature = match self.signature(class_file.get_attributes())? {
+                Some(signature) => Some(descriptor::parse_class_signature(&signature)?),
+                None => None,
+            };
+            let name = match &signature {
+                Some(signature) if !signature.get_type_parameters().is_empty() => format!("{}<{}>", name, type_parameter_list(signature.get_type_parameters())),
+                _ => name,
+            };
+            if flags.is_interface() {
+                declaration.push_str(&format!("interface {}", name));
+            } else {
+                if flags.is_final() {
+                    declaration.push_str("final ");
+                }
+                if flags.is_abstract() {
+                    declaration.push_str("abstract ");
+                }
+                declaration.push_str(&format!("class {}", name));
+                if let Some(signature) = &signature {
+                    declaration.push_str(&format!(" extends {}", signature.get_superclass()));
+                } else if class_file.get_super_class() != 0 {
+                    let super_name = class_file.get_name_of_class(class_file.get_super_class() as usize)?;
+                    if super_name != "java/lang/Object" {
+                        declaration.push_str(&format!(" extends {}", java_name(&super_name)));
+                    }
+                }
+            }
+            // javap separates the interfaces with ", " when they come from the signature and with
+            // "," otherwise.
+            let (interfaces, separator) = match &signature {
+                Some(signature) => (signature.get_interfaces().iter().map(|interface| interface.to_string()).collect(), ", "),
+                None => (class_file.get_interfaces().iter()
+                    .map(|interface| class_file.get_name_of_class(*interface as usize).map(|name| java_name(&name)))
+                    .collect::<Result<Vec<String>, ParseError>>()?, ","),
+            };
+            if !interfaces.is_empty() {
+                let keyword = if flags.is_interface() { "extends" } else { "implements" };
+                declaration.push_str(&format!(" {} {}", keyword, interfaces.join(separator)));
+            }
+        }
+        self.line(0, &declaration);
+        self.line(2, &format!("minor version: {}", class_file.get_minor_version()));
+        self.line(2, &format!("major version: {}", class_file.get_major_version()));
+        self.line(2, &format!("flags: {}", flags));
+        let this_class = class_file.get_this_class();
+        self.line_with_comment(2, &format!("this_class: #{}", this_class), &self.constant_comment(this_class)?.unwrap_or_default());
+        let super_class = class_file.get_super_class();
+        if super_class == 0 {
+            self.line(2, "super_class: #0");
+        } else {
+            self.line_with_comment(2, &format!("super_class: #{}", super_class), &self.constant_comment(super_class)?.unwrap_or_default());
+        }
+        self.line(2, &format!("interfaces: {}, fields: {}, methods: {}, attributes: {}",
+            class_file.get_interfaces().len(), class_file.get_fields().len(), class_file.get_methods().len(), class_file.get_attributes().len()));
+        Ok(())
+    }
+
+    fn print_constant_pool(&mut self) -> Result<(), ParseError> {
+        self.line(0, "Constant pool:");
+        let constant_pool = self.class_file.get_constant_pool();
+        let width = format!("#{}", constant_pool.len()).len();
+        for (position, constant) in constant_pool.iter().enumerate() {
+            let index = (position + 1) as u16;
+            let (tag, arguments) = match constant {
+                ConstType::Unusable => continue,
+                ConstType::ConstantUtf8(_) => ("Utf8", escape(&self.utf8(index)?)),
+                ConstType::ConstantInteger(bytes) => ("Integer", (*bytes as i32).to_string()),
+                ConstType::ConstantFloat(bytes) => ("Float", format!("{}f", number_format::float_to_string(f32::from_bits(*bytes)))),
+                ConstType::ConstantLong(high_bytes, low_bytes) => ("Long", format!("{}l", ((*high_bytes as u64) << 32 | *low_bytes as u64) as i64)),
+                ConstType::ConstantDouble(high_bytes, low_bytes) => {
+                    ("Double", format!("{}d", number_format::double_to_string(f64::from_bits((*high_bytes as u64) << 32 | *low_bytes as u64))))
+                }
+                ConstType::ConstantClass(name_index) => ("Class", format!("#{}", name_index)),
+                ConstType::ConstantString(string_index) => ("String", format!("#{}", string_index)),
+                ConstType::ConstantFieldref(class_index, name_and_type_index) => ("Fieldref", format!("#{}.#{}", class_index, name_and_type_index)),
+                ConstType::ConstantMethodref(class_index, name_and_type_index) => ("Methodref", format!("#{}.#{}", class_index, name_and_type_index)),
+                ConstType::ConstantInterfaceMethodref(class_index, name_and_type_index) => {
+                    ("InterfaceMethodref", format!("#{}.#{}", class_index, name_and_type_index))
+                }
+                ConstType::ConstantNameAndType(name_index, descriptor_index) => ("NameAndType", format!("#{}:#{}", name_index, descriptor_index)),
+                ConstType::ConstantMethodHandle(reference_kind, reference_index) => ("MethodHandle", format!("{}:#{}", reference_kind, reference_index)),
+                ConstType::ConstantMethodType(descriptor_index) => ("MethodType", format!("#{}", descriptor_index)),
+                ConstType::ConstantDynamic(bootstrap_method_attr_index, name_and_type_index) => {
+                    ("Dynamic", format!("#{}:#{}", bootstrap_method_attr_index, name_and_type_index))
+                }
+                ConstType::ConstantInvokeDynamic(bootstrap_method_attr_index, name_and_type_index) => {
+                    ("InvokeDynamic", format!("#{}:#{}", bootstrap_method_attr_index, name_and_type_index))
+                }
+                ConstType::ConstantModule(name_index) => ("Module", format!("#{}", name_index)),
+                ConstType::ConstantPackage(name_index) => ("Package", format!("#{}", name_index)),
+            };
+            let text = format!("{:>width$} = {:<18} {}", format!("#{}", index), tag, arguments, width = width);
+            match self.constant_comment(index)? {
+                Some(comment) if !matches!(constant, ConstType::ConstantUtf8(_)) => self.line_with_comment(2, &text, &comment),
+                _ => self.line(2, &text),
+            }
+        }
+        Ok(())
+    }
+
+    fn print_field(&mut self, field: &crate::class_file::field_info::FieldInfo) -> Result<(), ParseError> {
+        let flags = field.get_access_flags();
+        let descriptor = self.utf8(field.get_descriptor_index())?;
+        let name = self.utf8(field.get_name_index())?;
+        let field_type = self.field_type(&descriptor, &field.get_attributes())?;
+        self.line(2, &format!("{}{} {};", modifiers(flags.get_modifiers()), field_type, name));
+        self.line(4, &format!("descriptor: {}", descriptor));
+        self.line(4, &format!("flags: {}", flags));
+        for attribute_info in field.get_attributes().iter() {
+            self.print_attribute(4, attribute_info, 0)?;
+        }
+        Ok(())
+    }
+
+    fn print_method(&mut self, method: &crate::class_file::method_info::MethodInfo) -> Result<(), ParseError> {
+        let flags = method.get_access_flags();
+        let descriptor = self.utf8(method.get_descriptor_index())?;
+        let name = self.utf8(method.get_name_index())?;
+        let method_descriptor = descriptor::parse_method_descriptor(&descriptor)?;
+        let mut type_parameters = String::new();
+        let mut parameters: Vec<String> = method_descriptor.get_parameters().iter().map(|parameter| parameter.to_string()).collect();
+        let mut return_type = method_descriptor.get_return_type().map(|return_type| return_type.to_string()).unwrap_or(String::from("void"));
+        let mut throws = Vec::new();
+        for attribute_info in method.get_attributes().iter() {
+            if let Attribute::Exceptions(exceptions) = attribute_info.to_attribute(self.class_file)? {
+                for exception in exceptions {
+                    throws.push(java_name(&self.class_file.get_name_of_class(exception as usize)?));
+                }
+            }
+        }
+        if let Some(signature) = self.signature(&method.get_attributes())? {
+            let signature = descriptor::parse_method_signature(&signature)?;
+            if !signature.get_type_parameters().is_empty() {
+                type_parameters = format!("<{}> ", type_parameter_list(signature.get_type_parameters()));
+            }
+            parameters = signature.get_parameters().iter().map(|parameter| parameter.to_string()).collect();
+            return_type = signature.get_result().map(|result| result.to_string()).unwrap_or(String::from("void"));
+            if !signature.get_throws().is_empty() {
+                // javap prints class types of a generic throws clause in internal form.
+                throws = signature.get_throws().iter().map(|exception| match exception {
+                    descriptor::ReferenceTypeSignature::Class(class_type) => class_type.get_class_name(),
+                    exception => exception.to_string(),
+                }).collect();
+            }
+        }
+        let throws = if throws.is_empty() { String::new() } else { format!(" throws {}", throws.join(", ")) };
+        let mut method_modifiers = flags.get_modifiers();
+        if self.class_file.get_access_flags().is_interface() && !flags.is_abstract() && !flags.is_static() && !flags.is_private() {
+            // A default method follows its access modifier.
+            let position = method_modifiers.iter().take_while(|modifier| matches!(**modifier, "public" | "protected")).count();
+            method_modifiers.insert(position, "default");
+        }
+        let declaration = match name.as_str() {
+            "<clinit>" => String::from("static {}"),
+            "<init>" => format!("{}{}{}({}){}", modifiers(method_modifiers), type_parameters, java_name(&self.this_class_name()?), parameters.join(", "), throws),
+            _ => format!("{}{}{} {}({}){}", modifiers(method_modifiers), type_parameters, return_type, name, parameters.join(", "), throws),
+        };
+        self.line(2, &format!("{};", declaration));
+        self.line(4, &format!("descriptor: {}", descriptor));
+        self.line(4, &format!("flags: {}", flags));
+        let argument_count = method_descriptor.get_parameters().len() + if flags.is_static() { 0 } else { 1 };
+        for attribute_info in method.get_attributes().iter() {
+            self.print_attribute(4, attribute_info, argument_count)?;
+        }
+        Ok(())
+    }
+
+    fn print_code(&mut self, indent: usize, code_attribute: &CodeAttribute, argument_count: usize) -> Result<(), ParseError> {
+        self.line(indent, "Code:");
+        self.line(indent + 2, &format!("stack={}, locals={}, args_size={}", code_attribute.get_max_stack(), code_attribute.get_max_locals(), argument_count));
+        self.print_instructions(indent + 2, &code_attribute.get_instructions()?)?;
+        let exception_table = code_attribute.get_exception_table();
+        if !exception_table.is_empty() {
+            self.line(indent + 2, "Exception table:");
+            self.line(indent + 5, "from    to  target type");
+            for entry in exception_table {
+                let catch_type = if entry.get_catch_type() == 0 {
+                    String::from("any")
+                } else {
+                    format!("Class {}", self.class_file.get_name_of_class(entry.get_catch_type() as usize)?)
+                };
+                self.line(indent + 5, &format!("{:5} {:5} {:5}   {}", entry.get_start_pc(), entry.get_end_pc(), entry.get_handler_pc(), catch_type));
+            }
+        }
+        for attribute_info in code_attribute.get_attributes() {
+            self.print_attribute(indent + 2, &attribute_info, 0)?;
+        }
+        Ok(())
+    }
+
+    fn print_instructions(&mut self, indent: usize, instructions: &[(usize, Insn)]) -> Result<(), ParseError> {
+        for (pc, insn) in instructions {
+            let prefix = format!("{:4}: {:<13}", pc, insn.get_mnemonic());
+            match insn {
+                Insn::Bipush(value) => self.line(indent, &format!("{} {}", prefix, value)),
+                Insn::Sipush(value) => self.line(indent, &format!("{} {}", prefix, value)),
+                Insn::Iinc { index, constant } => self.line(indent, &format!("{} {}, {}", prefix, index, constant)),
+                Insn::Newarray(array_type) => self.line(indent, &format!("{}  {}", prefix, array_type_name(*array_type))),
+                Insn::Invokeinterface { index, count } | Insn::Multianewarray { index, dimensions: count } => {
+                    let comment = self.instruction_comment(*index)?;
+                    self.line_with_comment(indent, &format!("{} #{},  {}", prefix, index, count), &comment);
+                }
+                Insn::Invokedynamic(index) => {
+                    let comment = self.instruction_comment(*index)?;
+                    self.line_with_comment(indent, &format!("{} #{},  0", prefix, index), &comment);
+                }
+                Insn::Tableswitch { low, high, .. } => {
+                    let keys: Vec<i64> = (*low as i64..=*high as i64).collect();
+                    self.print_switch(indent, &prefix, &format!("{} to {}", low, high), &keys, &insn.get_branch_targets(*pc));
+                }
+                Insn::Lookupswitch { pairs, .. } => {
+                    let keys: Vec<i64> = pairs.iter().map(|(key, _offset)| *key as i64).collect();
+                    self.print_switch(indent, &prefix, &pairs.len().to_string(), &keys, &insn.get_branch_targets(*pc));
+                }
+                Insn::Wide(modified) => {
+                    let name = format!("{}_w", modified.get_mnemonic());
+                    match modified.as_ref() {
+                        Insn::Iinc { index, constant } => self.line(indent, &format!("{:4}: {:<13} {}, {}", pc, name, index, constant)),
+                        _ => self.line(indent, &format!("{:4}: {:<13} {}", pc, name, modified.get_local_index().unwrap_or_default())),
+                    }
+                }
+                _ => {
+                    if let Some(index) = insn.get_constant_index() {
+                        let comment = self.instruction_comment(index)?;
+                        self.line_with_comment(indent, &format!("{} #{}", prefix, index), &comment);
+                    } else if let Some(offset) = insn.get_branch_offset() {
+                        self.line(indent, &format!("{} {}", prefix, *pc as i64 + offset as i64));
+                    } else if let (Some(index), false) = (insn.get_local_index(), insn.get_mnemonic().ends_with(|c: char| c.is_ascii_digit())) {
+                        self.line(indent, &format!("{} {}", prefix, index));
+                    } else {
+                        self.line(indent, &format!("{:4}: {}", pc, insn.get_mnemonic()));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `targets` holds the target of every key followed by the default target.
+    fn print_switch(&mut self, indent: usize, prefix: &str, header: &str, keys: &[i64], targets: &[i64]) {
+        self.line(indent, &format!("{} {{ // {}", prefix, header));
+        for (key, target) in keys.iter().zip(targets) {
+            self.line(indent, &format!("{:>18}: {}", key, target));
+        }
+        self.line(indent, &format!("{:>18}: {}", "default", targets[targets.len() - 1]));
+        self.line(indent, "      }");
+    }
+
+    fn print_local_variables(&mut self, indent: usize, local_variables: &[LocalVariable]) -> Result<(), ParseError> {
+        self.line(indent + 2, "Start  Length  Slot  Name   Signature");
+        for local_variable in local_variables {
+            self.line(indent + 2, &format!("{:5} {:7} {:5} {:>5}   {}", local_variable.get_start_pc(), local_variable.get_length(),
+                local_variable.get_index(), self.utf8(local_variable.get_name_index())?, self.utf8(local_variable.get_descriptor_index())?));
+        }
+        Ok(())
+    }
+
+    fn verification_type(&self, verification_type: &VerificationType) -> Result<String, ParseError> {
+        Ok(match verification_type {
+            VerificationType::Top => String::from("top"),
+            VerificationType::Integer => String::from("int"),
+            VerificationType::Float => String::from("float"),
+            VerificationType::Double => String::from("double"),
+            VerificationType::Long => String::from("long"),
+            VerificationType::Null => String::from("null"),
+            VerificationType::UninitializedThis => String::from("this"),
+            VerificationType::Object(class_index) => format!("class {}", self.constant_comment(*class_index)?.unwrap_or_default()),
+            VerificationType::Uninitialized(offset) => format!("uninitialized {}", offset),
+        })
+    }
+
+    fn verification_types(&self, verification_types: &[VerificationType]) -> Result<String, ParseError> {
+        if verification_types.is_empty() {
+            return Ok(String::from("[]"));
+        }
+        let types = verification_types.iter().map(|verification_type| self.verification_type(verification_type))
+            .collect::<Result<Vec<String>, ParseError>>()?;
+        Ok(format!("[ {} ]", types.join(", ")))
+    }
+
+    fn print_stack_map_table(&mut self, indent: usize, frames: &[StackMapFrame]) -> Result<(), ParseError> {
+        self.line(indent, &format!("StackMapTable: number_of_entries = {}", frames.len()));
+        for frame in frames {
+            match frame {
+                StackMapFrame::Same { offset_delta } => {
+                    self.line(indent + 2, &format!("frame_type = {} /* same */", offset_delta));
+                }
+                StackMapFrame::SameExtended { offset_delta } => {
+                    self.line(indent + 2, "frame_type = 251 /* same_frame_extended */");
+                    self.line(indent + 4, &format!("offset_delta = {}", offset_delta));
+                }
+                StackMapFrame::SameLocals1StackItem { offset_delta, stack } => {
+                    self.line(indent + 2, &format!("frame_type = {} /* same_locals_1_stack_item */", offset_delta + 64));
+                    self.line(indent + 4, &format!("stack = {}", self.verification_types(std::slice::from_ref(stack))?));
+                }
+                StackMapFrame::SameLocals1StackItemExtended { offset_delta, stack } => {
+                    self.line(indent + 2, "frame_type = 247 /* same_locals_1_stack_item_frame_extended */");
+                    self.line(indent + 4, &format!("offset_delta = {}", offset_delta));
+                    self.line(indent + 4, &format!("stack = {}", self.verification_types(std::slice::from_ref(stack))?));
+                }
+                StackMapFrame::Chop { offset_delta, chopped } => {
+                    self.line(indent + 2, &format!("frame_type = {} /* chop */", 251 - *chopped as u16));
+                    self.line(indent + 4, &format!("offset_delta = {}", offset_delta));
+                }
+                StackMapFrame::Append { offset_delta, locals } => {
+                    self.line(indent + 2, &format!("frame_type = {} /* append */", 251 + locals.len()));
+                    self.line(indent + 4, &format!("offset_delta = {}", offset_delta));
+                    self.line(indent + 4, &format!("locals = {}", self.verification_types(locals)?));
+                }
+                StackMapFrame::Full { offset_delta, locals, stack } => {
+                    self.line(indent + 2, "frame_type = 255 /* full_frame */");
+                    self.line(indent + 4, &format!("offset_delta = {}", offset_delta));
+                    self.line(indent + 4, &format!("locals = {}", self.verification_types(locals)?));
+                    self.line(indent + 4, &format!("stack = {}", self.verification_types(stack)?));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn element_value_raw(&self, value: &ElementValue) -> String {
+        match value {
+            ElementValue::Const { tag, const_value_index } => format!("{}#{}", tag, const_value_index),
+            ElementValue::Enum { type_name_index, const_name_index } => format!("e#{}.#{}", type_name_index, const_name_index),
+            ElementValue::Class(class_info_index) => format!("c#{}", class_info_index),
+            ElementValue::Annotation(annotation) => format!("@{}", self.annotation_raw(annotation)),
+            ElementValue::Array(values) => {
+                format!("[{}]", values.iter().map(|value| self.element_value_raw(value)).collect::<Vec<String>>().join(","))
+            }
+        }
+    }
+
+    fn annotation_raw(&self, annotation: &Annotation) -> String {
+        let pairs: Vec<String> = annotation.get_element_value_pairs().iter()
+            .map(|pair| format!("#{}={}", pair.get_element_name_index(), self.element_value_raw(pair.get_value())))
+            .collect();
+        format!("#{}({})", annotation.get_type_index(), pairs.join(","))
+    }
+
+    fn element_value(&mut self, indent: usize, value: &ElementValue) -> Result<String, ParseError> {
+        Ok(match value {
+            ElementValue::Const { tag, const_value_index } => {
+                let constant = self.class_file.get_constant(*const_value_index as usize)?;
+                match (tag, constant) {
+                    ('s', _) => format!("\"{}\"", escape(&self.utf8(*const_value_index)?)),
+                    ('Z', ConstType::ConstantInteger(bytes)) => (*bytes != 0).to_string(),
+                    ('C', ConstType::ConstantInteger(bytes)) => {
+                        format!("'{}'", escape(&char::from_u32(*bytes).map(String::from).unwrap_or_default()))
+                    }
+                    _ => {
+                        let value = self.constant_value(*const_value_index)?;
+                        value.split_once(' ').map(|(_kind, value)| value.to_string()).unwrap_or(value)
+                    }
+                }
+            }
+            ElementValue::Enum { type_name_index, const_name_index } => {
+                format!("{}.{}", self.utf8(*type_name_index)?, self.utf8(*const_name_index)?)
+            }
+            ElementValue::Class(class_info_index) => format!("class {}", self.utf8(*class_info_index)?),
+            ElementValue::Annotation(annotation) => {
+                let mut nested = Printer { class_file: self.class_file, out: String::new() };
+                nested.print_annotation(indent, annotation, "@")?;
+                nested.out.trim().to_string()
+            }
+            ElementValue::Array(values) => {
+                let values = values.iter().map(|value| self.element_value(indent, value)).collect::<Result<Vec<String>, ParseError>>()?;
+                format!("[{}]", values.join(","))
+            }
+        })
+    }
+
+    fn print_annotation(&mut self, indent: usize, annotation: &Annotation, prefix: &str) -> Result<(), ParseError> {
+        let type_name = descriptor::parse_field_descriptor(&self.utf8(annotation.get_type_index())?)?;
+        if annotation.get_element_value_pairs().is_empty() {
+            self.line(indent, &format!("{}{}", prefix, type_name));
+            return Ok(());
+        }
+        self.line(indent, &format!("{}{}(", prefix, type_name));
+        for pair in annotation.get_element_value_pairs() {
+            let value = self.element_value(indent + 2, pair.get_value())?;
+            self.line(indent + 2, &format!("{}={}", self.utf8(pair.get_element_name_index())?, value));
+        }
+        self.line(indent, ")");
+        Ok(())
+    }
+
+    fn print_annotations(&mut self, indent: usize, annotations: &[Annotation]) -> Result<(), ParseError> {
+        for (index, annotation) in annotations.iter().enumerate() {
+            self.line(indent, &format!("{}: {}", index, self.annotation_raw(annotation)));
+            self.print_annotation(indent + 2, annotation, "")?;
+        }
+        Ok(())
+    }
+
+    fn print_type_annotations(&mut self, indent: usize, annotations: &[TypeAnnotation]) -> Result<(), ParseError> {
+        for (index, annotation) in annotations.iter().enumerate() {
+            let mut target = String::from(target_type_name(annotation.get_target_type()));
+            match annotation.get_target_info() {
+                TargetInfo::TypeParameter { type_parameter_index } => target.push_str(&format!(", param_index={}", type_parameter_index)),
+                TargetInfo::Supertype { supertype_index } => target.push_str(&format!(", type_index={}", supertype_index)),
+                TargetInfo::TypeParameterBound { type_parameter_index, bound_index } => {
+                    target.push_str(&format!(", param_index={}, bound_index={}", type_parameter_index, bound_index))
+                }
+                TargetInfo::Empty => {}
+                TargetInfo::FormalParameter { formal_parameter_index } => target.push_str(&format!(", param_index={}", formal_parameter_index)),
+                TargetInfo::Throws { throws_type_index } => target.push_str(&format!(", type_index={}", throws_type_index)),
+                TargetInfo::Localvar(table) => {
+                    let entries: Vec<String> = table.iter()
+                        .map(|entry| format!("start_pc={}, length={}, index={}", entry.get_start_pc(), entry.get_length(), entry.get_index()))
+                        .collect();
+                    target.push_str(&format!(", {{{}}}", entries.join("; ")));
+                }
+                TargetInfo::Catch { exception_table_index } => target.push_str(&format!(", exception_index={}", exception_table_index)),
+                TargetInfo::Offset { offset } => target.push_str(&format!(", offset={}", offset)),
+                TargetInfo::TypeArgument { offset, type_argument_index } => {
+                    target.push_str(&format!(", offset={}, type_index={}", offset, type_argument_index))
+                }
+            }
+            if !annotation.get_target_path().is_empty() {
+                let path: Vec<String> = annotation.get_target_path().iter().map(|entry| match entry.get_type_path_kind() {
+                    0 => String::from("ARRAY"),
+                    1 => String::from("INNER_TYPE"),
+                    2 => String::from("WILDCARD"),
+                    _ => format!("TYPE_ARGUMENT({})", entry.get_type_argument_index()),
+                }).collect();
+                target.push_str(&format!(", location=[{}]", path.join(", ")));
+            }
+            self.line(indent, &format!("{}: {}: {}", index, self.annotation_raw(annotation.get_annotation()), target));
+            self.print_annotation(indent + 2, annotation.get_annotation(), "")?;
+        }
+        Ok(())
+    }
+
+    fn print_parameter_annotations(&mut self, indent: usize, parameters: &[Vec<Annotation>]) -> Result<(), ParseError> {
+        for (index, annotations) in parameters.iter().enumerate() {
+            self.line(indent, &format!("parameter {}:", index));
+            self.print_annotations(indent + 2, annotations)?;
+        }
+        Ok(())
+    }
+
+    fn class_list(&mut self, indent: usize, title: &str, classes: &[u16]) -> Result<(), ParseError> {
+        self.line(indent, &format!("{}:", title));
+        for class_index in classes {
+            self.line(indent + 2, &self.class_file.get_name_of_class(*class_index as usize)?);
+        }
+        Ok(())
+    }
+
+    fn print_attribute(&mut self, indent: usize, attribute_info: &AttributeInfo, argument_count: usize) -> Result<(), ParseError> {
+        let name = self.utf8(attribute_info.get_attribute_name_index())?;
+        match attribute_info.to_attribute(self.class_file)? {
+            Attribute::ConstantValue(index) => self.line(indent, &format!("ConstantValue: {}", self.constant_value(index)?)),
+            Attribute::Code(code_attribute) => self.print_code(indent, &code_attribute, argument_count)?,
+            Attribute::StackMapTable(frames) => self.print_stack_map_table(indent, &frames)?,
+            Attribute::Exceptions(exceptions) => {
+                self.line(indent, "Exceptions:");
+                let names = exceptions.iter()
+                    .map(|exception| self.class_file.get_name_of_class(*exception as usize).map(|name| java_name(&name)))
+                    .collect::<Result<Vec<String>, ParseError>>()?;
+                self.line(indent + 2, &format!("throws {}", names.join(", ")));
+            }
+            Attribute::InnerClasses(inner_classes) => {
+                self.line(indent, "InnerClasses:");
+                for inner_class in inner_classes {
+                    let modifiers_text = modifiers(inner_class.get_inner_class_access_flags().get_modifiers());
+                    let inner_class_index = inner_class.get_inner_class_info_index();
+                    let inner_class_comment = format!("class {}", self.constant_comment(inner_class_index)?.unwrap_or_default());
+                    let (text, comment) = match (inner_class.get_inner_name_index(), inner_class.get_outer_class_info_index()) {
+                        (0, _) => (format!("#{};", inner_class_index), inner_class_comment),
+                        (inner_name_index, 0) => {
+                            (format!("#{}= #{};", inner_name_index, inner_class_index), format!("{}={}", self.utf8(inner_name_index)?, inner_class_comment))
+                        }
+                        (inner_name_index, outer_class_index) => (
+                            format!("#{}= #{} of #{};", inner_name_index, inner_class_index, outer_class_index),
+                            format!("{}={} of class {}", self.utf8(inner_name_index)?, inner_class_comment, self.constant_comment(outer_class_index)?.unwrap_or_default()),
+                        ),
+                    };
+                    self.line_with_comment(indent + 2, &format!("{}{}", modifiers_text, text), &comment);
+                }
+            }
+            Attribute::EnclosingMethod { class_index, method_index } => {
+                let class_name = self.constant_comment(class_index)?.unwrap_or_default();
+                let comment = if method_index == 0 {
+                    class_name
+                } else {
+                    format!("{}.{}", class_name, check_name(&self.class_file.get_name_of_member(method_index as usize)?))
+                };
+                self.line_with_comment(indent, &format!("EnclosingMethod: #{}.#{}", class_index, method_index), &comment);
+            }
+            Attribute::Synthetic => self.line(indent, "Synthetic: true"),
+            Attribute::Signature(signature_index) => {
+                self.line_with_comment(indent, &format!("Signature: #{}", signature_index), &self.utf8(signature_index)?);
+            }
+            Attribute::SourceFile(source_file_index) => self.line(indent, &format!("SourceFile: \"{}\"", self.utf8(source_file_index)?)),
+            Attribute::SourceDebugExtension(debug_extension) => {
+                self.line(indent, "SourceDebugExtension:");
+                for line in String::from_utf8_lossy(&debug_extension).lines() {
+                    self.line(indent + 2, line);
+                }
+            }
+            Attribute::LineNumberTable(line_numbers) => {
+                self.line(indent, "LineNumberTable:");
+                for line_number in line_numbers {
+                    self.line(indent + 2, &format!("line {}: {}", line_number.get_line_number(), line_number.get_start_pc()));
+                }
+            }
+            Attribute::LocalVariableTable(local_variables) => {
+                self.line(indent, "LocalVariableTable:");
+                self.print_local_variables(indent, &local_variables)?;
+            }
+            Attribute::LocalVariableTypeTable(local_variables) => {
+                self.line(indent, "LocalVariableTypeTable:");
+                self.print_local_variables(indent, &local_variables)?;
+            }
+            Attribute::Deprecated => self.line(indent, "Deprecated: true"),
+            Attribute::RuntimeVisibleAnnotations(annotations) | Attribute::RuntimeInvisibleAnnotations(annotations) => {
+                self.line(indent, &format!("{}:", name));
+                self.print_annotations(indent + 2, &annotations)?;
+            }
+            Attribute::RuntimeVisibleParameterAnnotations(parameters) | Attribute::RuntimeInvisibleParameterAnnotations(parameters) => {
+                self.line(indent, &format!("{}:", name));
+                self.print_parameter_annotations(indent + 2, &parameters)?;
+            }
+            Attribute::RuntimeVisibleTypeAnnotations(annotations) | Attribute::RuntimeInvisibleTypeAnnotations(annotations) => {
+                self.line(indent, &format!("{}:", name));
+                self.print_type_annotations(indent + 2, &annotations)?;
+            }
+            Attribute::AnnotationDefault(value) => {
+                self.line(indent, "AnnotationDefault:");
+                self.line(indent + 2, &format!("default_value: {}", self.element_value_raw(&value)));
+                match &value {
+                    ElementValue::Annotation(annotation) => self.print_annotation(indent + 4, annotation, "@")?,
+                    _ => {
+                        let text = self.element_value(indent + 4, &value)?;
+                        self.line(indent + 4, &text);
+                    }
+                }
+            }
+            Attribute::BootstrapMethods(bootstrap_methods) => {
+                self.line(indent, "BootstrapMethods:");
+                for (index, bootstrap_method) in bootstrap_methods.iter().enumerate() {
+                    let reference = bootstrap_method.get_bootstrap_method_ref();
+                    self.line(indent + 2, &format!("{}: #{} {}", index, reference, self.constant_comment(reference)?.unwrap_or_default()));
+                    self.line(indent + 4, "Method arguments:");
+                    for argument in bootstrap_method.get_bootstrap_arguments() {
+                        let text = match self.class_file.get_constant(*argument as usize)? {
+                            ConstType::ConstantString(_) | ConstType::ConstantClass(_) | ConstType::ConstantMethodHandle(_, _) => {
+                                self.constant_comment(*argument)?.unwrap_or_default()
+                            }
+                            ConstType::ConstantMethodType(_) => self.constant_comment(*argument)?.unwrap_or_default().trim().to_string(),
+                            _ => {
+                                let value = self.constant_value(*argument)?;
+                                value.split_once(' ').map(|(_kind, value)| value.to_string()).unwrap_or(value)
+                            }
+                        };
+                        self.line(indent + 6, &format!("#{} {}", argument, text));
+                    }
+                }
+            }
+            Attribute::MethodParameters(parameters) => {
+                self.line(indent, "MethodParameters:");
+                self.line(indent + 2, &format!("{:<30} {}", "Name", "Flags"));
+                for parameter in parameters {
+                    let name = if parameter.get_name_index() == 0 { String::from("<no name>") } else { self.utf8(parameter.get_name_index())? };
+                    self.line(indent + 2, &format!("{:<30} {}", name, parameter.get_access_flags().get_modifiers().join(" ")));
+                }
+            }
+            Attribute::Module(module) => {
+                self.line(indent, "Module:");
+                let module_name = self.constant_comment(module.get_module_name_index())?.unwrap_or_default();
+                let module_flags = module.get_module_flags();
+                self.line_with_comment(indent + 2, &format!("#{},{:x}", module.get_module_name_index(), module_flags.get_bits()),
+                    &with_flag_names(module_name, module_flags.get_names()));
+                self.version_line(indent + 2, module.get_module_version_index())?;
+                self.line_with_comment(indent + 2, &module.get_requires().len().to_string(), "requires");
+                for requires in module.get_requires() {
+                    let required = self.constant_comment(requires.get_requires_index())?.unwrap_or_default();
+                    let requires_flags = requires.get_requires_flags();
+                    self.line_with_comment(indent + 4, &format!("#{},{:x}", requires.get_requires_index(), requires_flags.get_bits()),
+                        &with_flag_names(required, requires_flags.get_names()));
+                    self.version_line(indent + 4, requires.get_requires_version_index())?;
+                }
+                for (title, entries) in [("exports", module.get_exports()), ("opens", module.get_opens())] {
+                    self.line_with_comment(indent + 2, &entries.len().to_string(), title);
+                    for entry in entries {
+                        let mut package = with_flag_names(self.constant_comment(entry.get_package_index())?.unwrap_or_default(), entry.get_flags().get_names());
+                        if !entry.get_to_indices().is_empty() {
+                            package.push_str(&format!(" to ... {}", entry.get_to_indices().len()));
+                        }
+                        self.line_with_comment(indent + 4, &format!("#{},{:x}", entry.get_package_index(), entry.get_flags().get_bits()), &package);
+                        for to_index in entry.get_to_indices() {
+                            let target = self.constant_comment(*to_index)?.unwrap_or_default();
+                            self.line_with_comment(indent + 6, &format!("#{}", to_index), &format!("... to {}", target));
+                        }
+                    }
+                }
+                self.line_with_comment(indent + 2, &module.get_uses().len().to_string(), "uses");
+                for uses in module.get_uses() {
+                    self.line_with_comment(indent + 4, &format!("#{}", uses), &self.constant_comment(*uses)?.unwrap_or_default());
+                }
+                self.line_with_comment(indent + 2, &module.get_provides().len().to_string(), "provides");
+                for provides in module.get_provides() {
+                    let mut provided = self.constant_comment(provides.get_provides_index())?.unwrap_or_default();
+                    if !provides.get_provides_with_indices().is_empty() {
+                        provided.push_str(&format!(" with ... {}", provides.get_provides_with_indices().len()));
+                    }
+                    self.line_with_comment(indent + 4, &format!("#{}", provides.get_provides_index()), &provided);
+                    for with_index in provides.get_provides_with_indices() {
+                        let implementation = self.constant_comment(*with_index)?.unwrap_or_default();
+                        self.line_with_comment(indent + 6, &format!("#{}", with_index), &format!("... with {}", implementation));
+                    }
+                }
+            }
+            Attribute::ModulePackages(packages) => {
+                self.line(indent, "ModulePackages:");
+                for package in packages {
+                    self.line_with_comment(indent + 2, &format!("#{}", package), &self.constant_comment(package)?.unwrap_or_default());
+                }
+            }
+            Attribute::ModuleMainClass(main_class_index) => {
+                let main_class = self.constant_comment(main_class_index)?.unwrap_or_default();
+                self.line_with_comment(indent, &format!("ModuleMainClass: #{}", main_class_index), &main_class);
+            }
+            Attribute::NestHost(host_class_index) => {
+                self.line(indent, &format!("NestHost: class {}", self.constant_comment(host_class_index)?.unwrap_or_default()));
+            }
+            Attribute::NestMembers(classes) => self.class_list(indent, "NestMembers", &classes)?,
+            Attribute::PermittedSubclasses(classes) => self.class_list(indent, "PermittedSubclasses", &classes)?,
+            Attribute::Record(components) => {
+                self.line(indent, "Record:");
+                for component in components {
+                    let descriptor = self.utf8(component.get_descriptor_index())?;
+                    let component_type = self.field_type(&descriptor, component.get_attributes())?;
+                    self.line(indent + 2, &format!("{} {};", component_type, self.utf8(component.get_name_index())?));
+                    self.line(indent + 4, &format!("descriptor: {}", descriptor));
+                    for attribute_info in component.get_attributes() {
+                        self.print_attribute(indent + 4, attribute_info, 0)?;
+                    }
+                    self.line(0, "");
+                }
+            }
+            Attribute::Unknown { name, info } => {
+                self.line(indent, &format!("{}: length = {:#x} (unknown attribute)", name, info.len()));
+                let bytes: Vec<String> = info.iter().map(|byte| format!("{:02x}", byte)).collect();
+                for chunk in bytes.chunks(16) {
+                    self.line(indent + 1, &chunk.join(" "));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Disassembles the class file read from `path`.
+pub fn disassemble(path: &str, bytes: &[u8], class_file: &ClassFile, options: &Options) -> Result<String, Box<dyn Error>> {
+    let mut printer = Printer { class_file, out: String::new() };
+    printer.print_header(path, bytes)?;
+    printer.print_class_declaration()?;
+    printer.print_constant_pool()?;
+    printer.line(0, "{");
+    let mut first = true;
+    for field in class_file.get_fields() {
+        if field.get_access_flags().is_private() && !options.show_private {
+            continue;
+        }
+        if !first {
+            printer.line(0, "");
+        }
+        first = false;
+        printer.print_field(field)?;
+    }
+    for method in class_file.get_methods() {
+        if method.get_access_flags().is_private() && !options.show_private {
+            continue;
+        }
+        if !first {
+            printer.line(0, "");
+        }
+        first = false;
+        printer.print_method(method)?;
+    }
+    printer.line(0, "}");
+    for attribute_info in class_file.get_attributes() {
+        printer.print_attribute(0, attribute_info, 0)?;
+    }
+    Ok(printer.out)
+}
+
+/// Quotes names that are not valid identifiers separated by slashes, like javap does.
+fn check_name(name: &str) -> String {
+    let mut previous = '/';
+    for c in name.chars() {
+        let valid = if previous == '/' {
+            c.is_alphabetic() || c == '_' || c == '$'
+        } else {
+            c == '/' || c.is_alphanumeric() || c == '_' || c == '$'
+        };
+        if !valid {
+            return format!("\"{}\"", escape(name));
+        }
+        previous = c;
+    }
+    name.to_string()
+}
+
+fn escape(string: &str) -> String {
+    let mut out = String::new();
+    for c in string.chars() {
+        match c {
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn reference_kind_name(reference_kind: u8) -> &'static str {
+    match reference_kind {
+        1 => "REF_getField",
+        2 => "REF_getStatic",
+        3 => "REF_putField",
+        4 => "REF_putStatic",
+        5 => "REF_invokeVirtual",
+        6 => "REF_invokeStatic",
+        7 => "REF_invokeSpecial",
+        8 => "REF_newInvokeSpecial",
+        9 => "REF_invokeInterface",
+        _ => "REF_unknown",
+    }
+}
+
+fn array_type_name(array_type: u8) -> &'static str {
+    match array_type {
+        4 => "boolean",
+        5 => "char",
+        6 => "float",
+        7 => "double",
+        8 => "byte",
+        9 => "short",
+        10 => "int",
+        11 => "long",
+        _ => "unknown",
+    }
+}
+
+fn target_type_name(target_type: u8) -> &'static str {
+    match target_type {
+        0x00 => "CLASS_TYPE_PARAMETER",
+        0x01 => "METHOD_TYPE_PARAMETER",
+        0x10 => "CLASS_EXTENDS",
+        0x11 => "CLASS_TYPE_PARAMETER_BOUND",
+        0x12 => "METHOD_TYPE_PARAMETER_BOUND",
+        0x13 => "FIELD",
+        0x14 => "METHOD_RETURN",
+        0x15 => "METHOD_RECEIVER",
+        0x16 => "METHOD_FORMAL_PARAMETER",
+        0x17 => "THROWS",
+        0x40 => "LOCAL_VARIABLE",
+        0x41 => "RESOURCE_VARIABLE",
+        0x42 => "EXCEPTION_PARAMETER",
+        0x43 => "INSTANCEOF",
+        0x44 => "NEW",
+        0x45 => "CONSTRUCTOR_REFERENCE",
+        0x46 => "METHOD_REFERENCE",
+        0x47 => "CAST",
+        0x48 => "CONSTRUCTOR_INVOCATION_TYPE_ARGUMENT",
+        0x49 => "METHOD_INVOCATION_TYPE_ARGUMENT",
+        0x4A => "CONSTRUCTOR_REFERENCE_TYPE_ARGUMENT",
+        0x4B => "METHOD_REFERENCE_TYPE_ARGUMENT",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Appends flag names to a constant comment, e.g. `"java.base" ACC_MANDATED`.
+fn with_flag_names(comment: String, names: Vec<&str>) -> String {
+    names.iter().fold(comment, |comment, name| format!("{} {}", comment, name))
+}
+
+fn java_name(internal_name: &str) -> String {
+    internal_name.replace('/', ".")
+}
+
+/// Formats type parameters like `javap -v`, which unlike Java source also shows `java.lang.Object`
+/// bounds, e.g. `T extends java.lang.Object, U extends java.lang.Runnable`.
+fn type_parameter_list(type_parameters: &[descriptor::TypeParameter]) -> String {
+    type_parameters.iter().map(|type_parameter| {
+        let bounds: Vec<String> = type_parameter.get_class_bound().into_iter().chain(type_parameter.get_interface_bounds())
+            .map(|bound| bound.to_string())
+            .collect();
+        format!("{} extends {}", type_parameter.get_name(), bounds.join(" & "))
+    }).collect::<Vec<String>>().join(", ")
+}
+
+/// Formats seconds since the epoch like javap's "Oct 18, 2026", in UTC.
+fn format_date(seconds: u64) -> String {
+    const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+    let days = (seconds / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{} {}, {}", MONTHS[(month - 1) as usize], day, year)
+}
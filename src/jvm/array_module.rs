@@ -0,0 +1,190 @@
+use super::{Frame, Heap, InstructionModule, JVMError, JVMEvent, OperandFrame};
+use super::heap::{HeapObject, Reference};
+use super::integer_module::IntegerModule;
+use crate::class_file::instruction::Insn;
+
+pub struct ArrayModule;
+
+impl ArrayModule {
+    /// Pops an array, throwing a `NullPointerException` with `message` if it is null.
+    pub fn try_to_pop_array(frame: &mut Frame, message: &str) -> Result<Reference, JVMError> {
+        match frame.pop_operand()? {
+            OperandFrame::Reference(reference) => Ok(reference),
+            OperandFrame::Null => Err(JVMError::NullPointerException(message.to_string())),
+            operand => Err(JVMError::Internal(format!("The operand {:?} is no reference", operand))),
+        }
+    }
+
+    /// The position of `index` in an array of `length` elements, or an
+    /// `ArrayIndexOutOfBoundsException` if it is outside.
+    pub fn check_index(index: i32, length: usize) -> Result<usize, JVMError> {
+        usize::try_from(index).ok().filter(|&position| position < length)
+            .ok_or_else(|| JVMError::ArrayIndexOutOfBoundsException(format!("Index {} out of bounds for length {}", index, length)))
+    }
+
+    /// The length of a new array, or a `NegativeArraySizeException` if `count` is negative.
+    pub fn check_count(count: i32) -> Result<usize, JVMError> {
+        usize::try_from(count).map_err(|_e| JVMError::NegativeArraySizeException(count.to_string()))
+    }
+
+    /// Pops an index and an array of primitives and pushes the element. `name` is the component
+    /// type as in the message of the `NullPointerException`, e.g. `int`.
+    fn load_primitive(frame: &mut Frame, heap: &Heap, name: &str) -> Result<JVMEvent, JVMError> {
+        let index = IntegerModule::try_to_pop_integer(frame)?;
+        let array = ArrayModule::try_to_pop_array(frame, &format!("Cannot load from {} array", name))?;
+        match heap.get(array) {
+            HeapObject::PrimitiveArray(elements) => {
+                let position = ArrayModule::check_index(index, elements.len())?;
+                frame.push_operand(elements.get(position));
+            }
+            object => return Err(JVMError::Internal(format!("Loading a {} from {:?} is not implemented", name, object))),
+        }
+        Ok(JVMEvent::None)
+    }
+
+    /// Pops a value, an index and an array of primitives and stores the value in the element.
+    fn store_primitive(frame: &mut Frame, heap: &mut Heap, name: &str) -> Result<JVMEvent, JVMError> {
+        let value = frame.pop_operand()?;
+        let index = IntegerModule::try_to_pop_integer(frame)?;
+        let array = ArrayModule::try_to_pop_array(frame, &format!("Cannot store to {} array", name))?;
+        match heap.get_mut(array) {
+            HeapObject::PrimitiveArray(elements) => {
+                let position = ArrayModule::check_index(index, elements.len())?;
+                elements.set(position, value)?;
+            }
+            object => return Err(JVMError::Internal(format!("Storing a {} in {:?} is not implemented", name, object))),
+        }
+        Ok(JVMEvent::None)
+    }
+}
+
+impl InstructionModule for ArrayModule {
+    fn get_instructions(&self) -> Vec<(u8 , super::Instruction)> {
+        vec![(188, super::Instruction {
+            name: String::from("newarray"),
+            handler: |frame, insn, _class_file, heap| {
+                let array_class = match insn {
+                    Insn::Newarray(4) => "[Z",
+                    Insn::Newarray(5) => "[C",
+                    Insn::Newarray(6) => "[F",
+                    Insn::Newarray(7) => "[D",
+                    Insn::Newarray(8) => "[B",
+                    Insn::Newarray(9) => "[S",
+                    Insn::Newarray(10) => "[I",
+                    Insn::Newarray(11) => "[J",
+                    insn => return Err(JVMError::Internal(format!("The instruction {:?} has no valid array type", insn))),
+                };
+                let length = ArrayModule::check_count(IntegerModule::try_to_pop_integer(frame)?)?;
+                let array = heap.allocate_array(array_class, length)?;
+                frame.push_operand(OperandFrame::Reference(array));
+                Ok(JVMEvent::None)
+            }
+        }),
+        (189, super::Instruction {
+            name: String::from("anewarray"),
+            handler: |_frame, insn, _class_file, _heap| {
+                Ok(JVMEvent::NewArray(insn.get_constant_index().unwrap_or_default() as usize))
+            }
+        }),
+        (197, super::Instruction {
+            name: String::from("multianewarray"),
+            handler: |_frame, insn, _class_file, _heap| {
+                match insn {
+                    &Insn::Multianewarray { index, dimensions } => Ok(JVMEvent::NewMultiArray(index as usize, dimensions as usize)),
+                    insn => Err(JVMError::Internal(format!("The instruction {:?} is no multianewarray", insn))),
+                }
+            }
+        }),
+        (190, super::Instruction {
+            name: String::from("arraylength"),
+            handler: |frame, _insn, _class_file, heap| {
+                let array = ArrayModule::try_to_pop_array(frame, "Cannot read the array length")?;
+                match heap.get(array).get_array_length() {
+                    Some(length) => frame.push_operand(OperandFrame::Int(length as i32)),
+                    None => return Err(JVMError::Internal(format!("arraylength is not implemented for {:?}", heap.get(array)))),
+                }
+                Ok(JVMEvent::None)
+            }
+        }),
+        (50, super::Instruction {
+            name: String::from("aaload"),
+            handler: |frame, _insn, _class_file, heap| {
+                let index = IntegerModule::try_to_pop_integer(frame)?;
+                let array = ArrayModule::try_to_pop_array(frame, "Cannot load from object array")?;
+                match heap.get(array) {
+                    HeapObject::ReferenceArray { elements, .. } => {
+                        match elements[ArrayModule::check_index(index, elements.len())?] {
+                            Some(reference) => frame.push_operand(OperandFrame::Reference(reference)),
+                            None => frame.push_operand(OperandFrame::Null),
+                        }
+                    }
+                    object => return Err(JVMError::Internal(format!("aaload is not implemented for {:?}", object))),
+                }
+                Ok(JVMEvent::None)
+            }
+        }),
+        (83, super::Instruction {
+            name: String::from("aastore"),
+            handler: |_frame, _insn, _class_file, _heap| {
+                // Whether the object may be stored depends on its class and the component type.
+                Ok(JVMEvent::StoreReference)
+            }
+        }),
+        (46, super::Instruction {
+            name: String::from("iaload"),
+            handler: |frame, _insn, _class_file, heap| ArrayModule::load_primitive(frame, heap, "int"),
+        }),
+        (47, super::Instruction {
+            name: String::from("laload"),
+            handler: |frame, _insn, _class_file, heap| ArrayModule::load_primitive(frame, heap, "long"),
+        }),
+        (48, super::Instruction {
+            name: String::from("faload"),
+            handler: |frame, _insn, _class_file, heap| ArrayModule::load_primitive(frame, heap, "float"),
+        }),
+        (49, super::Instruction {
+            name: String::from("daload"),
+            handler: |frame, _insn, _class_file, heap| ArrayModule::load_primitive(frame, heap, "double"),
+        }),
+        (51, super::Instruction {
+            name: String::from("baload"),
+            handler: |frame, _insn, _class_file, heap| ArrayModule::load_primitive(frame, heap, "byte/boolean"),
+        }),
+        (52, super::Instruction {
+            name: String::from("caload"),
+            handler: |frame, _insn, _class_file, heap| ArrayModule::load_primitive(frame, heap, "char"),
+        }),
+        (53, super::Instruction {
+            name: String::from("saload"),
+            handler: |frame, _insn, _class_file, heap| ArrayModule::load_primitive(frame, heap, "short"),
+        }),
+        (79, super::Instruction {
+            name: String::from("iastore"),
+            handler: |frame, _insn, _class_file, heap| ArrayModule::store_primitive(frame, heap, "int"),
+        }),
+        (80, super::Instruction {
+            name: String::from("lastore"),
+            handler: |frame, _insn, _class_file, heap| ArrayModule::store_primitive(frame, heap, "long"),
+        }),
+        (81, super::Instruction {
+            name: String::from("fastore"),
+            handler: |frame, _insn, _class_file, heap| ArrayModule::store_primitive(frame, heap, "float"),
+        }),
+        (82, super::Instruction {
+            name: String::from("dastore"),
+            handler: |frame, _insn, _class_file, heap| ArrayModule::store_primitive(frame, heap, "double"),
+        }),
+        (84, super::Instruction {
+            name: String::from("bastore"),
+            handler: |frame, _insn, _class_file, heap| ArrayModule::store_primitive(frame, heap, "byte/boolean"),
+        }),
+        (85, super::Instruction {
+            name: String::from("castore"),
+            handler: |frame, _insn, _class_file, heap| ArrayModule::store_primitive(frame, heap, "char"),
+        }),
+        (86, super::Instruction {
+            name: String::from("sastore"),
+            handler: |frame, _insn, _class_file, heap| ArrayModule::store_primitive(frame, heap, "short"),
+        }),]
+    }
+}
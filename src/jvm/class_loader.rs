@@ -0,0 +1,505 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+use crate::class_file::{self, const_type::ConstType, ClassFile};
+use crate::util::file;
+use crate::verifier;
+use super::jar::Jar;
+use super::JVMError;
+
+/// A directory or JAR file that classes are loaded from.
+pub enum ClassPathEntry {
+    Directory(PathBuf),
+    Jar(Jar),
+}
+
+impl ClassPathEntry {
+    /// The location of the entry, as printed by `-verbose:class`.
+    fn get_source(&self) -> String {
+        match self {
+            ClassPathEntry::Directory(directory) => format!("file:{}/", directory.display()),
+            ClassPathEntry::Jar(jar) => format!("file:{}", jar.get_path().display()),
+        }
+    }
+
+    fn read_class(&self, class_name: &str) -> Result<Option<Vec<u8>>, JVMError> {
+        match self {
+            ClassPathEntry::Directory(directory) => Ok(fs::read(directory.join(format!("{}.class", class_name))).ok()),
+            ClassPathEntry::Jar(jar) => jar.read_class(class_name),
+        }
+    }
+}
+
+/// An instance field in the layout of objects of a class.
+#[derive(Debug)]
+pub struct InstanceField {
+    /// The class declaring the field, in internal form.
+    class_name: String,
+    name: String,
+    descriptor: String,
+}
+
+impl InstanceField {
+    /// `Throwable.detailMessage`, the first field of every throwable object.
+    pub fn detail_message() -> InstanceField {
+        InstanceField { class_name: String::from("java/lang/Throwable"), name: String::from("detailMessage"), descriptor: String::from("Ljava/lang/String;") }
+    }
+
+    pub fn get_class_name(&self) -> &str {
+        &self.class_name
+    }
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn get_descriptor(&self) -> &str {
+        &self.descriptor
+    }
+}
+
+/// Loads classes from the class path on first use (JVMS §5.3) and keeps them in the method area.
+pub struct ClassLoader {
+    class_path: Vec<ClassPathEntry>,
+    /// Every loaded class, keyed by its binary name in internal form, e.g. `java/lang/Object`.
+    method_area: HashMap<String, Rc<ClassFile>>,
+    /// The instance fields of objects of every class instantiated so far, keyed by class name.
+    instance_layouts: HashMap<String, Rc<Vec<InstanceField>>>,
+    /// Print every loaded class, as `-verbose:class` does.
+    verbose: bool,
+}
+
+impl ClassLoader {
+    /// Opens the directories and JAR files of `class_path`, followed by the `Class-Path` of each
+    /// JAR. Entries that do not exist are skipped.
+    pub fn new(class_path: Vec<PathBuf>) -> Result<ClassLoader, JVMError> {
+        let mut entries = vec![];
+        let mut opened: Vec<PathBuf> = vec![];
+        let mut pending: Vec<PathBuf> = class_path.into_iter().rev().collect();
+        while let Some(path) = pending.pop() {
+            if opened.contains(&path) {
+                continue;
+            }
+            opened.push(path.clone());
+            if path.is_dir() {
+                entries.push(ClassPathEntry::Directory(path));
+            } else if path.is_file() {
+                let jar = Jar::open(&path)?;
+                pending.extend(jar.get_class_path().into_iter().rev());
+                entries.push(ClassPathEntry::Jar(jar));
+            }
+        }
+        Ok(ClassLoader { class_path: entries, method_area: HashMap::new(), instance_layouts: HashMap::new(), verbose: false })
+    }
+
+    pub fn with_verbose(mut self, verbose: bool) -> ClassLoader {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Creates a class from class file bytes: parses, format checks and verifies it, and adds it
+    /// to the method area.
+    pub fn define_class(&mut self, bytes: Vec<u8>) -> Result<Rc<ClassFile>, JVMError> {
+        let class_file = class_file::parse_file(&mut file::File::new(bytes))?;
+        // HotSpot names the source of classes defined from bytes the same way.
+        self.link_class(class_file, "__JVM_DefineClass__")
+    }
+
+    fn link_class(&mut self, class_file: ClassFile, source: &str) -> Result<Rc<ClassFile>, JVMError> {
+        if let Some(error) = class_file.validate().into_iter().next() {
+            return Err(error.into());
+        }
+        verifier::verify_class(&class_file)?;
+        let class_name = class_file.get_name_of_class(class_file.get_this_class() as usize)?;
+        if self.verbose {
+            println!("[info][class,load] {} source: {}", class_name.replace('/', "."), source);
+        }
+        let class_file = Rc::new(class_file);
+        self.method_area.insert(class_name, class_file.clone());
+        Ok(class_file)
+    }
+
+    /// The class named `class_name`, loaded from the first class path entry containing it if it
+    /// has not been loaded yet.
+    pub fn load_class(&mut self, class_name: &str) -> Result<Rc<ClassFile>, JVMError> {
+        if let Some(class_file) = self.method_area.get(class_name) {
+            return Ok(class_file.clone());
+        }
+        let mut found = None;
+        for entry in &self.class_path {
+            if let Some(bytes) = entry.read_class(class_name)? {
+                found = Some((bytes, entry.get_source()));
+                break;
+            }
+        }
+        let (bytes, source) = found.ok_or_else(|| JVMError::NoClassDefFoundError(class_name.to_string()))?;
+        let class_file = class_file::parse_file(&mut file::File::new(bytes))?;
+        let defined_name = class_file.get_name_of_class(class_file.get_this_class() as usize)?;
+        if defined_name != class_name {
+            return Err(JVMError::NoClassDefFoundError(format!("{} (wrong name: {})", class_name, defined_name)));
+        }
+        self.link_class(class_file, &source)
+    }
+
+    /// Resolves the `Methodref` (JVMS §5.4.3.3) or `InterfaceMethodref` (JVMS §5.4.3.4) at
+    /// `method_index` in the constant pool of `class_file`. The method is looked up by name and
+    /// descriptor in the referenced class and its superclasses, then among the maximally-specific
+    /// methods of its superinterfaces. Returns the declaring class and the index of the method in it.
+    pub fn resolve_method(&mut self, class_file: &ClassFile, method_index: usize) -> Result<(Rc<ClassFile>, usize), JVMError> {
+        let (class_name, name, descriptor) = class_file.get_member_ref(method_index)?;
+        let interface_method = matches!(class_file.get_constant(method_index)?, ConstType::ConstantInterfaceMethodref(_, _));
+        let class = self.load_class(&class_name)?;
+        let binary_name = class_name.replace('/', ".");
+        match (interface_method, class.get_access_flags().is_interface()) {
+            (false, true) => return Err(JVMError::IncompatibleClassChangeError(format!("Found interface {}, but class was expected", binary_name))),
+            (true, false) => return Err(JVMError::IncompatibleClassChangeError(format!("Found class {}, but interface was expected", binary_name))),
+            _ => {}
+        }
+
+        // An interface has no superclass besides java.lang.Object, which is not loaded.
+        let classes = if interface_method { vec![class.clone()] } else { self.load_superclasses(class.clone())? };
+        for class in &classes {
+            if let Some(position) = find_method(class, &name, &descriptor) {
+                return Ok((class.clone(), position));
+            }
+        }
+
+        let maximally_specific = self.maximally_specific_methods(&classes, &name, &descriptor)?;
+        let non_abstract: Vec<_> = maximally_specific.iter()
+            .filter(|(interface, position)| !interface.get_methods()[*position].get_access_flags().is_abstract())
+            .collect();
+        if let [(interface, position)] = non_abstract.as_slice() {
+            return Ok((interface.clone(), *position));
+        }
+        // Otherwise any of the candidates is chosen.
+        match maximally_specific.into_iter().next() {
+            Some((interface, position)) => Ok((interface, position)),
+            None => Err(JVMError::NoSuchMethodError(format!("{}.{}{}", binary_name, name, descriptor))),
+        }
+    }
+
+    /// The non-private, non-static methods with `name` and `descriptor` of the superinterfaces of
+    /// `classes` that no other such method is declared in a subinterface of (JVMS §5.4.3.3).
+    fn maximally_specific_methods(&mut self, classes: &[Rc<ClassFile>], name: &str, descriptor: &str) -> Result<Vec<(Rc<ClassFile>, usize)>, JVMError> {
+        let mut candidates = vec![];
+        for interface in self.load_superinterfaces(classes)? {
+            if let Some(position) = find_method(&interface, name, descriptor) {
+                let flags = interface.get_methods()[position].get_access_flags();
+                if !flags.is_private() && !flags.is_static() {
+                    candidates.push((interface, position));
+                }
+            }
+        }
+        let mut maximally_specific = vec![];
+        for (interface, position) in &candidates {
+            let mut overridden = false;
+            for (other, _position) in &candidates {
+                if !Rc::ptr_eq(interface, other) && self.load_superinterfaces(std::slice::from_ref(other))?.iter()
+                    .any(|superinterface| Rc::ptr_eq(superinterface, interface)) {
+                    overridden = true;
+                }
+            }
+            if !overridden {
+                maximally_specific.push((interface.clone(), *position));
+            }
+        }
+        Ok(maximally_specific)
+    }
+
+    /// `class` followed by its superclasses. The walk ends at the class library, which is not
+    /// loaded from the class path. A class that is its own superclass is a `ClassCircularityError`.
+    fn load_superclasses(&mut self, class: Rc<ClassFile>) -> Result<Vec<Rc<ClassFile>>, JVMError> {
+        let mut visited = HashSet::from([class.get_name_of_class(class.get_this_class() as usize)?]);
+        let mut classes = vec![class];
+        loop {
+            let class = &classes[classes.len() - 1];
+            if class.get_super_class() == 0 {
+                return Ok(classes);
+            }
+            let super_class = class.get_name_of_class(class.get_super_class() as usize)?;
+            if is_library_class(&super_class) {
+                return Ok(classes);
+            }
+            if !visited.insert(super_class.clone()) {
+                return Err(JVMError::ClassCircularityError(super_class));
+            }
+            let super_class = self.load_class(&super_class)?;
+            classes.push(super_class);
+        }
+    }
+
+    /// The direct and indirect superinterfaces of `classes`, each once, outside the class library.
+    fn load_superinterfaces(&mut self, classes: &[Rc<ClassFile>]) -> Result<Vec<Rc<ClassFile>>, JVMError> {
+        let mut pending: Vec<Rc<ClassFile>> = classes.to_vec();
+        let mut interfaces: Vec<Rc<ClassFile>> = vec![];
+        while let Some(class) = pending.pop() {
+            for &interface_index in class.get_interfaces().iter().rev() {
+                let interface_name = class.get_name_of_class(interface_index as usize)?;
+                if is_library_class(&interface_name) {
+                    continue;
+                }
+                let interface = self.load_class(&interface_name)?;
+                if !interfaces.iter().any(|known| Rc::ptr_eq(known, &interface)) {
+                    interfaces.push(interface.clone());
+                    pending.push(interface);
+                }
+            }
+        }
+        Ok(interfaces)
+    }
+
+    /// Resolves the `Fieldref` at `field_index` in the constant pool of `class_file` (JVMS §5.4.3.2):
+    /// the field is looked up in the referenced class, its superinterfaces and then its superclass.
+    /// Returns the declaring class and the index of the field in it.
+    pub fn resolve_field(&mut self, class_file: &ClassFile, field_index: usize) -> Result<(Rc<ClassFile>, usize), JVMError> {
+        let (class_name, name, descriptor) = class_file.get_member_ref(field_index)?;
+        let mut class = self.load_class(&class_name)?;
+        loop {
+            if let Some(position) = find_field(&class, &name, &descriptor) {
+                return Ok((class, position));
+            }
+            for interface in self.load_superinterfaces(std::slice::from_ref(&class))? {
+                if let Some(position) = find_field(&interface, &name, &descriptor) {
+                    return Ok((interface, position));
+                }
+            }
+            match self.load_superclasses(class)?.into_iter().nth(1) {
+                Some(super_class) => class = super_class,
+                None => return Err(JVMError::NoSuchFieldError(format!("{}.{}", class_name.replace('/', "."), name))),
+            }
+        }
+    }
+
+    /// Selects the method an object of the class `class_name` runs for a resolved method with
+    /// `name` and `descriptor` (JVMS §5.4.6): the overriding method in the class or its nearest
+    /// superclass, or else the maximally-specific default method of its superinterfaces.
+    pub fn select_method(&mut self, class_name: &str, name: &str, descriptor: &str) -> Result<Option<(Rc<ClassFile>, usize)>, JVMError> {
+        let class = self.load_class(class_name)?;
+        let classes = self.load_superclasses(class)?;
+        for class in &classes {
+            if let Some(position) = find_method(class, name, descriptor) {
+                let flags = class.get_methods()[position].get_access_flags();
+                if !flags.is_static() && !flags.is_private() {
+                    return Ok(Some((class.clone(), position)));
+                }
+            }
+        }
+        let mut defaults = self.maximally_specific_methods(&classes, name, descriptor)?.into_iter()
+            .filter(|(interface, position)| !interface.get_methods()[*position].get_access_flags().is_abstract());
+        match (defaults.next(), defaults.next()) {
+            (Some(method), None) => Ok(Some(method)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Whether the class `class_name` is `super_class_name` or one of its subclasses.
+    pub fn is_subclass(&mut self, class_name: &str, super_class_name: &str) -> Result<bool, JVMError> {
+        let class = self.load_class(class_name)?;
+        for class in self.load_superclasses(class)? {
+            if class.get_name_of_class(class.get_this_class() as usize)? == super_class_name {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Resolves the class or array class `class_name` (JVMS §5.4.3.1): loads the class, or the
+    /// element class of an array class, unless it is a primitive type or part of the class library.
+    pub fn resolve_class(&mut self, class_name: &str) -> Result<(), JVMError> {
+        let element_class = class_name.trim_start_matches('[');
+        let element_class = match element_class.strip_prefix('L') {
+            Some(element_class) if class_name.starts_with('[') => element_class.trim_end_matches(';'),
+            _ if class_name.starts_with('[') => return Ok(()),
+            _ => element_class,
+        };
+        if !is_library_class(element_class) {
+            self.load_class(element_class)?;
+        }
+        Ok(())
+    }
+
+    /// Whether a reference to an object of the class or array class `class_name` may be stored in
+    /// a variable of the class, interface or array type `target` (JVMS §6.5 aastore).
+    pub fn is_assignable(&mut self, class_name: &str, target: &str) -> Result<bool, JVMError> {
+        if class_name == target || target == "java/lang/Object" {
+            return Ok(true);
+        }
+        if let Some(component) = class_name.strip_prefix('[') {
+            return match target.strip_prefix('[') {
+                Some(target_component) => match (reference_component(component), reference_component(target_component)) {
+                    (Some(component), Some(target_component)) => self.is_assignable(component, target_component),
+                    _ => Ok(false),
+                },
+                None => Ok(ARRAY_INTERFACES.contains(&target)),
+            };
+        }
+        if is_library_class(class_name) {
+            let mut library_class = class_name;
+            loop {
+                if library_class == target || LIBRARY_SUPERTYPES.iter().any(|&(subtype, supertype)| subtype == library_class && supertype == target) {
+                    return Ok(true);
+                }
+                match get_library_superclass(library_class) {
+                    Some(super_class) => library_class = super_class,
+                    None => return Ok(false),
+                }
+            }
+        }
+        let class = self.load_class(class_name)?;
+        let classes = self.load_superclasses(class)?;
+        let interfaces = self.load_superinterfaces(&classes)?;
+        // The walk stops at the class library, whose classes and interfaces are only known by name.
+        if let Some(library_class) = self.find_library_superclass(&classes)? {
+            if self.is_assignable(&library_class, target)? {
+                return Ok(true);
+            }
+        }
+        for class in classes.iter().chain(interfaces.iter()) {
+            if class.get_name_of_class(class.get_this_class() as usize)? == target {
+                return Ok(true);
+            }
+            for &interface_index in class.get_interfaces() {
+                if class.get_name_of_class(interface_index as usize)? == target {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// The superclass of the class `class_name` in the class library, which all classes have but
+    /// `java.lang.Object`.
+    pub fn load_library_superclass(&mut self, class_name: &str) -> Result<Option<String>, JVMError> {
+        let class = self.load_class(class_name)?;
+        let classes = self.load_superclasses(class)?;
+        self.find_library_superclass(&classes)
+    }
+
+    /// The class library superclass the walk of `load_superclasses` stopped at, given its result.
+    fn find_library_superclass(&self, classes: &[Rc<ClassFile>]) -> Result<Option<String>, JVMError> {
+        let last_class = &classes[classes.len() - 1];
+        match last_class.get_super_class() {
+            0 => Ok(None),
+            super_class => Ok(Some(last_class.get_name_of_class(super_class as usize)?)),
+        }
+    }
+
+    /// The instance fields of objects of `class`: those of its superclasses first, then its own.
+    pub fn get_instance_fields(&mut self, class: &Rc<ClassFile>) -> Result<Rc<Vec<InstanceField>>, JVMError> {
+        let class_name = class.get_name_of_class(class.get_this_class() as usize)?;
+        if let Some(layout) = self.instance_layouts.get(&class_name) {
+            return Ok(layout.clone());
+        }
+        let classes = self.load_superclasses(class.clone())?;
+        let mut layout = vec![];
+        // The detail message is the only state of a library throwable the VM keeps.
+        if let Some(library_class) = self.find_library_superclass(&classes)? {
+            if is_library_throwable(&library_class) {
+                layout.push(InstanceField::detail_message());
+            }
+        }
+        for class in classes.iter().rev() {
+            let class_name = class.get_name_of_class(class.get_this_class() as usize)?;
+            for field in class.get_fields() {
+                if !field.get_access_flags().is_static() {
+                    layout.push(InstanceField {
+                        class_name: class_name.clone(),
+                        name: class.get_utf8(field.get_name_index() as usize)?,
+                        descriptor: class.get_utf8(field.get_descriptor_index() as usize)?,
+                    });
+                }
+            }
+        }
+        let layout = Rc::new(layout);
+        self.instance_layouts.insert(class_name, layout.clone());
+        Ok(layout)
+    }
+}
+
+/// The index of the method `name` with `descriptor` declared in `class`.
+fn find_method(class: &ClassFile, name: &str, descriptor: &str) -> Option<usize> {
+    class.get_methods().iter().position(|method| {
+        class.get_utf8(method.get_name_index() as usize).is_ok_and(|method_name| method_name == name)
+            && class.get_utf8(method.get_descriptor_index() as usize).is_ok_and(|method_descriptor| method_descriptor == descriptor)
+    })
+}
+
+/// The interfaces every array implements (JLS §10.8).
+const ARRAY_INTERFACES: [&str; 2] = ["java/lang/Cloneable", "java/io/Serializable"];
+
+/// The superinterfaces of the library classes the VM creates objects of.
+const LIBRARY_SUPERTYPES: [(&str, &str); 6] = [
+    ("java/lang/Throwable", "java/io/Serializable"),
+    ("java/lang/String", "java/io/Serializable"),
+    ("java/lang/String", "java/lang/Comparable"),
+    ("java/lang/String", "java/lang/CharSequence"),
+    ("java/lang/String", "java/lang/constant/Constable"),
+    ("java/lang/String", "java/lang/constant/ConstantDesc"),
+];
+
+/// The class or array class of the components of an array whose component descriptor is
+/// `component`, e.g. `java/lang/String` for `Ljava/lang/String;`, or `None` for a primitive type.
+fn reference_component(component: &str) -> Option<&str> {
+    match component.strip_prefix('L') {
+        Some(class_name) => class_name.strip_suffix(';'),
+        None if component.starts_with('[') => Some(component),
+        None => None,
+    }
+}
+
+/// The library throwables, each with its superclass: the ones the VM throws and their superclasses.
+pub const LIBRARY_THROWABLES: [(&str, &str); 31] = [
+    ("java/lang/Throwable", "java/lang/Object"),
+    ("java/lang/Exception", "java/lang/Throwable"),
+    ("java/lang/RuntimeException", "java/lang/Exception"),
+    ("java/lang/ArithmeticException", "java/lang/RuntimeException"),
+    ("java/lang/IndexOutOfBoundsException", "java/lang/RuntimeException"),
+    ("java/lang/ArrayIndexOutOfBoundsException", "java/lang/IndexOutOfBoundsException"),
+    ("java/lang/NegativeArraySizeException", "java/lang/RuntimeException"),
+    ("java/lang/ArrayStoreException", "java/lang/RuntimeException"),
+    ("java/lang/NullPointerException", "java/lang/RuntimeException"),
+    ("java/lang/IllegalArgumentException", "java/lang/RuntimeException"),
+    ("java/lang/IllegalStateException", "java/lang/RuntimeException"),
+    ("java/lang/UnsupportedOperationException", "java/lang/RuntimeException"),
+    ("java/io/IOException", "java/lang/Exception"),
+    ("java/util/zip/ZipException", "java/io/IOException"),
+    ("java/lang/Error", "java/lang/Throwable"),
+    ("java/lang/AssertionError", "java/lang/Error"),
+    ("java/lang/LinkageError", "java/lang/Error"),
+    ("java/lang/NoClassDefFoundError", "java/lang/LinkageError"),
+    ("java/lang/ClassCircularityError", "java/lang/LinkageError"),
+    ("java/lang/ClassFormatError", "java/lang/LinkageError"),
+    ("java/lang/VerifyError", "java/lang/LinkageError"),
+    ("java/lang/UnsatisfiedLinkError", "java/lang/LinkageError"),
+    ("java/lang/ExceptionInInitializerError", "java/lang/LinkageError"),
+    ("java/lang/IncompatibleClassChangeError", "java/lang/LinkageError"),
+    ("java/lang/NoSuchFieldError", "java/lang/IncompatibleClassChangeError"),
+    ("java/lang/NoSuchMethodError", "java/lang/IncompatibleClassChangeError"),
+    ("java/lang/AbstractMethodError", "java/lang/IncompatibleClassChangeError"),
+    ("java/lang/InstantiationError", "java/lang/IncompatibleClassChangeError"),
+    ("java/lang/VirtualMachineError", "java/lang/Error"),
+    ("java/lang/StackOverflowError", "java/lang/VirtualMachineError"),
+    ("java/lang/OutOfMemoryError", "java/lang/VirtualMachineError"),
+];
+
+/// The superclass of a library class the VM knows, see `LIBRARY_THROWABLES`.
+pub fn get_library_superclass(class_name: &str) -> Option<&'static str> {
+    LIBRARY_THROWABLES.iter().find(|(library_class, _super_class)| *library_class == class_name).map(|(_library_class, super_class)| *super_class)
+}
+
+/// Whether `class_name` is `java.lang.Throwable` or one of the library throwables.
+pub fn is_library_throwable(class_name: &str) -> bool {
+    get_library_superclass(class_name).is_some()
+}
+
+/// Classes of the Java SE platform, which the VM implements natively instead of loading them.
+pub fn is_library_class(class_name: &str) -> bool {
+    class_name.starts_with("java/")
+}
+
+/// The index of the field `name` with `descriptor` declared in `class`.
+fn find_field(class: &ClassFile, name: &str, descriptor: &str) -> Option<usize> {
+    class.get_fields().iter().position(|field| {
+        class.get_utf8(field.get_name_index() as usize).is_ok_and(|field_name| field_name == name)
+            && class.get_utf8(field.get_descriptor_index() as usize).is_ok_and(|field_descriptor| field_descriptor == descriptor)
+    })
+}
@@ -0,0 +1,137 @@
+use super::{InstructionModule, OperandFrame};
+use super::integer_module::IntegerModule;
+use super::long_module::LongModule;
+use super::float_module::FloatModule;
+use super::double_module::DoubleModule;
+
+/// Conversions between the numeric types (JVMS §2.11.4). Rust's `as` has the semantics the JVMS
+/// prescribes: widening to a floating point type and narrowing between floating point types round
+/// to nearest, narrowing between integer types keeps the low bits, and a floating point value
+/// converted to an integer type is rounded towards zero and saturates, with NaN becoming zero.
+pub struct ConversionModule;
+
+impl InstructionModule for ConversionModule {
+    fn get_instructions(&self) -> Vec<(u8 , super::Instruction)> {
+        vec![
+        (133, super::Instruction {
+            name: String::from("i2l"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Long(value as i64));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (134, super::Instruction {
+            name: String::from("i2f"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Float(value as f32));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (135, super::Instruction {
+            name: String::from("i2d"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Double(value as f64));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (136, super::Instruction {
+            name: String::from("l2i"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = LongModule::try_to_pop_long(frame)?;
+                frame.push_operand(OperandFrame::Int(value as i32));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (137, super::Instruction {
+            name: String::from("l2f"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = LongModule::try_to_pop_long(frame)?;
+                frame.push_operand(OperandFrame::Float(value as f32));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (138, super::Instruction {
+            name: String::from("l2d"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = LongModule::try_to_pop_long(frame)?;
+                frame.push_operand(OperandFrame::Double(value as f64));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (139, super::Instruction {
+            name: String::from("f2i"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = FloatModule::try_to_pop_float(frame)?;
+                frame.push_operand(OperandFrame::Int(value as i32));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (140, super::Instruction {
+            name: String::from("f2l"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = FloatModule::try_to_pop_float(frame)?;
+                frame.push_operand(OperandFrame::Long(value as i64));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (141, super::Instruction {
+            name: String::from("f2d"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = FloatModule::try_to_pop_float(frame)?;
+                frame.push_operand(OperandFrame::Double(value as f64));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (142, super::Instruction {
+            name: String::from("d2i"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = DoubleModule::try_to_pop_double(frame)?;
+                frame.push_operand(OperandFrame::Int(value as i32));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (143, super::Instruction {
+            name: String::from("d2l"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = DoubleModule::try_to_pop_double(frame)?;
+                frame.push_operand(OperandFrame::Long(value as i64));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (144, super::Instruction {
+            name: String::from("d2f"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = DoubleModule::try_to_pop_double(frame)?;
+                frame.push_operand(OperandFrame::Float(value as f32));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (145, super::Instruction {
+            name: String::from("i2b"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Int(value as i8 as i32));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (146, super::Instruction {
+            name: String::from("i2c"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Int(value as u16 as i32));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (147, super::Instruction {
+            name: String::from("i2s"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let value = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Int(value as i16 as i32));
+                Ok(super::JVMEvent::None)
+            }
+        })]
+    }
+}
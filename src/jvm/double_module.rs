@@ -0,0 +1,204 @@
+use super::{InstructionModule, Frame, JVMError, OperandFrame, LocalFrame};
+
+/// Instructions on `double` values, which follow IEEE 754 double precision as Java's do since
+/// strict floating point became the only mode (JEP 306). A double takes two local variables, of
+/// which the second is unused, and one entry of category 2 on the operand stack.
+pub struct DoubleModule;
+
+impl DoubleModule {
+    pub fn store_to_local(frame: &mut Frame, index: usize) -> Result<(), JVMError> {
+        let double = DoubleModule::try_to_pop_double(frame)?;
+        frame.store_locale_variable(index, LocalFrame::Double(double));
+        frame.store_locale_variable(index + 1, LocalFrame::None);
+        Ok(())
+    }
+    pub fn load_to_stack(frame: &mut Frame, index: usize) -> Result<(), JVMError> {
+        match frame.load_locale_variable(index) {
+            LocalFrame::Double(double) => frame.push_operand(OperandFrame::Double(double)),
+            local_value => return Err(JVMError::Internal(format!("The local variable {:?} is no double", local_value))),
+        }
+        Ok(())
+    }
+    pub fn try_to_pop_double(frame: &mut Frame) -> Result<f64, JVMError> {
+        match frame.pop_operand()? {
+            OperandFrame::Double(double) => Ok(double),
+            operand => Err(JVMError::Internal(format!("The operand {:?} is no double", operand))),
+        }
+    }
+}
+
+impl InstructionModule for DoubleModule {
+    fn get_instructions(&self) -> Vec<(u8 , super::Instruction)> {
+        vec![
+        (14, super::Instruction {
+            name: String::from("dconst_d(0)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                frame.push_operand(OperandFrame::Double(0.0));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (15, super::Instruction {
+            name: String::from("dconst_d(1)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                frame.push_operand(OperandFrame::Double(1.0));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (57, super::Instruction {
+            name: String::from("dstore"),
+            handler: |frame, insn, _class_file, _heap| {
+                if let Some(index) = insn.get_local_index() {
+                    DoubleModule::store_to_local(frame, index as usize)?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (71, super::Instruction {
+            name: String::from("dstore_n(n=0)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                DoubleModule::store_to_local(frame, 0)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (72, super::Instruction {
+            name: String::from("dstore_n(n=1)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                DoubleModule::store_to_local(frame, 1)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (73, super::Instruction {
+            name: String::from("dstore_n(n=2)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                DoubleModule::store_to_local(frame, 2)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (74, super::Instruction {
+            name: String::from("dstore_n(n=3)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                DoubleModule::store_to_local(frame, 3)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (24, super::Instruction {
+            name: String::from("dload"),
+            handler: |frame, insn, _class_file, _heap| {
+                if let Some(index) = insn.get_local_index() {
+                    DoubleModule::load_to_stack(frame, index as usize)?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (38, super::Instruction {
+            name: String::from("dload_n(n=0)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                DoubleModule::load_to_stack(frame, 0)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (39, super::Instruction {
+            name: String::from("dload_n(n=1)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                DoubleModule::load_to_stack(frame, 1)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (40, super::Instruction {
+            name: String::from("dload_n(n=2)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                DoubleModule::load_to_stack(frame, 2)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (41, super::Instruction {
+            name: String::from("dload_n(n=3)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                DoubleModule::load_to_stack(frame, 3)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (99, super::Instruction {
+            name: String::from("dadd"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = DoubleModule::try_to_pop_double(frame)?;
+                let param2 = DoubleModule::try_to_pop_double(frame)?;
+                frame.push_operand(OperandFrame::Double(param2 + param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (103, super::Instruction {
+            name: String::from("dsub"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = DoubleModule::try_to_pop_double(frame)?;
+                let param2 = DoubleModule::try_to_pop_double(frame)?;
+                frame.push_operand(OperandFrame::Double(param2 - param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (107, super::Instruction {
+            name: String::from("dmul"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = DoubleModule::try_to_pop_double(frame)?;
+                let param2 = DoubleModule::try_to_pop_double(frame)?;
+                frame.push_operand(OperandFrame::Double(param2 * param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (111, super::Instruction {
+            name: String::from("ddiv"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = DoubleModule::try_to_pop_double(frame)?;
+                let param2 = DoubleModule::try_to_pop_double(frame)?;
+                frame.push_operand(OperandFrame::Double(param2 / param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (115, super::Instruction {
+            name: String::from("drem"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = DoubleModule::try_to_pop_double(frame)?;
+                let param2 = DoubleModule::try_to_pop_double(frame)?;
+                // Truncating, as frem.
+                frame.push_operand(OperandFrame::Double(param2 % param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (119, super::Instruction {
+            name: String::from("dneg"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = DoubleModule::try_to_pop_double(frame)?;
+                frame.push_operand(OperandFrame::Double(-param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (151, super::Instruction {
+            name: String::from("dcmpl"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = DoubleModule::try_to_pop_double(frame)?;
+                let param2 = DoubleModule::try_to_pop_double(frame)?;
+                // A comparison with NaN gives -1.
+                let result = param2.partial_cmp(&param1).map_or(-1, |ordering| ordering as i32);
+                frame.push_operand(OperandFrame::Int(result));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (152, super::Instruction {
+            name: String::from("dcmpg"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = DoubleModule::try_to_pop_double(frame)?;
+                let param2 = DoubleModule::try_to_pop_double(frame)?;
+                // A comparison with NaN gives 1.
+                let result = param2.partial_cmp(&param1).map_or(1, |ordering| ordering as i32);
+                frame.push_operand(OperandFrame::Int(result));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (175, super::Instruction {
+            name: String::from("dreturn"),
+            handler: |frame, _insn, _class_file, _heap| {
+                Ok(super::JVMEvent::Return(Some(OperandFrame::Double(DoubleModule::try_to_pop_double(frame)?))))
+            }
+        })]
+    }
+}
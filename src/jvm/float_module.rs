@@ -0,0 +1,209 @@
+use super::{InstructionModule, Frame, JVMError, OperandFrame, LocalFrame};
+
+/// Instructions on `float` values, which follow IEEE 754 single precision as Java's do since
+/// strict floating point became the only mode (JEP 306).
+pub struct FloatModule;
+
+impl FloatModule {
+    pub fn store_to_local(frame: &mut Frame, index: usize) -> Result<(), JVMError> {
+        let float = FloatModule::try_to_pop_float(frame)?;
+        frame.store_locale_variable(index, LocalFrame::Float(float));
+        Ok(())
+    }
+    pub fn load_to_stack(frame: &mut Frame, index: usize) -> Result<(), JVMError> {
+        match frame.load_locale_variable(index) {
+            LocalFrame::Float(float) => frame.push_operand(OperandFrame::Float(float)),
+            local_value => return Err(JVMError::Internal(format!("The local variable {:?} is no float", local_value))),
+        }
+        Ok(())
+    }
+    pub fn try_to_pop_float(frame: &mut Frame) -> Result<f32, JVMError> {
+        match frame.pop_operand()? {
+            OperandFrame::Float(float) => Ok(float),
+            operand => Err(JVMError::Internal(format!("The operand {:?} is no float", operand))),
+        }
+    }
+}
+
+impl InstructionModule for FloatModule {
+    fn get_instructions(&self) -> Vec<(u8 , super::Instruction)> {
+        vec![
+        (11, super::Instruction {
+            name: String::from("fconst_f(0)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                frame.push_operand(OperandFrame::Float(0.0));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (12, super::Instruction {
+            name: String::from("fconst_f(1)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                frame.push_operand(OperandFrame::Float(1.0));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (13, super::Instruction {
+            name: String::from("fconst_f(2)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                frame.push_operand(OperandFrame::Float(2.0));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (56, super::Instruction {
+            name: String::from("fstore"),
+            handler: |frame, insn, _class_file, _heap| {
+                if let Some(index) = insn.get_local_index() {
+                    FloatModule::store_to_local(frame, index as usize)?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (67, super::Instruction {
+            name: String::from("fstore_n(n=0)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                FloatModule::store_to_local(frame, 0)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (68, super::Instruction {
+            name: String::from("fstore_n(n=1)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                FloatModule::store_to_local(frame, 1)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (69, super::Instruction {
+            name: String::from("fstore_n(n=2)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                FloatModule::store_to_local(frame, 2)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (70, super::Instruction {
+            name: String::from("fstore_n(n=3)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                FloatModule::store_to_local(frame, 3)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (23, super::Instruction {
+            name: String::from("fload"),
+            handler: |frame, insn, _class_file, _heap| {
+                if let Some(index) = insn.get_local_index() {
+                    FloatModule::load_to_stack(frame, index as usize)?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (34, super::Instruction {
+            name: String::from("fload_n(n=0)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                FloatModule::load_to_stack(frame, 0)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (35, super::Instruction {
+            name: String::from("fload_n(n=1)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                FloatModule::load_to_stack(frame, 1)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (36, super::Instruction {
+            name: String::from("fload_n(n=2)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                FloatModule::load_to_stack(frame, 2)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (37, super::Instruction {
+            name: String::from("fload_n(n=3)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                FloatModule::load_to_stack(frame, 3)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (98, super::Instruction {
+            name: String::from("fadd"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = FloatModule::try_to_pop_float(frame)?;
+                let param2 = FloatModule::try_to_pop_float(frame)?;
+                frame.push_operand(OperandFrame::Float(param2 + param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (102, super::Instruction {
+            name: String::from("fsub"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = FloatModule::try_to_pop_float(frame)?;
+                let param2 = FloatModule::try_to_pop_float(frame)?;
+                frame.push_operand(OperandFrame::Float(param2 - param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (106, super::Instruction {
+            name: String::from("fmul"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = FloatModule::try_to_pop_float(frame)?;
+                let param2 = FloatModule::try_to_pop_float(frame)?;
+                frame.push_operand(OperandFrame::Float(param2 * param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (110, super::Instruction {
+            name: String::from("fdiv"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = FloatModule::try_to_pop_float(frame)?;
+                let param2 = FloatModule::try_to_pop_float(frame)?;
+                frame.push_operand(OperandFrame::Float(param2 / param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (114, super::Instruction {
+            name: String::from("frem"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = FloatModule::try_to_pop_float(frame)?;
+                let param2 = FloatModule::try_to_pop_float(frame)?;
+                // Rust's % is C's fmod: the quotient is truncated, not rounded as in IEEE 754.
+                frame.push_operand(OperandFrame::Float(param2 % param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (118, super::Instruction {
+            name: String::from("fneg"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = FloatModule::try_to_pop_float(frame)?;
+                frame.push_operand(OperandFrame::Float(-param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (149, super::Instruction {
+            name: String::from("fcmpl"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = FloatModule::try_to_pop_float(frame)?;
+                let param2 = FloatModule::try_to_pop_float(frame)?;
+                // A comparison with NaN gives -1.
+                let result = param2.partial_cmp(&param1).map_or(-1, |ordering| ordering as i32);
+                frame.push_operand(OperandFrame::Int(result));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (150, super::Instruction {
+            name: String::from("fcmpg"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = FloatModule::try_to_pop_float(frame)?;
+                let param2 = FloatModule::try_to_pop_float(frame)?;
+                // A comparison with NaN gives 1.
+                let result = param2.partial_cmp(&param1).map_or(1, |ordering| ordering as i32);
+                frame.push_operand(OperandFrame::Int(result));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (174, super::Instruction {
+            name: String::from("freturn"),
+            handler: |frame, _insn, _class_file, _heap| {
+                Ok(super::JVMEvent::Return(Some(OperandFrame::Float(FloatModule::try_to_pop_float(frame)?))))
+            }
+        })]
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use crate::class_file::descriptor::{self, BaseType, FieldType};
+use super::{JVMError, OperandFrame};
+
+/// Index of an object on the heap.
+pub type Reference = usize;
+
+/// Bytes of the header every object starts with, as in HotSpot with compressed class pointers.
+const OBJECT_HEADER_SIZE: usize = 16;
+/// Bytes of a reference inside an object, as with compressed oops.
+const REFERENCE_SIZE: usize = 4;
+
+#[derive(Debug)]
+pub enum HeapObject {
+    /// An instance of a class loaded from the class path, with the values of its instance fields
+    /// in the order of the class loader's instance layout.
+    Object { class_name: String, fields: Vec<OperandFrame> },
+    /// An instance of `java.lang.String`.
+    String(String),
+    /// An array whose component type is a class or interface, e.g. `java/lang/String`, or an
+    /// array type, e.g. `[I`.
+    ReferenceArray { component_type: String, elements: Vec<Option<Reference>> },
+    PrimitiveArray(PrimitiveArray),
+}
+
+/// The elements of an array of a primitive type. Booleans are stored as bytes, as in HotSpot.
+#[derive(Debug)]
+pub enum PrimitiveArray {
+    Boolean(Vec<i8>),
+    Byte(Vec<i8>),
+    Char(Vec<u16>),
+    Short(Vec<i16>),
+    Int(Vec<i32>),
+    Long(Vec<i64>),
+    Float(Vec<f32>),
+    Double(Vec<f64>),
+}
+
+impl PrimitiveArray {
+    /// An array of `length` elements of `component_type`, each zero.
+    pub fn new(component_type: &BaseType, length: usize) -> PrimitiveArray {
+        match component_type {
+            BaseType::Boolean => PrimitiveArray::Boolean(vec![0; length]),
+            BaseType::Byte => PrimitiveArray::Byte(vec![0; length]),
+            BaseType::Char => PrimitiveArray::Char(vec![0; length]),
+            BaseType::Short => PrimitiveArray::Short(vec![0; length]),
+            BaseType::Int => PrimitiveArray::Int(vec![0; length]),
+            BaseType::Long => PrimitiveArray::Long(vec![0; length]),
+            BaseType::Float => PrimitiveArray::Float(vec![0.0; length]),
+            BaseType::Double => PrimitiveArray::Double(vec![0.0; length]),
+        }
+    }
+
+    pub fn get_component_type(&self) -> BaseType {
+        match self {
+            PrimitiveArray::Boolean(_) => BaseType::Boolean,
+            PrimitiveArray::Byte(_) => BaseType::Byte,
+            PrimitiveArray::Char(_) => BaseType::Char,
+            PrimitiveArray::Short(_) => BaseType::Short,
+            PrimitiveArray::Int(_) => BaseType::Int,
+            PrimitiveArray::Long(_) => BaseType::Long,
+            PrimitiveArray::Float(_) => BaseType::Float,
+            PrimitiveArray::Double(_) => BaseType::Double,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            PrimitiveArray::Boolean(elements) | PrimitiveArray::Byte(elements) => elements.len(),
+            PrimitiveArray::Char(elements) => elements.len(),
+            PrimitiveArray::Short(elements) => elements.len(),
+            PrimitiveArray::Int(elements) => elements.len(),
+            PrimitiveArray::Long(elements) => elements.len(),
+            PrimitiveArray::Float(elements) => elements.len(),
+            PrimitiveArray::Double(elements) => elements.len(),
+        }
+    }
+
+    /// The element at `index` as operand: booleans, bytes, chars and shorts are extended to int.
+    pub fn get(&self, index: usize) -> OperandFrame {
+        match self {
+            PrimitiveArray::Boolean(elements) | PrimitiveArray::Byte(elements) => OperandFrame::Int(elements[index] as i32),
+            PrimitiveArray::Char(elements) => OperandFrame::Int(elements[index] as i32),
+            PrimitiveArray::Short(elements) => OperandFrame::Int(elements[index] as i32),
+            PrimitiveArray::Int(elements) => OperandFrame::Int(elements[index]),
+            PrimitiveArray::Long(elements) => OperandFrame::Long(elements[index]),
+            PrimitiveArray::Float(elements) => OperandFrame::Float(elements[index]),
+            PrimitiveArray::Double(elements) => OperandFrame::Double(elements[index]),
+        }
+    }
+
+    /// Stores `value` at `index`, narrowing an int to the component type. A boolean keeps only the
+    /// lowest bit (JVMS §6.5 bastore).
+    pub fn set(&mut self, index: usize, value: OperandFrame) -> Result<(), JVMError> {
+        match (self, value) {
+            (PrimitiveArray::Boolean(elements), OperandFrame::Int(integer)) => elements[index] = (integer & 1) as i8,
+            (PrimitiveArray::Byte(elements), OperandFrame::Int(integer)) => elements[index] = integer as i8,
+            (PrimitiveArray::Char(elements), OperandFrame::Int(integer)) => elements[index] = integer as u16,
+            (PrimitiveArray::Short(elements), OperandFrame::Int(integer)) => elements[index] = integer as i16,
+            (PrimitiveArray::Int(elements), OperandFrame::Int(integer)) => elements[index] = integer,
+            (PrimitiveArray::Long(elements), OperandFrame::Long(long)) => elements[index] = long,
+            (PrimitiveArray::Float(elements), OperandFrame::Float(float)) => elements[index] = float,
+            (PrimitiveArray::Double(elements), OperandFrame::Double(double)) => elements[index] = double,
+            (array, value) => return Err(JVMError::Internal(format!("Storing {:?} in an array of {} is not implemented", value, array.get_component_type()))),
+        }
+        Ok(())
+    }
+}
+
+/// Bytes an element of `component_type` takes in an array.
+fn get_element_size(component_type: &FieldType) -> usize {
+    match component_type {
+        FieldType::Base(BaseType::Boolean | BaseType::Byte) => 1,
+        FieldType::Base(BaseType::Char | BaseType::Short) => 2,
+        FieldType::Base(BaseType::Int | BaseType::Float) => 4,
+        FieldType::Base(BaseType::Long | BaseType::Double) => 8,
+        FieldType::Object(_) | FieldType::Array(_) => REFERENCE_SIZE,
+    }
+}
+
+impl HeapObject {
+    /// The approximate number of bytes the object takes, counted against `-Xmx`.
+    fn get_size(&self) -> usize {
+        match self {
+            HeapObject::Object { fields, .. } => OBJECT_HEADER_SIZE + REFERENCE_SIZE * fields.len(),
+            // The char array is stored with the object, one byte per Latin-1 character.
+            HeapObject::String(string) => OBJECT_HEADER_SIZE + REFERENCE_SIZE + string.len(),
+            HeapObject::ReferenceArray { elements, .. } => OBJECT_HEADER_SIZE + REFERENCE_SIZE * elements.len(),
+            HeapObject::PrimitiveArray(array) => OBJECT_HEADER_SIZE + get_element_size(&FieldType::Base(array.get_component_type())) * array.len(),
+        }
+    }
+
+    /// The number of elements, if the object is an array.
+    pub fn get_array_length(&self) -> Option<usize> {
+        match self {
+            HeapObject::ReferenceArray { elements, .. } => Some(elements.len()),
+            HeapObject::PrimitiveArray(array) => Some(array.len()),
+            HeapObject::Object { .. } | HeapObject::String(_) => None,
+        }
+    }
+}
+
+/// The objects created by the program. Nothing is collected, so the heap only grows.
+pub struct Heap {
+    objects: Vec<HeapObject>,
+    /// String literals, which are shared by all classes (JLS §3.10.5).
+    interned_strings: HashMap<String, Reference>,
+    size: usize,
+    max_size: usize,
+}
+
+impl Heap {
+    pub fn new(max_size: usize) -> Heap {
+        Heap { objects: vec![], interned_strings: HashMap::new(), size: 0, max_size }
+    }
+
+    /// Adds `object` to the heap, throwing an `OutOfMemoryError` if it would grow beyond its
+    /// maximum size.
+    pub fn allocate(&mut self, object: HeapObject) -> Result<Reference, JVMError> {
+        let size = object.get_size();
+        if self.size + size > self.max_size {
+            return Err(JVMError::OutOfMemoryError(String::from("Java heap space")));
+        }
+        self.size += size;
+        self.objects.push(object);
+        Ok(self.objects.len() - 1)
+    }
+
+    pub fn get(&self, reference: Reference) -> &HeapObject {
+        &self.objects[reference]
+    }
+
+    pub fn get_mut(&mut self, reference: Reference) -> &mut HeapObject {
+        &mut self.objects[reference]
+    }
+
+    /// The characters of the `String` at `reference`.
+    pub fn get_string(&self, reference: Reference) -> Option<&str> {
+        match self.get(reference) {
+            HeapObject::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// The `String` with the characters of `string`, created on first use.
+    pub fn intern(&mut self, string: &str) -> Result<Reference, JVMError> {
+        if let Some(reference) = self.interned_strings.get(string) {
+            return Ok(*reference);
+        }
+        let reference = self.allocate(HeapObject::String(string.to_string()))?;
+        self.interned_strings.insert(string.to_string(), reference);
+        Ok(reference)
+    }
+
+    /// The internal name of the class of the object at `reference`, e.g. `[Ljava/lang/String;`.
+    pub fn get_class_name(&self, reference: Reference) -> String {
+        match self.get(reference) {
+            HeapObject::Object { class_name, .. } => class_name.clone(),
+            HeapObject::String(_) => String::from("java/lang/String"),
+            HeapObject::ReferenceArray { component_type, .. } if component_type.starts_with('[') => format!("[{}", component_type),
+            HeapObject::ReferenceArray { component_type, .. } => format!("[L{};", component_type),
+            HeapObject::PrimitiveArray(array) => format!("[{}", array.get_component_type().get_descriptor()),
+        }
+    }
+
+    /// Creates an array of the array class `array_class`, e.g. `[I` or `[Ljava/lang/String;`, with
+    /// `length` elements of the default value. The size is checked before the elements are created,
+    /// so that huge arrays throw an `OutOfMemoryError` instead of exhausting the memory of the VM.
+    pub fn allocate_array(&mut self, array_class: &str, length: usize) -> Result<Reference, JVMError> {
+        let component_type = match descriptor::parse_field_descriptor(array_class)? {
+            FieldType::Array(component_type) => *component_type,
+            field_type => return Err(JVMError::Internal(format!("{} is no array class", field_type))),
+        };
+        let size = get_element_size(&component_type).checked_mul(length).and_then(|size| size.checked_add(OBJECT_HEADER_SIZE));
+        if size.is_none_or(|size| self.size + size > self.max_size) {
+            return Err(JVMError::OutOfMemoryError(String::from("Java heap space")));
+        }
+        let array = match component_type {
+            FieldType::Base(base_type) => HeapObject::PrimitiveArray(PrimitiveArray::new(&base_type, length)),
+            FieldType::Object(class_name) => HeapObject::ReferenceArray { component_type: class_name, elements: vec![None; length] },
+            FieldType::Array(_) => HeapObject::ReferenceArray { component_type: component_type.get_descriptor(), elements: vec![None; length] },
+        };
+        self.allocate(array)
+    }
+
+    /// Creates a multidimensional array of `array_class` whose first dimensions have the `lengths`
+    /// (JVMS §6.5 multianewarray). Deeper dimensions are left null.
+    pub fn allocate_multi_array(&mut self, array_class: &str, lengths: &[usize]) -> Result<Reference, JVMError> {
+        let array = self.allocate_array(array_class, lengths[0])?;
+        if lengths.len() > 1 {
+            for index in 0..lengths[0] {
+                let element = self.allocate_multi_array(&array_class[1..], &lengths[1..])?;
+                if let HeapObject::ReferenceArray { elements, .. } = self.get_mut(array) {
+                    elements[index] = Some(element);
+                }
+            }
+        }
+        Ok(array)
+    }
+
+    /// Creates a `String[]` holding `strings`.
+    pub fn allocate_string_array(&mut self, strings: &[String]) -> Result<Reference, JVMError> {
+        let mut elements = Vec::with_capacity(strings.len());
+        for string in strings {
+            elements.push(Some(self.allocate(HeapObject::String(string.clone()))?));
+        }
+        self.allocate(HeapObject::ReferenceArray { component_type: String::from("java/lang/String"), elements })
+    }
+}
@@ -1,42 +1,29 @@
-use super::{InstructionModule, Frame, OperandFrame, LocalFrame};
+use super::{InstructionModule, Frame, JVMError, OperandFrame, LocalFrame};
+use crate::class_file::instruction::Insn;
 
 pub struct IntegerModule;
 
 impl IntegerModule {
-    pub fn store_to_local(frame: &mut Frame, index: usize) {
-        let operand = frame.pop_operand();
-        match operand {
-            OperandFrame::Int(integer) => {
-                frame.store_locale_variable(index, LocalFrame::Int(integer));
-            }
-            _ => {panic!("The top Operand({:?}) was no Int!", operand);}
-        }
+    pub fn store_to_local(frame: &mut Frame, index: usize) -> Result<(), JVMError> {
+        let integer = IntegerModule::try_to_pop_integer(frame)?;
+        frame.store_locale_variable(index, LocalFrame::Int(integer));
+        Ok(())
     }
-    pub fn load_to_stack(frame: &mut Frame, index: usize) {
-        let local_value = frame.load_locale_variable(index);
-        match local_value {
-            LocalFrame::Int(integer) => {
-                frame.push_operand(OperandFrame::Int(integer));
-            }
-            _ => {panic!("The local variable ({:?}) was no Int!", local_value);}
-        }
+    pub fn load_to_stack(frame: &mut Frame, index: usize) -> Result<(), JVMError> {
+        let integer = IntegerModule::load_local_variable(frame, index)?;
+        frame.push_operand(OperandFrame::Int(integer));
+        Ok(())
     }
-    pub fn load_local_variable(frame: &mut Frame, index: usize) -> i32{
-        let local_value = frame.load_locale_variable(index);
-        match local_value {
-            LocalFrame::Int(integer) => {
-                integer
-            }
-            _ => {panic!("The local variable ({:?}) was no Int!", local_value);}
+    pub fn load_local_variable(frame: &mut Frame, index: usize) -> Result<i32, JVMError> {
+        match frame.load_locale_variable(index) {
+            LocalFrame::Int(integer) => Ok(integer),
+            local_value => Err(JVMError::Internal(format!("The local variable {:?} is no int", local_value))),
         }
     }
-    pub fn try_to_pop_integer(frame: &mut Frame) -> i32 {
-        let param = frame.pop_operand();
-        match param {
-            OperandFrame::Int(integer) => {
-                integer
-            }
-        _ => {panic!("The top Operand({:?}) was no Int!", param);}
+    pub fn try_to_pop_integer(frame: &mut Frame) -> Result<i32, JVMError> {
+        match frame.pop_operand()? {
+            OperandFrame::Int(integer) => Ok(integer),
+            operand => Err(JVMError::Internal(format!("The operand {:?} is no int", operand))),
         }
     }
 }
@@ -45,252 +32,414 @@ impl InstructionModule for IntegerModule {
     fn get_instructions(&self) -> Vec<(u8 , super::Instruction)> {
         vec![(16, super::Instruction {
             name: String::from("bipush"),
-            handler: |frame, _class_file| {
-                let integer = frame.get_u1() as i32;
-                frame.push_operand(OperandFrame::Int(integer));
-                super::JVMEvent::None
+            handler: |frame, insn, _class_file, _heap| {
+                if let Insn::Bipush(value) = insn {
+                    frame.push_operand(OperandFrame::Int(*value as i32));
+                }
+                Ok(super::JVMEvent::None)
             }
         }),
         (54, super::Instruction {
             name: String::from("istore"),
-            handler: |frame, _class_file| {
-                let index = frame.get_u2() as usize;
-                IntegerModule::store_to_local(frame, index);
-                super::JVMEvent::None
+            handler: |frame, insn, _class_file, _heap| {
+                if let Some(index) = insn.get_local_index() {
+                    IntegerModule::store_to_local(frame, index as usize)?;
+                }
+                Ok(super::JVMEvent::None)
             }
         }),
         (59, super::Instruction {
             name: String::from("istore_n(n=0)"),
-            handler: |frame, _class_file| {
-                IntegerModule::store_to_local(frame, 0);
-                super::JVMEvent::None
+            handler: |frame, _insn, _class_file, _heap| {
+                IntegerModule::store_to_local(frame, 0)?;
+                Ok(super::JVMEvent::None)
             }
         }),
         (60, super::Instruction {
             name: String::from("istore_n(n=1)"),
-            handler: |frame, _class_file| {
-                IntegerModule::store_to_local(frame, 1);
-                super::JVMEvent::None
+            handler: |frame, _insn, _class_file, _heap| {
+                IntegerModule::store_to_local(frame, 1)?;
+                Ok(super::JVMEvent::None)
             }
         }),
         (61, super::Instruction {
             name: String::from("istore_n(n=2)"),
-            handler: |frame, _class_file| {
-                IntegerModule::store_to_local(frame, 2);
-                super::JVMEvent::None
+            handler: |frame, _insn, _class_file, _heap| {
+                IntegerModule::store_to_local(frame, 2)?;
+                Ok(super::JVMEvent::None)
             }
         }),
         (62, super::Instruction {
             name: String::from("istore_n(n=3)"),
-            handler: |frame, _class_file| {
-                IntegerModule::store_to_local(frame, 3);
-                super::JVMEvent::None
+            handler: |frame, _insn, _class_file, _heap| {
+                IntegerModule::store_to_local(frame, 3)?;
+                Ok(super::JVMEvent::None)
             }
         }),
         (21, super::Instruction {
             name: String::from("iload"),
-            handler: |frame, _class_file| {
-                let index = frame.get_u2() as usize;
-                IntegerModule::load_to_stack(frame, index);
-                super::JVMEvent::None
+            handler: |frame, insn, _class_file, _heap| {
+                if let Some(index) = insn.get_local_index() {
+                    IntegerModule::load_to_stack(frame, index as usize)?;
+                }
+                Ok(super::JVMEvent::None)
             }
         }),
         (26, super::Instruction {
             name: String::from("iload_n(n=0)"),
-            handler: |frame, _class_file| {
-                IntegerModule::load_to_stack(frame, 0);
-                super::JVMEvent::None
+            handler: |frame, _insn, _class_file, _heap| {
+                IntegerModule::load_to_stack(frame, 0)?;
+                Ok(super::JVMEvent::None)
             }
         }),
         (27, super::Instruction {
             name: String::from("iload_n(n=1)"),
-            handler: |frame, _class_file| {
-                IntegerModule::load_to_stack(frame, 1);
-                super::JVMEvent::None
+            handler: |frame, _insn, _class_file, _heap| {
+                IntegerModule::load_to_stack(frame, 1)?;
+                Ok(super::JVMEvent::None)
             }
         }),
         (28, super::Instruction {
             name: String::from("iload_n(n=2)"),
-            handler: |frame, _class_file| {
-                IntegerModule::load_to_stack(frame, 2);
-                super::JVMEvent::None
+            handler: |frame, _insn, _class_file, _heap| {
+                IntegerModule::load_to_stack(frame, 2)?;
+                Ok(super::JVMEvent::None)
             }
         }),
         (29, super::Instruction {
             name: String::from("iload_n(n=3)"),
-            handler: |frame, _class_file| {
-                IntegerModule::load_to_stack(frame, 3);
-                super::JVMEvent::None
+            handler: |frame, _insn, _class_file, _heap| {
+                IntegerModule::load_to_stack(frame, 3)?;
+                Ok(super::JVMEvent::None)
             }
         }),
         (2, super::Instruction {
             name: String::from("iconst_i(-1)"),
-            handler: |frame, _class_file| {
+            handler: |frame, _insn, _class_file, _heap| {
                 frame.push_operand(OperandFrame::Int(-1));
-                super::JVMEvent::None
+                Ok(super::JVMEvent::None)
             }
         }),
         (3, super::Instruction {
             name: String::from("iconst_i(0)"),
-            handler: |frame, _class_file| {
+            handler: |frame, _insn, _class_file, _heap| {
                 frame.push_operand(OperandFrame::Int(0));
-                super::JVMEvent::None
+                Ok(super::JVMEvent::None)
             }
         }),
         (4, super::Instruction {
             name: String::from("iconst_i(1)"),
-            handler: |frame, _class_file| {
+            handler: |frame, _insn, _class_file, _heap| {
                 frame.push_operand(OperandFrame::Int(1));
-                super::JVMEvent::None
+                Ok(super::JVMEvent::None)
             }
         }),
         (5, super::Instruction {
             name: String::from("iconst_i(2)"),
-            handler: |frame, _class_file| {
+            handler: |frame, _insn, _class_file, _heap| {
                 frame.push_operand(OperandFrame::Int(2));
-                super::JVMEvent::None
+                Ok(super::JVMEvent::None)
             }
         }),
         (6, super::Instruction {
             name: String::from("iconst_i(3)"),
-            handler: |frame, _class_file| {
+            handler: |frame, _insn, _class_file, _heap| {
                 frame.push_operand(OperandFrame::Int(3));
-                super::JVMEvent::None
+                Ok(super::JVMEvent::None)
             }
         }),
         (7, super::Instruction {
             name: String::from("iconst_i(4)"),
-            handler: |frame, _class_file| {
+            handler: |frame, _insn, _class_file, _heap| {
                 frame.push_operand(OperandFrame::Int(4));
-                super::JVMEvent::None
+                Ok(super::JVMEvent::None)
             }
         }),
         (8, super::Instruction {
             name: String::from("iconst_i(5)"),
-            handler: |frame, _class_file| {
+            handler: |frame, _insn, _class_file, _heap| {
                 frame.push_operand(OperandFrame::Int(5));
-                super::JVMEvent::None
+                Ok(super::JVMEvent::None)
             }
         }),
         (96, super::Instruction {
             name: String::from("iadd"),
-            handler: |frame, _class_file| {
-                let param1 = IntegerModule::try_to_pop_integer(frame);
-                let param2 = IntegerModule::try_to_pop_integer(frame);
-                frame.push_operand(OperandFrame::Int(param1+param2));
-                super::JVMEvent::None
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Int(param2.wrapping_add(param1)));
+                Ok(super::JVMEvent::None)
             }
         }),
         (104, super::Instruction {
             name: String::from("imul"),
-            handler: |frame, _class_file| {
-                let param1 = IntegerModule::try_to_pop_integer(frame);
-                let param2 = IntegerModule::try_to_pop_integer(frame);
-                frame.push_operand(OperandFrame::Int(param1*param2));
-                super::JVMEvent::None
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Int(param2.wrapping_mul(param1)));
+                Ok(super::JVMEvent::None)
             }
         }),
         (100, super::Instruction {
             name: String::from("isub"),
-            handler: |frame, _class_file| {
-                let param1 = IntegerModule::try_to_pop_integer(frame);
-                let param2 = IntegerModule::try_to_pop_integer(frame);
-                frame.push_operand(OperandFrame::Int(param2-param1));
-                super::JVMEvent::None
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Int(param2.wrapping_sub(param1)));
+                Ok(super::JVMEvent::None)
             }
         }),
         (108, super::Instruction {
             name: String::from("idiv"),
-            handler: |frame, _class_file| {
-                let param1 = IntegerModule::try_to_pop_integer(frame);
-                let param2 = IntegerModule::try_to_pop_integer(frame);
-                frame.push_operand(OperandFrame::Int(param2/param1));
-                super::JVMEvent::None
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                if param1 == 0 {
+                    return Err(JVMError::ArithmeticException(String::from("/ by zero")));
+                }
+                // Integer.MIN_VALUE / -1 overflows to Integer.MIN_VALUE.
+                frame.push_operand(OperandFrame::Int(param2.wrapping_div(param1)));
+                Ok(super::JVMEvent::None)
             }
         }),
         (112, super::Instruction {
-            name: String::from("imod"),
-            handler: |frame, _class_file| {
-                let param1 = IntegerModule::try_to_pop_integer(frame);
-                let param2 = IntegerModule::try_to_pop_integer(frame);
-                frame.push_operand(OperandFrame::Int(param2%param1));
-                super::JVMEvent::None
+            name: String::from("irem"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                if param1 == 0 {
+                    return Err(JVMError::ArithmeticException(String::from("/ by zero")));
+                }
+                frame.push_operand(OperandFrame::Int(param2.wrapping_rem(param1)));
+                Ok(super::JVMEvent::None)
             }
         }),
         (154, super::Instruction {
             name: String::from("ifne"),
-            handler: |frame, _class_file| {
-                let param1 = IntegerModule::try_to_pop_integer(frame);
-                let offset = frame.get_u2();
+            handler: |frame, insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let offset = insn.get_branch_offset().unwrap_or_default();
                 if param1 != 0 {
-                    frame.offset_code((offset-3) as isize);
+                    frame.jump(offset)?;
                 }
-                super::JVMEvent::None
+                Ok(super::JVMEvent::None)
             }
         }),
         (159, super::Instruction {
             name: String::from("if_icmpeq"),
-            handler: |frame, _class_file| {
-                let param1 = IntegerModule::try_to_pop_integer(frame);
-                let param2 = IntegerModule::try_to_pop_integer(frame);
-                let offset = frame.get_u2();
+            handler: |frame, insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                let offset = insn.get_branch_offset().unwrap_or_default();
                 if param1 == param2 {
-                    frame.offset_code((offset-3) as isize);
+                    frame.jump(offset)?;
                 }
-                super::JVMEvent::None
+                Ok(super::JVMEvent::None)
             }
         }),
         (160, super::Instruction {
             name: String::from("if_icmpne"),
-            handler: |frame, _class_file| {
-                let param1 = IntegerModule::try_to_pop_integer(frame);
-                let param2 = IntegerModule::try_to_pop_integer(frame);
-                let offset = frame.get_u2();
+            handler: |frame, insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                let offset = insn.get_branch_offset().unwrap_or_default();
                 if param1 != param2 {
-                    frame.offset_code((offset-3) as isize);
+                    frame.jump(offset)?;
                 }
-                super::JVMEvent::None
+                Ok(super::JVMEvent::None)
             }
         }),
         (161, super::Instruction {
             name: String::from("if_icmplt"),
-            handler: |frame, _class_file| {
-                let param1 = IntegerModule::try_to_pop_integer(frame);
-                let param2 = IntegerModule::try_to_pop_integer(frame);
-                let offset = frame.get_u2();
+            handler: |frame, insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                let offset = insn.get_branch_offset().unwrap_or_default();
                 if  param2 < param1 {
-                    frame.offset_code((offset-3) as isize);
+                    frame.jump(offset)?;
                 }
-                super::JVMEvent::None
+                Ok(super::JVMEvent::None)
             }
         }),
         (162, super::Instruction {
             name: String::from("if_icmpge"),
-            handler: |frame, _class_file| {
-                let param1 = IntegerModule::try_to_pop_integer(frame);
-                let param2 = IntegerModule::try_to_pop_integer(frame);
-                let offset = frame.get_u2();
+            handler: |frame, insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                let offset = insn.get_branch_offset().unwrap_or_default();
                 if  param2 >= param1 {
-                    frame.offset_code((offset-3) as isize);
+                    frame.jump(offset)?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (17, super::Instruction {
+            name: String::from("sipush"),
+            handler: |frame, insn, _class_file, _heap| {
+                if let Insn::Sipush(value) = insn {
+                    frame.push_operand(OperandFrame::Int(*value as i32));
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (116, super::Instruction {
+            name: String::from("ineg"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Int(param1.wrapping_neg()));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (126, super::Instruction {
+            name: String::from("iand"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Int(param2 & param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (128, super::Instruction {
+            name: String::from("ior"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Int(param2 | param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (130, super::Instruction {
+            name: String::from("ixor"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Int(param2 ^ param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (120, super::Instruction {
+            name: String::from("ishl"),
+            handler: |frame, _insn, _class_file, _heap| {
+                // Only the lowest five bits of the shift distance are used.
+                let shift = IntegerModule::try_to_pop_integer(frame)? & 0x1f;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Int(param2.wrapping_shl(shift as u32)));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (122, super::Instruction {
+            name: String::from("ishr"),
+            handler: |frame, _insn, _class_file, _heap| {
+                // Only the lowest five bits of the shift distance are used.
+                let shift = IntegerModule::try_to_pop_integer(frame)? & 0x1f;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Int(param2.wrapping_shr(shift as u32)));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (124, super::Instruction {
+            name: String::from("iushr"),
+            handler: |frame, _insn, _class_file, _heap| {
+                // Only the lowest five bits of the shift distance are used.
+                let shift = IntegerModule::try_to_pop_integer(frame)? & 0x1f;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                frame.push_operand(OperandFrame::Int((param2 as u32).wrapping_shr(shift as u32) as i32));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (153, super::Instruction {
+            name: String::from("ifeq"),
+            handler: |frame, insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let offset = insn.get_branch_offset().unwrap_or_default();
+                if param1 == 0 {
+                    frame.jump(offset)?;
                 }
-                super::JVMEvent::None
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (155, super::Instruction {
+            name: String::from("iflt"),
+            handler: |frame, insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let offset = insn.get_branch_offset().unwrap_or_default();
+                if param1 < 0 {
+                    frame.jump(offset)?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (156, super::Instruction {
+            name: String::from("ifge"),
+            handler: |frame, insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let offset = insn.get_branch_offset().unwrap_or_default();
+                if param1 >= 0 {
+                    frame.jump(offset)?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (157, super::Instruction {
+            name: String::from("ifgt"),
+            handler: |frame, insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let offset = insn.get_branch_offset().unwrap_or_default();
+                if param1 > 0 {
+                    frame.jump(offset)?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (158, super::Instruction {
+            name: String::from("ifle"),
+            handler: |frame, insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let offset = insn.get_branch_offset().unwrap_or_default();
+                if param1 <= 0 {
+                    frame.jump(offset)?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (163, super::Instruction {
+            name: String::from("if_icmpgt"),
+            handler: |frame, insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                let offset = insn.get_branch_offset().unwrap_or_default();
+                if param2 > param1 {
+                    frame.jump(offset)?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (164, super::Instruction {
+            name: String::from("if_icmple"),
+            handler: |frame, insn, _class_file, _heap| {
+                let param1 = IntegerModule::try_to_pop_integer(frame)?;
+                let param2 = IntegerModule::try_to_pop_integer(frame)?;
+                let offset = insn.get_branch_offset().unwrap_or_default();
+                if param2 <= param1 {
+                    frame.jump(offset)?;
+                }
+                Ok(super::JVMEvent::None)
             }
         }),
         (132, super::Instruction {
             name: String::from("iinc"),
-            handler: |frame, _class_file| {
-                let index = frame.get_u1() as usize;
-                let constant = frame.get_u1() as i8;
-                let mom_value = IntegerModule::load_local_variable(frame, index);
-                frame.store_locale_variable(index, LocalFrame::Int(mom_value+i32::from(constant)));
-                super::JVMEvent::None
+            handler: |frame, insn, _class_file, _heap| {
+                if let Insn::Iinc { index, constant } = insn {
+                    let mom_value = IntegerModule::load_local_variable(frame, *index as usize)?;
+                    frame.store_locale_variable(*index as usize, LocalFrame::Int(mom_value.wrapping_add(i32::from(*constant))));
+                }
+                Ok(super::JVMEvent::None)
             }
         }),
         (172, super::Instruction {
             name: String::from("ireturn"),
-            handler: |frame, _class_file| {
-                //TODO: Type checking
-                super::JVMEvent::Return(Some(frame.pop_operand()))
+            handler: |frame, _insn, _class_file, _heap| {
+                Ok(super::JVMEvent::Return(Some(OperandFrame::Int(IntegerModule::try_to_pop_integer(frame)?))))
             }
         })]
     }
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::class_file::validate::MAX_MAJOR_VERSION;
+use crate::util::zip::ZipArchive;
+use super::JVMError;
+
+const MANIFEST_NAME: &str = "META-INF/MANIFEST.MF";
+const VERSIONS_DIRECTORY: &str = "META-INF/versions/";
+/// The Java release whose class files the VM runs, e.g. 17 for major version 61.
+pub const JAVA_RELEASE: u32 = MAX_MAJOR_VERSION as u32 - 44;
+/// Versioned directories of multi-release JARs start at release 9 (JEP 238).
+const MIN_VERSIONED_RELEASE: u32 = 9;
+
+/// The main section of a JAR manifest. Attribute names are case-insensitive.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    main_attributes: HashMap<String, String>,
+}
+
+impl Manifest {
+    /// Parses the main section, which ends at the first empty line. A line starting with a space
+    /// continues the value of the previous line.
+    pub fn parse(text: &str) -> Manifest {
+        let mut lines: Vec<String> = vec![];
+        for line in text.lines() {
+            if line.is_empty() {
+                break;
+            }
+            match (line.strip_prefix(' '), lines.last_mut()) {
+                (Some(continuation), Some(previous)) => previous.push_str(continuation),
+                _ => lines.push(line.to_string()),
+            }
+        }
+        let main_attributes = lines.iter()
+            .filter_map(|line| line.split_once(": "))
+            .map(|(name, value)| (name.to_ascii_lowercase(), value.to_string()))
+            .collect();
+        Manifest { main_attributes }
+    }
+
+    pub fn get_main_attribute(&self, name: &str) -> Option<&str> {
+        self.main_attributes.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+}
+
+/// A JAR file on the class path.
+pub struct Jar {
+    path: PathBuf,
+    archive: ZipArchive,
+    manifest: Manifest,
+    /// The versioned directories a multi-release JAR provides for this VM, highest release first.
+    releases: Vec<u32>,
+}
+
+impl Jar {
+    pub fn open(path: &Path) -> Result<Jar, JVMError> {
+        let archive = ZipArchive::new(fs::read(path).map_err(|_e| JVMError::NoClassDefFoundError(path.display().to_string()))?)?;
+        let manifest = match archive.read(MANIFEST_NAME)? {
+            Some(bytes) => Manifest::parse(&String::from_utf8_lossy(&bytes)),
+            None => Manifest::default(),
+        };
+        let mut releases = vec![];
+        if manifest.get_main_attribute("Multi-Release").is_some_and(|value| value.eq_ignore_ascii_case("true")) {
+            for entry in archive.get_entries() {
+                let release = entry.get_name().strip_prefix(VERSIONS_DIRECTORY)
+                    .and_then(|name| name.split_once('/'))
+                    .and_then(|(release, _name)| release.parse::<u32>().ok());
+                if let Some(release) = release {
+                    if (MIN_VERSIONED_RELEASE..=JAVA_RELEASE).contains(&release) && !releases.contains(&release) {
+                        releases.push(release);
+                    }
+                }
+            }
+            releases.sort_unstable_by(|a, b| b.cmp(a));
+        }
+        Ok(Jar { path: path.to_path_buf(), archive, manifest, releases })
+    }
+
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    #[allow(dead_code)]
+    pub fn get_manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// The `Main-Class` of the manifest as binary name in internal form.
+    pub fn get_main_class(&self) -> Option<String> {
+        self.manifest.get_main_attribute("Main-Class").map(|name| name.trim().replace('.', "/"))
+    }
+
+    /// The `Class-Path` of the manifest: space separated relative URLs, resolved against the
+    /// directory of the JAR.
+    pub fn get_class_path(&self) -> Vec<PathBuf> {
+        let directory = self.path.parent().unwrap_or(Path::new(""));
+        self.manifest.get_main_attribute("Class-Path").unwrap_or_default()
+            .split_whitespace()
+            .map(|url| directory.join(url.strip_prefix("file:").unwrap_or(url)))
+            .collect()
+    }
+
+    /// The class file of `class_name`. A multi-release JAR serves it from the highest versioned
+    /// directory the VM supports that contains it, and from the root otherwise.
+    pub fn read_class(&self, class_name: &str) -> Result<Option<Vec<u8>>, JVMError> {
+        let name = format!("{}.class", class_name);
+        for release in &self.releases {
+            if let Some(bytes) = self.archive.read(&format!("{}{}/{}", VERSIONS_DIRECTORY, release, name))? {
+                return Ok(Some(bytes));
+            }
+        }
+        Ok(self.archive.read(&name)?)
+    }
+}
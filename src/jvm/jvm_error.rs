@@ -0,0 +1,137 @@
+use std::error::Error;
+use std::fmt;
+use crate::class_file::class_format_error::ClassFormatError;
+use crate::class_file::parse_error::ParseError;
+use crate::util::zip::ZipError;
+use crate::verifier::verify_error::VerifyError;
+use super::heap::Reference;
+
+/// Error that unwinds the interpreter until an exception handler catches it. Apart from `Parse`,
+/// `Zip`, `Thrown`, `Exit` and `Internal`, the variants are named after the `java.lang` error or
+/// exception the JVMS prescribes for the situation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JVMError {
+    Parse(ParseError),
+    /// A JAR file on the class path is not a readable ZIP archive.
+    Zip(ZipError),
+    ClassFormatError(ClassFormatError),
+    VerifyError(VerifyError),
+    NoClassDefFoundError(String),
+    ClassCircularityError(String),
+    NoSuchFieldError(String),
+    NoSuchMethodError(String),
+    IncompatibleClassChangeError(String),
+    AbstractMethodError(String),
+    UnsatisfiedLinkError(String),
+    InstantiationError(String),
+    /// An exception thrown by a static initializer, with the exception as its cause.
+    ExceptionInInitializerError(Box<JVMError>),
+    StackOverflowError,
+    OutOfMemoryError(String),
+    ArithmeticException(String),
+    IllegalArgumentException(String),
+    ArrayIndexOutOfBoundsException(String),
+    NegativeArraySizeException(String),
+    ArrayStoreException(String),
+    NullPointerException(String),
+    /// A throwable object of the program, thrown by athrow, with its class in internal form and
+    /// its detail message.
+    Thrown { reference: Reference, class_name: String, message: Option<String>, is_error: bool },
+    /// Not an error: `System.exit` unwinds the interpreter with the exit status.
+    Exit(i32),
+    /// Bytecode the interpreter cannot execute: an instruction or operand it does not implement,
+    /// or code the verifier would have rejected. Not visible to the program.
+    Internal(String),
+}
+
+impl fmt::Display for JVMError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JVMError::Parse(error) => write!(f, "{}", error),
+            JVMError::Zip(error) => write!(f, "java.util.zip.ZipException: {}", error),
+            JVMError::ClassFormatError(error) => write!(f, "{}", error),
+            JVMError::VerifyError(error) => write!(f, "{}", error),
+            JVMError::NoClassDefFoundError(message) => write!(f, "java.lang.NoClassDefFoundError: {}", message),
+            JVMError::ClassCircularityError(message) => write!(f, "java.lang.ClassCircularityError: {}", message),
+            JVMError::NoSuchFieldError(message) => write!(f, "java.lang.NoSuchFieldError: {}", message),
+            JVMError::NoSuchMethodError(message) => write!(f, "java.lang.NoSuchMethodError: {}", message),
+            JVMError::IncompatibleClassChangeError(message) => write!(f, "java.lang.IncompatibleClassChangeError: {}", message),
+            JVMError::AbstractMethodError(message) => write!(f, "java.lang.AbstractMethodError: {}", message),
+            JVMError::UnsatisfiedLinkError(message) => write!(f, "java.lang.UnsatisfiedLinkError: {}", message),
+            JVMError::InstantiationError(message) => write!(f, "java.lang.InstantiationError: {}", message),
+            JVMError::ExceptionInInitializerError(cause) => write!(f, "java.lang.ExceptionInInitializerError\nCaused by: {}", cause),
+            JVMError::StackOverflowError => write!(f, "java.lang.StackOverflowError"),
+            JVMError::OutOfMemoryError(message) => write!(f, "java.lang.OutOfMemoryError: {}", message),
+            JVMError::ArithmeticException(message) => write!(f, "java.lang.ArithmeticException: {}", message),
+            JVMError::IllegalArgumentException(message) => write!(f, "java.lang.IllegalArgumentException: {}", message),
+            JVMError::ArrayIndexOutOfBoundsException(message) => write!(f, "java.lang.ArrayIndexOutOfBoundsException: {}", message),
+            JVMError::NegativeArraySizeException(message) => write!(f, "java.lang.NegativeArraySizeException: {}", message),
+            JVMError::ArrayStoreException(message) => write!(f, "java.lang.ArrayStoreException: {}", message),
+            JVMError::NullPointerException(message) => write!(f, "java.lang.NullPointerException: {}", message),
+            JVMError::Thrown { class_name, message: Some(message), .. } => write!(f, "{}: {}", class_name.replace('/', "."), message),
+            JVMError::Thrown { class_name, message: None, .. } => write!(f, "{}", class_name.replace('/', ".")),
+            JVMError::Exit(status) => write!(f, "exit status {}", status),
+            JVMError::Internal(message) => write!(f, "internal error: {}", message),
+        }
+    }
+}
+
+impl JVMError {
+    /// Whether the variant stands for a `java.lang.Exception` rather than a `java.lang.Error`.
+    pub fn is_exception(&self) -> bool {
+        matches!(self, JVMError::Zip(_) | JVMError::ArithmeticException(_) | JVMError::IllegalArgumentException(_) | JVMError::ArrayIndexOutOfBoundsException(_) | JVMError::NegativeArraySizeException(_)
+            | JVMError::ArrayStoreException(_) | JVMError::NullPointerException(_))
+            || matches!(self, JVMError::Thrown { is_error: false, .. })
+    }
+
+    /// The class of the throwable, in internal form, e.g. `java/lang/ArithmeticException`.
+    pub fn get_class_name(&self) -> String {
+        match self {
+            JVMError::Thrown { class_name, .. } => class_name.clone(),
+            JVMError::ExceptionInInitializerError(_) => String::from("java/lang/ExceptionInInitializerError"),
+            // The description starts with the binary name of the class.
+            error => error.to_string().split(':').next().unwrap_or_default().replace('.', "/"),
+        }
+    }
+
+    /// The detail message of the throwable, the description without the class name.
+    pub fn get_message(&self) -> Option<String> {
+        match self {
+            JVMError::Thrown { message, .. } => message.clone(),
+            JVMError::ExceptionInInitializerError(_) => None,
+            error => error.to_string().split_once(": ").map(|(_class_name, message)| message.to_string()),
+        }
+    }
+
+    /// Whether the variant stands for a `java.lang.Throwable` the program could observe, rather
+    /// than for a failure of the VM or for `System.exit`.
+    pub fn is_throwable(&self) -> bool {
+        !matches!(self, JVMError::Parse(_) | JVMError::Exit(_) | JVMError::Internal(_))
+    }
+}
+
+impl Error for JVMError {}
+
+impl From<ParseError> for JVMError {
+    fn from(error: ParseError) -> JVMError {
+        JVMError::Parse(error)
+    }
+}
+
+impl From<ClassFormatError> for JVMError {
+    fn from(error: ClassFormatError) -> JVMError {
+        JVMError::ClassFormatError(error)
+    }
+}
+
+impl From<VerifyError> for JVMError {
+    fn from(error: VerifyError) -> JVMError {
+        JVMError::VerifyError(error)
+    }
+}
+
+impl From<ZipError> for JVMError {
+    fn from(error: ZipError) -> JVMError {
+        JVMError::Zip(error)
+    }
+}
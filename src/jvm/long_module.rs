@@ -0,0 +1,265 @@
+use super::{InstructionModule, Frame, JVMError, OperandFrame, LocalFrame};
+use super::integer_module::IntegerModule;
+
+/// Instructions on `long` values. A long takes two local variables, of which the second is unused,
+/// and one entry of category 2 on the operand stack.
+pub struct LongModule;
+
+impl LongModule {
+    pub fn store_to_local(frame: &mut Frame, index: usize) -> Result<(), JVMError> {
+        let long = LongModule::try_to_pop_long(frame)?;
+        frame.store_locale_variable(index, LocalFrame::Long(long));
+        frame.store_locale_variable(index + 1, LocalFrame::None);
+        Ok(())
+    }
+    pub fn load_to_stack(frame: &mut Frame, index: usize) -> Result<(), JVMError> {
+        match frame.load_locale_variable(index) {
+            LocalFrame::Long(long) => frame.push_operand(OperandFrame::Long(long)),
+            local_value => return Err(JVMError::Internal(format!("The local variable {:?} is no long", local_value))),
+        }
+        Ok(())
+    }
+    pub fn try_to_pop_long(frame: &mut Frame) -> Result<i64, JVMError> {
+        match frame.pop_operand()? {
+            OperandFrame::Long(long) => Ok(long),
+            operand => Err(JVMError::Internal(format!("The operand {:?} is no long", operand))),
+        }
+    }
+}
+
+impl InstructionModule for LongModule {
+    fn get_instructions(&self) -> Vec<(u8 , super::Instruction)> {
+        vec![
+        (9, super::Instruction {
+            name: String::from("lconst_l(0)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                frame.push_operand(OperandFrame::Long(0));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (10, super::Instruction {
+            name: String::from("lconst_l(1)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                frame.push_operand(OperandFrame::Long(1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (20, super::Instruction {
+            name: String::from("ldc2_w"),
+            handler: |frame, insn, class_file, _heap| {
+                let constant = class_file.get_constant(insn.get_constant_index().unwrap_or_default() as usize)?;
+                match OperandFrame::from_numeric_constant(constant) {
+                    Some(value) if value.is_category_2() => frame.push_operand(value),
+                    _ => return Err(JVMError::Internal(format!("ldc2_w is not implemented for {:?}", constant))),
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (55, super::Instruction {
+            name: String::from("lstore"),
+            handler: |frame, insn, _class_file, _heap| {
+                if let Some(index) = insn.get_local_index() {
+                    LongModule::store_to_local(frame, index as usize)?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (63, super::Instruction {
+            name: String::from("lstore_n(n=0)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                LongModule::store_to_local(frame, 0)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (64, super::Instruction {
+            name: String::from("lstore_n(n=1)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                LongModule::store_to_local(frame, 1)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (65, super::Instruction {
+            name: String::from("lstore_n(n=2)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                LongModule::store_to_local(frame, 2)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (66, super::Instruction {
+            name: String::from("lstore_n(n=3)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                LongModule::store_to_local(frame, 3)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (22, super::Instruction {
+            name: String::from("lload"),
+            handler: |frame, insn, _class_file, _heap| {
+                if let Some(index) = insn.get_local_index() {
+                    LongModule::load_to_stack(frame, index as usize)?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (30, super::Instruction {
+            name: String::from("lload_n(n=0)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                LongModule::load_to_stack(frame, 0)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (31, super::Instruction {
+            name: String::from("lload_n(n=1)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                LongModule::load_to_stack(frame, 1)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (32, super::Instruction {
+            name: String::from("lload_n(n=2)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                LongModule::load_to_stack(frame, 2)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (33, super::Instruction {
+            name: String::from("lload_n(n=3)"),
+            handler: |frame, _insn, _class_file, _heap| {
+                LongModule::load_to_stack(frame, 3)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (97, super::Instruction {
+            name: String::from("ladd"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = LongModule::try_to_pop_long(frame)?;
+                let param2 = LongModule::try_to_pop_long(frame)?;
+                frame.push_operand(OperandFrame::Long(param2.wrapping_add(param1)));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (101, super::Instruction {
+            name: String::from("lsub"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = LongModule::try_to_pop_long(frame)?;
+                let param2 = LongModule::try_to_pop_long(frame)?;
+                frame.push_operand(OperandFrame::Long(param2.wrapping_sub(param1)));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (105, super::Instruction {
+            name: String::from("lmul"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = LongModule::try_to_pop_long(frame)?;
+                let param2 = LongModule::try_to_pop_long(frame)?;
+                frame.push_operand(OperandFrame::Long(param2.wrapping_mul(param1)));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (109, super::Instruction {
+            name: String::from("ldiv"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = LongModule::try_to_pop_long(frame)?;
+                let param2 = LongModule::try_to_pop_long(frame)?;
+                if param1 == 0 {
+                    return Err(JVMError::ArithmeticException(String::from("/ by zero")));
+                }
+                // Long.MIN_VALUE / -1 overflows to Long.MIN_VALUE.
+                frame.push_operand(OperandFrame::Long(param2.wrapping_div(param1)));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (113, super::Instruction {
+            name: String::from("lrem"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = LongModule::try_to_pop_long(frame)?;
+                let param2 = LongModule::try_to_pop_long(frame)?;
+                if param1 == 0 {
+                    return Err(JVMError::ArithmeticException(String::from("/ by zero")));
+                }
+                frame.push_operand(OperandFrame::Long(param2.wrapping_rem(param1)));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (117, super::Instruction {
+            name: String::from("lneg"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = LongModule::try_to_pop_long(frame)?;
+                frame.push_operand(OperandFrame::Long(param1.wrapping_neg()));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (121, super::Instruction {
+            name: String::from("lshl"),
+            handler: |frame, _insn, _class_file, _heap| {
+                // Only the lowest six bits of the shift distance are used.
+                let shift = IntegerModule::try_to_pop_integer(frame)? & 0x3f;
+                let param2 = LongModule::try_to_pop_long(frame)?;
+                frame.push_operand(OperandFrame::Long(param2.wrapping_shl(shift as u32)));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (123, super::Instruction {
+            name: String::from("lshr"),
+            handler: |frame, _insn, _class_file, _heap| {
+                // Only the lowest six bits of the shift distance are used.
+                let shift = IntegerModule::try_to_pop_integer(frame)? & 0x3f;
+                let param2 = LongModule::try_to_pop_long(frame)?;
+                frame.push_operand(OperandFrame::Long(param2.wrapping_shr(shift as u32)));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (125, super::Instruction {
+            name: String::from("lushr"),
+            handler: |frame, _insn, _class_file, _heap| {
+                // Only the lowest six bits of the shift distance are used.
+                let shift = IntegerModule::try_to_pop_integer(frame)? & 0x3f;
+                let param2 = LongModule::try_to_pop_long(frame)?;
+                frame.push_operand(OperandFrame::Long((param2 as u64).wrapping_shr(shift as u32) as i64));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (127, super::Instruction {
+            name: String::from("land"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = LongModule::try_to_pop_long(frame)?;
+                let param2 = LongModule::try_to_pop_long(frame)?;
+                frame.push_operand(OperandFrame::Long(param2 & param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (129, super::Instruction {
+            name: String::from("lor"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = LongModule::try_to_pop_long(frame)?;
+                let param2 = LongModule::try_to_pop_long(frame)?;
+                frame.push_operand(OperandFrame::Long(param2 | param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (131, super::Instruction {
+            name: String::from("lxor"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = LongModule::try_to_pop_long(frame)?;
+                let param2 = LongModule::try_to_pop_long(frame)?;
+                frame.push_operand(OperandFrame::Long(param2 ^ param1));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (148, super::Instruction {
+            name: String::from("lcmp"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = LongModule::try_to_pop_long(frame)?;
+                let param2 = LongModule::try_to_pop_long(frame)?;
+                frame.push_operand(OperandFrame::Int(param2.cmp(&param1) as i32));
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (173, super::Instruction {
+            name: String::from("lreturn"),
+            handler: |frame, _insn, _class_file, _heap| {
+                Ok(super::JVMEvent::Return(Some(OperandFrame::Long(LongModule::try_to_pop_long(frame)?))))
+            }
+        })]
+    }
+}
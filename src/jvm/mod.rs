@@ -1,18 +1,92 @@
 pub mod static_module;
 pub mod integer_module;
-use super::class_file::{attribute_info, method_info, const_type, ClassFile};
+pub mod reference_module;
+pub mod stack_module;
+pub mod array_module;
+pub mod long_module;
+pub mod float_module;
+pub mod double_module;
+pub mod conversion_module;
+pub mod heap;
+pub mod jvm_error;
+pub mod class_loader;
+pub mod jar;
+use super::class_file::{attribute, attribute_info, method_info, const_type, code_attribute, descriptor, ClassFile};
+use super::class_file::exception_table_entry::ExceptionTableEntry;
+use class_loader::ClassLoader;
+use heap::{Heap, Reference};
+use super::class_file::instruction::Insn;
 use std::collections::HashMap;
-use super::util::file;
+use std::rc::Rc;
+use super::class_file::parse_error::{ParseError, ParseErrorKind};
+use super::class_file::class_format_error::{ClassFormatError, ClassFormatErrorKind};
+pub use jvm_error::JVMError;
 
+/// The thread stack size if `-Xss` is not given, as in HotSpot on 64-bit Linux.
+pub const DEFAULT_THREAD_STACK_SIZE: usize = 1024 * 1024;
+/// Bytes a frame takes on the thread stack besides its locals and operand stack.
+const FRAME_OVERHEAD: usize = 64;
+/// The maximum heap size if `-Xmx` is not given.
+pub const DEFAULT_MAX_HEAP_SIZE: usize = 256 * 1024 * 1024;
 
+#[allow(clippy::upper_case_acronyms)]
 pub struct JVM {
+    class_loader: ClassLoader,
+    heap: Heap,
     instructions: HashMap<u8, Instruction>,
-    debug: bool
+    /// Implementations of native methods, keyed by class name, method name and descriptor.
+    native_methods: HashMap<(String, String, String), NativeMethodHandler>,
+    /// Values of the static fields, keyed by class name and field name.
+    static_fields: HashMap<(String, String), OperandFrame>,
+    /// States of the classes whose initialization has started (JVMS §5.5), keyed by class name.
+    class_states: HashMap<String, ClassState>,
+    /// Entry counts of the monitors currently held, keyed by owner.
+    monitors: HashMap<String, usize>,
+    /// `-D` system properties.
+    system_properties: HashMap<String, String>,
+    /// Bytes the frames of the thread may take before a `StackOverflowError` is thrown.
+    thread_stack_size: usize,
+    /// Bytes taken by the frames currently executing.
+    stack_size: usize,
+    /// Print every executed instruction to stderr, as `-XX:+TraceBytecodes` does.
+    trace_bytecodes: bool,
 }
 
 impl JVM {
-    pub fn new() -> JVM {
-        JVM { instructions: HashMap::new(), debug: true }
+    pub fn new(class_loader: ClassLoader) -> JVM {
+        JVM {
+            class_loader,
+            heap: Heap::new(DEFAULT_MAX_HEAP_SIZE),
+            instructions: HashMap::new(),
+            native_methods: HashMap::new(),
+            static_fields: HashMap::new(),
+            class_states: HashMap::new(),
+            monitors: HashMap::new(),
+            system_properties: HashMap::new(),
+            thread_stack_size: DEFAULT_THREAD_STACK_SIZE,
+            stack_size: 0,
+            trace_bytecodes: false,
+        }
+    }
+
+    pub fn with_thread_stack_size(mut self, thread_stack_size: usize) -> JVM {
+        self.thread_stack_size = thread_stack_size;
+        self
+    }
+
+    pub fn with_max_heap_size(mut self, max_heap_size: usize) -> JVM {
+        self.heap = Heap::new(max_heap_size);
+        self
+    }
+
+    pub fn with_trace_bytecodes(mut self, trace_bytecodes: bool) -> JVM {
+        self.trace_bytecodes = trace_bytecodes;
+        self
+    }
+
+    pub fn with_system_properties(mut self, system_properties: HashMap<String, String>) -> JVM {
+        self.system_properties = system_properties;
+        self
     }
 
     #[allow(dead_code)]
@@ -28,126 +102,729 @@ impl JVM {
         for (opcode, handler) in instructions {
             self.instructions.insert(opcode, handler);
         }
+        for native_method in module.get_native_methods() {
+            self.native_methods.insert((native_method.class_name, native_method.name, native_method.descriptor), native_method.handler);
+        }
         self
     }
 
-    pub fn execute_main_method(&mut self, class_file: &ClassFile, main_method: &method_info::MethodInfo) {
-        let code_attribute = find_attributes_by_name(class_file, Box::new(main_method), "Code".as_bytes().to_vec())[0].to_code_attribute();
-        println!("Code: {:?}", code_attribute.get_code());
-        let mut locale_variables = Vec::new();
-        for _i in 0..code_attribute.get_max_locals() {
-            locale_variables.push(LocalFrame::None);
+    /// Initializes the main class and runs `main` with the program arguments as `String[]` in
+    /// local variable 0.
+    pub fn execute_main_method(&mut self, class_file: &ClassFile, main_method: &method_info::MethodInfo, args: &[String]) -> Result<(), JVMError> {
+        let main_class = self.class_loader.load_class(&class_file.get_name_of_class(class_file.get_this_class() as usize)?)?;
+        self.initialize_class(&main_class)?;
+        let code_attribute = find_code_attribute(class_file, main_method)?;
+        let mut frame = Frame::new(&code_attribute)?;
+        let args = self.heap.allocate_string_array(args)?;
+        frame.store_locale_variable(0, LocalFrame::Reference(args));
+        self.execute_frame(class_file, frame)?;
+        Ok(())
+    }
+
+    /// Executes `frame` on top of the thread stack, throwing a `StackOverflowError` if it does not
+    /// fit.
+    fn execute_frame(&mut self, class_file: &ClassFile, frame: Frame) -> Result<Option<OperandFrame>, JVMError> {
+        let frame_size = FRAME_OVERHEAD + 8 * (frame.locale_variables.len() + frame.max_stack);
+        if self.stack_size + frame_size > self.thread_stack_size {
+            return Err(JVMError::StackOverflowError);
+        }
+        self.stack_size += frame_size;
+        let return_value = self.execute_method(class_file, frame);
+        self.stack_size -= frame_size;
+        return_value
+    }
+
+    /// Calls the native implementation of a method of the class library, which is not loaded from
+    /// class files. Returns false if no module implements the method.
+    fn invoke_library_method(&mut self, class_file: &ClassFile, frame: &mut Frame, invoke_index: usize) -> Result<bool, JVMError> {
+        let (class_name, method_name, descriptor) = class_file.get_member_ref(invoke_index)?;
+        if !class_loader::is_library_class(&class_name) {
+            return Ok(false);
+        }
+        let Some(native_method) = self.find_library_method(&class_name, &method_name, &descriptor) else {
+            return Ok(false);
+        };
+        if let Some(return_value) = native_method(frame, class_file, &mut self.heap, &self.system_properties)? {
+            frame.push_operand(return_value);
+        }
+        Ok(true)
+    }
+
+    /// The native implementation of the library method, declared in `class_name` or inherited from
+    /// one of its library superclasses, except for constructors.
+    fn find_library_method(&self, class_name: &str, method_name: &str, descriptor: &str) -> Option<NativeMethodHandler> {
+        let mut class_name = class_name;
+        loop {
+            if let Some(&native_method) = self.native_methods.get(&(class_name.to_string(), method_name.to_string(), descriptor.to_string())) {
+                return Some(native_method);
+            }
+            match class_loader::get_library_superclass(class_name) {
+                Some(super_class) if method_name != "<init>" => class_name = super_class,
+                _ => return None,
+            }
+        }
+    }
+
+    /// Invokes the static method referenced by the `Methodref` constant `invoke_index`, taking the
+    /// arguments from and pushing the return value onto `frame`.
+    fn invoke_static(&mut self, class_file: &ClassFile, frame: &mut Frame, invoke_index: usize) -> Result<(), JVMError> {
+        if self.invoke_library_method(class_file, frame, invoke_index)? {
+            return Ok(());
+        }
+        let (class, method_position) = self.class_loader.resolve_method(class_file, invoke_index)?;
+        if !class.get_methods()[method_position].get_access_flags().is_static() {
+            return Err(JVMError::IncompatibleClassChangeError(format!("Expected static method {}", qualified_method_name(&class, method_position)?)));
+        }
+        self.initialize_class(&class)?;
+        self.invoke(frame, class, method_position)
+    }
+
+    /// Invokes an instance initialization method, a private method or a method of a superclass
+    /// (JVMS §6.5 invokespecial) on the object below the arguments on `frame`.
+    fn invoke_special(&mut self, class_file: &ClassFile, frame: &mut Frame, invoke_index: usize) -> Result<(), JVMError> {
+        if self.invoke_library_method(class_file, frame, invoke_index)? {
+            return Ok(());
+        }
+        let (class_name, method_name, descriptor) = class_file.get_member_ref(invoke_index)?;
+        let (class, method_position) = self.class_loader.resolve_method(class_file, invoke_index)?;
+        let resolved_class_name = class.get_name_of_class(class.get_this_class() as usize)?;
+        if method_name == "<init>" && resolved_class_name != class_name {
+            // Constructors are not inherited.
+            return Err(JVMError::NoSuchMethodError(format!("{}.{}{}", class_name.replace('/', "."), method_name, descriptor)));
+        }
+        if class.get_methods()[method_position].get_access_flags().is_static() {
+            return Err(JVMError::IncompatibleClassChangeError(format!("Expected non-static method {}", qualified_method_name(&class, method_position)?)));
+        }
+        self.check_receiver(&class, method_position, frame)?;
+        // super.m() starts the search for an overriding method at the direct superclass of the
+        // current class.
+        let current_class = class_file.get_name_of_class(class_file.get_this_class() as usize)?;
+        let super_class = match class_file.get_super_class() {
+            0 => None,
+            super_class => Some(class_file.get_name_of_class(super_class as usize)?),
+        };
+        if method_name != "<init>" && class_file.get_access_flags().is_super() && !class.get_access_flags().is_interface()
+            && current_class != class_name && self.class_loader.is_subclass(&current_class, &class_name)? {
+            if let Some(super_class) = super_class {
+                if let Some((class, method_position)) = self.class_loader.select_method(&super_class, &method_name, &descriptor)? {
+                    return self.invoke(frame, class, method_position);
+                }
+            }
+        }
+        self.invoke(frame, class, method_position)
+    }
+
+    /// Invokes the method referenced by `invoke_index` that the class of the object below the
+    /// arguments on `frame` selects (JVMS §5.4.6), for invokevirtual and invokeinterface.
+    fn invoke_virtual(&mut self, class_file: &ClassFile, frame: &mut Frame, invoke_index: usize) -> Result<(), JVMError> {
+        if self.invoke_library_method(class_file, frame, invoke_index)? {
+            return Ok(());
+        }
+        let (class_name, method_name, descriptor) = class_file.get_member_ref(invoke_index)?;
+        let (class, method_position) = match self.class_loader.resolve_method(class_file, invoke_index) {
+            // The method may be inherited from the class library, like Throwable.getMessage.
+            Err(JVMError::NoSuchMethodError(message)) => {
+                let native_method = match self.class_loader.load_library_superclass(&class_name)? {
+                    Some(library_class) => self.find_library_method(&library_class, &method_name, &descriptor),
+                    None => None,
+                };
+                let native_method = native_method.ok_or(JVMError::NoSuchMethodError(message))?;
+                if let Some(return_value) = native_method(frame, class_file, &mut self.heap, &self.system_properties)? {
+                    frame.push_operand(return_value);
+                }
+                return Ok(());
+            }
+            resolved => resolved?,
+        };
+        let flags = class.get_methods()[method_position].get_access_flags();
+        if flags.is_static() {
+            return Err(JVMError::IncompatibleClassChangeError(format!("Expected non-static method {}", qualified_method_name(&class, method_position)?)));
+        }
+        let receiver = self.check_receiver(&class, method_position, frame)?;
+        if flags.is_private() {
+            return self.invoke(frame, class, method_position);
+        }
+        let receiver_class = self.heap.get_class_name(receiver);
+        match self.class_loader.select_method(&receiver_class, &method_name, &descriptor)? {
+            Some((class, method_position)) => self.invoke(frame, class, method_position),
+            None => Err(JVMError::AbstractMethodError(format!("Receiver class {} does not define or inherit an implementation of the resolved method {}",
+                receiver_class.replace('/', "."), qualified_method_name(&class, method_position)?))),
+        }
+    }
+
+    /// The object an instance method is invoked on, below its arguments on `frame`. Throws a
+    /// `NullPointerException` if it is null.
+    fn check_receiver(&self, class: &ClassFile, method_position: usize, frame: &Frame) -> Result<Reference, JVMError> {
+        let method = &class.get_methods()[method_position];
+        let descriptor = descriptor::parse_method_descriptor(&class.get_utf8(method.get_descriptor_index() as usize)?)?;
+        match frame.peek_operand(descriptor.get_parameters().len())? {
+            OperandFrame::Reference(reference) => Ok(*reference),
+            OperandFrame::Null => Err(JVMError::NullPointerException(format!("Cannot invoke \"{}\"", qualified_method_name(class, method_position)?))),
+            receiver => Err(JVMError::Internal(format!("Invoking a method on {:?} is not implemented", receiver))),
         }
-        self.execute_method(class_file, Frame { code: file::File::new(code_attribute.get_code()), operand_stack: vec![], locale_variables });
     }
 
-    pub fn execute_method(&mut self, class_file: &ClassFile, mut frame: Frame) -> Option<OperandFrame>{
-        while frame.code.has_next() {
-            let opcode = frame.code.get_u1();
-            if self.instructions.contains_key(&opcode) {
-                let instruction = self.instructions.get(&opcode).unwrap();
-                if self.debug {
-                    println!("Instruction: {}({})", instruction.name, opcode);
+    /// Invokes the method at `method_position` of `class`, taking the arguments, and the object for
+    /// an instance method, from and pushing the return value onto `frame`.
+    fn invoke(&mut self, frame: &mut Frame, class: Rc<ClassFile>, method_position: usize) -> Result<(), JVMError> {
+        let class_name = class.get_name_of_class(class.get_this_class() as usize)?;
+        let method = &class.get_methods()[method_position];
+        let method_name = class.get_utf8(method.get_name_index() as usize)?;
+        let descriptor = class.get_utf8(method.get_descriptor_index() as usize)?;
+        let flags = method.get_access_flags();
+        let qualified_name = qualified_method_name(&class, method_position)?;
+        if flags.is_abstract() {
+            return Err(JVMError::AbstractMethodError(qualified_name));
+        }
+        if flags.is_native() {
+            let native_method = *self.native_methods.get(&(class_name, method_name, descriptor))
+                .ok_or(JVMError::UnsatisfiedLinkError(qualified_name))?;
+            if let Some(return_value) = native_method(frame, &class, &mut self.heap, &self.system_properties)? {
+                frame.push_operand(return_value);
+            }
+            return Ok(());
+        }
+        let code_attribute = find_code_attribute(&class, method)?;
+        let mut invoked_frame = Frame::new(&code_attribute)?;
+        let descriptor = descriptor::parse_method_descriptor(&descriptor)?;
+        let receiver_slots = if flags.is_static() { 0 } else { 1 };
+        let mut slot = receiver_slots + descriptor.get_argument_slots();
+        for parameter in descriptor.get_parameters().iter().rev() {
+            slot -= parameter.get_slot_count();
+            let param = frame.pop_operand()?;
+            match (parameter, param) {
+                (descriptor::FieldType::Base(base_type), OperandFrame::Int(integer)) if base_type.is_int_like() => {
+                    invoked_frame.store_locale_variable(slot, LocalFrame::Int(integer));
                 }
-                let result = instruction.get_handler()(&mut frame, class_file);
-                match result {
-                    JVMEvent::InvokeMethod(invoke_index) => {
-                        let method_ref = class_file.get_constant(invoke_index);
-                        match method_ref {
-                            const_type::ConstType::ConstantMethodref(class_index, type_and_name_index) => {
-                                let method_name = class_file.get_name_of_member(*type_and_name_index as usize);
-                                let _class_name = class_file.get_name_of_class(*class_index as usize);
-                                let descriptor = class_file.get_description_of_member(*type_and_name_index as usize);
-                                let method = find_methods_by_name(class_file, Box::new(class_file), method_name.into_bytes())[0];
-                                let code_attribute = find_attributes_by_name(class_file, Box::new(method), "Code".as_bytes().to_vec())[0].to_code_attribute();
-                                let mut locale_variables = Vec::new();
-                                for _i in 0..code_attribute.get_max_locals() {
-                                    locale_variables.push(LocalFrame::None);
-                                }
-                                let mut param_index = 1;
-                                let mut arg_index = 0;
-                                let mut d = 0;
-                                while &descriptor[param_index..param_index+1] != ")" {
-                                    let c = &descriptor[param_index..param_index+1];
-                                    if c == "[" {
-                                        d += 1;
-                                    } else {
-                                        let param = frame.pop_operand();
-                                        if d == 0 {
-                                            match c {
-                                                "I" => {
-                                                    match param {
-                                                        OperandFrame::Int(integer) => {
-                                                            locale_variables[arg_index] = LocalFrame::Int(integer);
-                                                        }
-                                                        _ => {panic!("The top Operand({:?}) was no Int!", param);}
-                                                    }
-                                                }
-                                                _ => {panic!("The descriptor {} is no implemented!", &descriptor[param_index..param_index+1])}
-                                            }
-                                        } else {
-                                            panic!("Can't handle arrays in decoraters!");
-                                        }
-                                        d = 0;
-                                        arg_index += 1;
-                                    }
-                                    param_index += 1;
-                                }
-                                let return_value = self.execute_method(&class_file, Frame { code: file::File::new(code_attribute.get_code()), operand_stack: vec![], locale_variables });
-                                if return_value.is_some() {
-                                    frame.push_operand(return_value.unwrap());
-                                }
-                            }
-                            _ => {panic!("The constant {:?} is not implemented for cannot be staticly invoked!", method_ref)}
-                        }
-                    }
-                    JVMEvent::Return(return_value) => {
-                        return return_value;
-                    }
-                    _ => {}
+                (descriptor::FieldType::Base(descriptor::BaseType::Long), OperandFrame::Long(long)) => {
+                    invoked_frame.store_locale_variable(slot, LocalFrame::Long(long));
                 }
-            } else {
-                panic!("The opcode({}) is not implemented!", opcode);
+                (descriptor::FieldType::Base(descriptor::BaseType::Float), OperandFrame::Float(float)) => {
+                    invoked_frame.store_locale_variable(slot, LocalFrame::Float(float));
+                }
+                (descriptor::FieldType::Base(descriptor::BaseType::Double), OperandFrame::Double(double)) => {
+                    invoked_frame.store_locale_variable(slot, LocalFrame::Double(double));
+                }
+                (descriptor::FieldType::Object(_) | descriptor::FieldType::Array(_), param) => {
+                    invoked_frame.store_locale_variable(slot, LocalFrame::from_reference(param)?);
+                }
+                (parameter, param) => return Err(JVMError::Internal(format!("Passing the operand {:?} as {} is not implemented", param, parameter))),
             }
         }
-        None
+        // A synchronized method locks the monitor of its class or object for the whole invocation.
+        let monitor = if flags.is_static() {
+            class_name
+        } else {
+            let receiver = frame.pop_operand()?;
+            let monitor = match receiver {
+                OperandFrame::Reference(reference) => format!("@{}", reference),
+                _ => String::from("System.out"),
+            };
+            invoked_frame.store_locale_variable(0, LocalFrame::from_reference(receiver)?);
+            monitor
+        };
+        if flags.is_synchronized() {
+            self.enter_monitor(&monitor);
+        }
+        let return_value = self.execute_frame(&class, invoked_frame);
+        if flags.is_synchronized() {
+            self.exit_monitor(&monitor);
+        }
+        if let Some(return_value) = return_value? {
+            frame.push_operand(return_value);
+        }
+        Ok(())
+    }
+
+    /// Creates an object of the class named by the `Class` constant `class_index`, with every
+    /// instance field set to its default value.
+    fn new_object(&mut self, class_file: &ClassFile, frame: &mut Frame, class_index: usize) -> Result<(), JVMError> {
+        let class_name = class_file.get_name_of_class(class_index)?;
+        if class_loader::is_library_throwable(&class_name) {
+            let object = self.heap.allocate(heap::HeapObject::Object { class_name, fields: vec![OperandFrame::Null] })?;
+            frame.push_operand(OperandFrame::Reference(object));
+            return Ok(());
+        }
+        let class = self.class_loader.load_class(&class_name)?;
+        let flags = class.get_access_flags();
+        if flags.is_interface() || flags.is_abstract() {
+            return Err(JVMError::InstantiationError(class_name.replace('/', ".")));
+        }
+        self.initialize_class(&class)?;
+        let fields = self.class_loader.get_instance_fields(&class)?.iter()
+            .map(|field| descriptor::parse_field_descriptor(field.get_descriptor()).map(|field_type| OperandFrame::default_value(&field_type)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let object = self.heap.allocate(heap::HeapObject::Object { class_name, fields })?;
+        frame.push_operand(OperandFrame::Reference(object));
+        Ok(())
+    }
+
+    /// The position in the instance layout of the object on `frame` of the instance field
+    /// referenced by the `Fieldref` constant `field_index`, and the object itself.
+    fn resolve_instance_field(&mut self, class_file: &ClassFile, field_index: usize, object: OperandFrame, access: &str) -> Result<(Reference, usize), JVMError> {
+        let (class, field_position) = self.class_loader.resolve_field(class_file, field_index)?;
+        let field = &class.get_fields()[field_position];
+        let class_name = class.get_name_of_class(class.get_this_class() as usize)?;
+        let field_name = class.get_utf8(field.get_name_index() as usize)?;
+        if field.get_access_flags().is_static() {
+            return Err(JVMError::IncompatibleClassChangeError(format!("Expected non-static field {}.{}", class_name.replace('/', "."), field_name)));
+        }
+        let reference = match object {
+            OperandFrame::Reference(reference) => reference,
+            OperandFrame::Null => return Err(JVMError::NullPointerException(format!("Cannot {} field \"{}\"", access, field_name))),
+            object => return Err(JVMError::Internal(format!("Accessing the field {} of {:?} is not implemented", field_name, object))),
+        };
+        let object_class = self.class_loader.load_class(&self.heap.get_class_name(reference))?;
+        let position = self.class_loader.get_instance_fields(&object_class)?.iter()
+            .position(|field| field.get_class_name() == class_name && field.get_name() == field_name)
+            .ok_or_else(|| JVMError::Internal(format!("The field {} is not part of the layout of {}", field_name, self.heap.get_class_name(reference))))?;
+        Ok((reference, position))
+    }
+
+    /// Creates an array of the class or array type named by the `Class` constant `class_index`
+    /// (anewarray), with the length popped from `frame`.
+    fn new_reference_array(&mut self, class_file: &ClassFile, frame: &mut Frame, class_index: usize) -> Result<(), JVMError> {
+        let class_name = class_file.get_name_of_class(class_index)?;
+        self.class_loader.resolve_class(&class_name)?;
+        let length = array_module::ArrayModule::check_count(integer_module::IntegerModule::try_to_pop_integer(frame)?)?;
+        let array_class = if class_name.starts_with('[') { format!("[{}", class_name) } else { format!("[L{};", class_name) };
+        let array = self.heap.allocate_array(&array_class, length)?;
+        frame.push_operand(OperandFrame::Reference(array));
+        Ok(())
+    }
+
+    /// Creates an array of the array class named by the `Class` constant `class_index`
+    /// (multianewarray), with the lengths of its first `dimensions` dimensions popped from `frame`.
+    fn new_multi_array(&mut self, class_file: &ClassFile, frame: &mut Frame, class_index: usize, dimensions: usize) -> Result<(), JVMError> {
+        let array_class = class_file.get_name_of_class(class_index)?;
+        self.class_loader.resolve_class(&array_class)?;
+        let mut counts = (0..dimensions).map(|_| integer_module::IntegerModule::try_to_pop_integer(frame)).collect::<Result<Vec<i32>, JVMError>>()?;
+        counts.reverse();
+        let lengths = counts.into_iter().map(array_module::ArrayModule::check_count).collect::<Result<Vec<_>, _>>()?;
+        let array = self.heap.allocate_multi_array(&array_class, &lengths)?;
+        frame.push_operand(OperandFrame::Reference(array));
+        Ok(())
+    }
+
+    /// Pops a reference, an index and an array of references from `frame` and stores the reference
+    /// in the element, throwing an `ArrayStoreException` if the array cannot hold the object.
+    fn store_reference(&mut self, frame: &mut Frame) -> Result<(), JVMError> {
+        let value = frame.pop_operand()?;
+        let index = integer_module::IntegerModule::try_to_pop_integer(frame)?;
+        let array = array_module::ArrayModule::try_to_pop_array(frame, "Cannot store to object array")?;
+        let (component_type, position) = match self.heap.get(array) {
+            heap::HeapObject::ReferenceArray { component_type, elements } => {
+                (component_type.clone(), array_module::ArrayModule::check_index(index, elements.len())?)
+            }
+            object => return Err(JVMError::Internal(format!("aastore is not implemented for {:?}", object))),
+        };
+        let element = match value {
+            OperandFrame::Reference(reference) => {
+                let class_name = self.heap.get_class_name(reference);
+                if !self.class_loader.is_assignable(&class_name, &component_type)? {
+                    return Err(JVMError::ArrayStoreException(class_name.replace('/', ".")));
+                }
+                Some(reference)
+            }
+            OperandFrame::Null => None,
+            value => return Err(JVMError::Internal(format!("Storing {:?} in an array of {} is not implemented", value, component_type))),
+        };
+        if let heap::HeapObject::ReferenceArray { elements, .. } = self.heap.get_mut(array) {
+            elements[position] = element;
+        }
+        Ok(())
+    }
+
+    /// Pops an object from `frame` and pushes the value of the instance field referenced by the
+    /// `Fieldref` constant `field_index`.
+    fn get_field(&mut self, class_file: &ClassFile, frame: &mut Frame, field_index: usize) -> Result<(), JVMError> {
+        let object = frame.pop_operand()?;
+        let (reference, position) = self.resolve_instance_field(class_file, field_index, object, "read")?;
+        match self.heap.get(reference) {
+            heap::HeapObject::Object { fields, .. } => frame.push_operand(fields[position].clone()),
+            object => return Err(JVMError::Internal(format!("Reading a field of {:?} is not implemented", object))),
+        }
+        Ok(())
+    }
+
+    /// Pops a value and an object from `frame` and stores the value in the instance field
+    /// referenced by the `Fieldref` constant `field_index`.
+    fn put_field(&mut self, class_file: &ClassFile, frame: &mut Frame, field_index: usize) -> Result<(), JVMError> {
+        let value = frame.pop_operand()?;
+        let object = frame.pop_operand()?;
+        let (reference, position) = self.resolve_instance_field(class_file, field_index, object, "assign")?;
+        match self.heap.get_mut(reference) {
+            heap::HeapObject::Object { fields, .. } => fields[position] = value,
+            object => return Err(JVMError::Internal(format!("Writing a field of {:?} is not implemented", object))),
+        }
+        Ok(())
+    }
+
+    /// The class name, field name and type of the static field referenced by the `Fieldref`
+    /// constant `field_index`, after initializing the class declaring it.
+    fn resolve_static_field(&mut self, class_file: &ClassFile, field_index: usize) -> Result<(String, String, descriptor::FieldType), JVMError> {
+        let (class, field_position) = self.class_loader.resolve_field(class_file, field_index)?;
+        let field = &class.get_fields()[field_position];
+        let class_name = class.get_name_of_class(class.get_this_class() as usize)?;
+        let field_name = class.get_utf8(field.get_name_index() as usize)?;
+        if !field.get_access_flags().is_static() {
+            return Err(JVMError::IncompatibleClassChangeError(format!("Expected static field {}.{}", class_name.replace('/', "."), field_name)));
+        }
+        let field_type = descriptor::parse_field_descriptor(&class.get_utf8(field.get_descriptor_index() as usize)?)?;
+        self.initialize_class(&class)?;
+        Ok((class_name, field_name, field_type))
+    }
+
+    /// Pushes the value of the static field referenced by the `Fieldref` constant `field_index`.
+    fn get_static(&mut self, class_file: &ClassFile, frame: &mut Frame, field_index: usize) -> Result<(), JVMError> {
+        let (class_name, field_name, _field_type) = self.resolve_static_field(class_file, field_index)?;
+        let Some(value) = self.static_fields.get(&(class_name.clone(), field_name.clone())).cloned() else {
+            return Err(JVMError::Internal(format!("The static field {}.{} is not prepared", class_name, field_name)));
+        };
+        frame.push_operand(value);
+        Ok(())
+    }
+
+    /// Pops a value from `frame` and stores it in the static field referenced by the `Fieldref`
+    /// constant `field_index`.
+    fn put_static(&mut self, class_file: &ClassFile, frame: &mut Frame, field_index: usize) -> Result<(), JVMError> {
+        let value = frame.pop_operand()?;
+        let (class_name, field_name, field_type) = self.resolve_static_field(class_file, field_index)?;
+        let value = match (&field_type, value) {
+            // Only the lowest bit of an int stored in a boolean field is kept (JVMS §6.5 putstatic).
+            (descriptor::FieldType::Base(descriptor::BaseType::Boolean), OperandFrame::Int(integer)) => OperandFrame::Int(integer & 1),
+            (_field_type, value) => value,
+        };
+        self.static_fields.insert((class_name, field_name), value);
+        Ok(())
+    }
+
+    /// Initializes `class` on its first active use (JVMS §5.5): prepares its static fields, sets
+    /// those with a `ConstantValue`, initializes its superclass and the superinterfaces declaring
+    /// default methods, and runs `<clinit>`. A class whose initialization is in progress counts as
+    /// initialized, since there is only one thread. An exception thrown by the initializer is
+    /// wrapped in an `ExceptionInInitializerError`, and later uses of the class fail.
+    fn initialize_class(&mut self, class: &Rc<ClassFile>) -> Result<(), JVMError> {
+        let class_name = class.get_name_of_class(class.get_this_class() as usize)?;
+        match self.class_states.get(&class_name) {
+            Some(ClassState::InProgress | ClassState::Initialized) => return Ok(()),
+            Some(ClassState::Erroneous) => return Err(JVMError::NoClassDefFoundError(format!("Could not initialize class {}", class_name.replace('/', ".")))),
+            None => {}
+        }
+        self.class_states.insert(class_name.clone(), ClassState::InProgress);
+        let result = self.run_class_initialization(class, &class_name);
+        let state = if result.is_ok() { ClassState::Initialized } else { ClassState::Erroneous };
+        self.class_states.insert(class_name, state);
+        result
+    }
+
+    fn run_class_initialization(&mut self, class: &Rc<ClassFile>, class_name: &str) -> Result<(), JVMError> {
+        for field in class.get_fields() {
+            if !field.get_access_flags().is_static() {
+                continue;
+            }
+            let field_name = class.get_utf8(field.get_name_index() as usize)?;
+            let field_type = descriptor::parse_field_descriptor(&class.get_utf8(field.get_descriptor_index() as usize)?)?;
+            let mut value = OperandFrame::default_value(&field_type);
+            for attribute in field.get_attributes() {
+                if let attribute::Attribute::ConstantValue(constant_index) = attribute.to_attribute(class)? {
+                    value = self.constant_value(class, &field_name, constant_index as usize)?;
+                }
+            }
+            self.static_fields.insert((class_name.to_string(), field_name), value);
+        }
+        if !class.get_access_flags().is_interface() {
+            if class.get_super_class() != 0 {
+                let super_class = class.get_name_of_class(class.get_super_class() as usize)?;
+                if !class_loader::is_library_class(&super_class) {
+                    let super_class = self.class_loader.load_class(&super_class)?;
+                    self.initialize_class(&super_class)?;
+                }
+            }
+            self.initialize_default_method_interfaces(class)?;
+        }
+        let Some(position) = class.get_methods().iter().position(|method| {
+            class.get_utf8(method.get_name_index() as usize).is_ok_and(|name| name == "<clinit>")
+                && class.get_utf8(method.get_descriptor_index() as usize).is_ok_and(|descriptor| descriptor == "()V")
+        }) else {
+            return Ok(());
+        };
+        let code_attribute = find_code_attribute(class, &class.get_methods()[position])?;
+        match self.execute_frame(class, Frame::new(&code_attribute)?) {
+            Ok(_) => Ok(()),
+            Err(error) if error.is_exception() => Err(JVMError::ExceptionInInitializerError(Box::new(error))),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Initializes the superinterfaces of `class` that declare a non-abstract, non-static method,
+    /// those of each direct superinterface before itself, from left to right.
+    fn initialize_default_method_interfaces(&mut self, class: &ClassFile) -> Result<(), JVMError> {
+        for &interface_index in class.get_interfaces() {
+            let interface_name = class.get_name_of_class(interface_index as usize)?;
+            if class_loader::is_library_class(&interface_name) {
+                continue;
+            }
+            let interface = self.class_loader.load_class(&interface_name)?;
+            self.initialize_default_method_interfaces(&interface)?;
+            let declares_default_method = interface.get_methods().iter().any(|method| {
+                let flags = method.get_access_flags();
+                !flags.is_abstract() && !flags.is_static()
+            });
+            if declares_default_method {
+                self.initialize_class(&interface)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The value of the `ConstantValue` attribute (JVMS §4.7.2) of `field_name` pointing at
+    /// `constant_index`.
+    fn constant_value(&mut self, class: &ClassFile, field_name: &str, constant_index: usize) -> Result<OperandFrame, JVMError> {
+        let constant = class.get_constant(constant_index)?;
+        if let Some(value) = OperandFrame::from_numeric_constant(constant) {
+            return Ok(value);
+        }
+        match *constant {
+            const_type::ConstType::ConstantString(string_index) => Ok(OperandFrame::Reference(self.heap.intern(&class.get_utf8(string_index as usize)?)?)),
+            _ => {
+                let kind = ParseErrorKind::UnexpectedConstant { index: constant_index, expected: "numeric or string" };
+                Err(ClassFormatError::new(format!("field {}", field_name), ClassFormatErrorKind::Parse(ParseError::new(kind))).into())
+            }
+        }
+    }
+
+    fn enter_monitor(&mut self, owner: &str) {
+        *self.monitors.entry(owner.to_string()).or_insert(0) += 1;
+    }
+
+    fn exit_monitor(&mut self, owner: &str) {
+        if let Some(count) = self.monitors.get_mut(owner) {
+            *count -= 1;
+            if *count == 0 {
+                self.monitors.remove(owner);
+            }
+        }
+    }
+
+    pub fn execute_method(&mut self, class_file: &ClassFile, mut frame: Frame) -> Result<Option<OperandFrame>, JVMError> {
+        while let Some(insn) = frame.next_instruction() {
+            match self.execute_instruction(class_file, &mut frame, insn) {
+                Ok(JVMEvent::Return(return_value)) => return Ok(return_value),
+                Ok(_) => {}
+                Err(error) => self.catch_exception(class_file, &mut frame, error)?,
+            }
+        }
+        Ok(None)
+    }
+
+    /// Executes `insn` and handles the event it results in, except for `Return`, which is passed on.
+    fn execute_instruction(&mut self, class_file: &ClassFile, frame: &mut Frame, insn: Insn) -> Result<JVMEvent, JVMError> {
+        let insn = match insn {
+            Insn::Wide(modified) => *modified,
+            insn => insn,
+        };
+        let opcode = insn.get_opcode();
+        let Some(instruction) = self.instructions.get(&opcode) else {
+            return Err(JVMError::Internal(format!("The opcode {} is not implemented", opcode)));
+        };
+        if self.trace_bytecodes {
+            eprintln!("Instruction: {}({})", instruction.name, opcode);
+        }
+        match instruction.get_handler()(frame, &insn, class_file, &mut self.heap)? {
+            JVMEvent::InvokeMethod(invoke_index) => self.invoke_static(class_file, frame, invoke_index)?,
+            JVMEvent::InvokeSpecial(invoke_index) => self.invoke_special(class_file, frame, invoke_index)?,
+            JVMEvent::InvokeVirtual(invoke_index) => self.invoke_virtual(class_file, frame, invoke_index)?,
+            JVMEvent::New(class_index) => self.new_object(class_file, frame, class_index)?,
+            JVMEvent::NewArray(class_index) => self.new_reference_array(class_file, frame, class_index)?,
+            JVMEvent::NewMultiArray(class_index, dimensions) => self.new_multi_array(class_file, frame, class_index, dimensions)?,
+            JVMEvent::StoreReference => self.store_reference(frame)?,
+            JVMEvent::GetField(field_index) => self.get_field(class_file, frame, field_index)?,
+            JVMEvent::PutField(field_index) => self.put_field(class_file, frame, field_index)?,
+            JVMEvent::GetStatic(field_index) => self.get_static(class_file, frame, field_index)?,
+            JVMEvent::PutStatic(field_index) => self.put_static(class_file, frame, field_index)?,
+            JVMEvent::Throw => return Err(self.throw(frame)?),
+            event => return Ok(event),
+        }
+        Ok(JVMEvent::None)
+    }
+
+    /// The throwable popped from `frame` by athrow, ready to unwind the stack.
+    fn throw(&mut self, frame: &mut Frame) -> Result<JVMError, JVMError> {
+        let reference = match frame.pop_operand()? {
+            OperandFrame::Reference(reference) => reference,
+            OperandFrame::Null => return Ok(JVMError::NullPointerException(String::from("Cannot throw exception"))),
+            operand => return Err(JVMError::Internal(format!("Throwing {:?} is not implemented", operand))),
+        };
+        let class_name = self.heap.get_class_name(reference);
+        let message = match self.heap.get(reference) {
+            heap::HeapObject::Object { fields, .. } => match fields.first() {
+                Some(&OperandFrame::Reference(message)) => self.heap.get_string(message).map(String::from),
+                _ => None,
+            },
+            object => return Err(JVMError::Internal(format!("Throwing {:?} is not implemented", object))),
+        };
+        let is_error = self.class_loader.is_assignable(&class_name, "java/lang/Error")?;
+        Ok(JVMError::Thrown { reference, class_name, message, is_error })
+    }
+
+    /// Continues at the handler of the first exception table entry of `frame` that covers the
+    /// current instruction and catches `error` (JVMS §2.10), with the throwable as the only operand.
+    /// Without one, the method completes abruptly and `error` goes on to the invoker.
+    fn catch_exception(&mut self, class_file: &ClassFile, frame: &mut Frame, error: JVMError) -> Result<(), JVMError> {
+        if !error.is_throwable() {
+            return Err(error);
+        }
+        let class_name = error.get_class_name();
+        for entry in frame.exception_table.clone() {
+            if frame.pc < entry.get_start_pc() as usize || frame.pc >= entry.get_end_pc() as usize {
+                continue;
+            }
+            if entry.get_catch_type() != 0 {
+                let catch_type = class_file.get_name_of_class(entry.get_catch_type() as usize)?;
+                self.class_loader.resolve_class(&catch_type)?;
+                if !self.class_loader.is_assignable(&class_name, &catch_type)? {
+                    continue;
+                }
+            }
+            let throwable = self.get_throwable(error)?;
+            frame.operand_stack.clear();
+            frame.push_operand(OperandFrame::Reference(throwable));
+            frame.jump_to(entry.get_handler_pc() as i64)?;
+            return Ok(());
+        }
+        Err(error)
+    }
+
+    /// The object of the throwable `error`, created on the heap if the VM threw it.
+    fn get_throwable(&mut self, error: JVMError) -> Result<Reference, JVMError> {
+        if let JVMError::Thrown { reference, .. } = error {
+            return Ok(reference);
+        }
+        let message = match error.get_message() {
+            Some(message) => OperandFrame::Reference(self.heap.allocate(heap::HeapObject::String(message))?),
+            None => OperandFrame::Null,
+        };
+        self.heap.allocate(heap::HeapObject::Object { class_name: error.get_class_name(), fields: vec![message] })
     }
 }
 
-#[derive(Debug)]
+/// The progress of the initialization of a class (JVMS §5.5).
+enum ClassState {
+    InProgress,
+    Initialized,
+    /// The initialization failed, so the class cannot be used.
+    Erroneous,
+}
+
+#[derive(Debug, Clone)]
 pub enum OperandFrame {
     StdOut,
-    Constant(const_type::ConstType),
     Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Reference(Reference),
+    Null,
+}
+
+impl OperandFrame {
+    /// The initial value of a field of `field_type` (JVMS §2.3, §2.4).
+    pub fn default_value(field_type: &descriptor::FieldType) -> OperandFrame {
+        match field_type {
+            descriptor::FieldType::Base(descriptor::BaseType::Long) => OperandFrame::Long(0),
+            descriptor::FieldType::Base(descriptor::BaseType::Float) => OperandFrame::Float(0.0),
+            descriptor::FieldType::Base(descriptor::BaseType::Double) => OperandFrame::Double(0.0),
+            descriptor::FieldType::Base(_) => OperandFrame::Int(0),
+            descriptor::FieldType::Object(_) | descriptor::FieldType::Array(_) => OperandFrame::Null,
+        }
+    }
+
+    /// The value of an `Integer`, `Float`, `Long` or `Double` constant.
+    pub fn from_numeric_constant(constant: &const_type::ConstType) -> Option<OperandFrame> {
+        match *constant {
+            const_type::ConstType::ConstantInteger(bytes) => Some(OperandFrame::Int(bytes as i32)),
+            const_type::ConstType::ConstantFloat(bytes) => Some(OperandFrame::Float(f32::from_bits(bytes))),
+            const_type::ConstType::ConstantLong(high, low) => Some(OperandFrame::Long((((high as u64) << 32) | low as u64) as i64)),
+            const_type::ConstType::ConstantDouble(high, low) => Some(OperandFrame::Double(f64::from_bits(((high as u64) << 32) | low as u64))),
+            _ => None,
+        }
+    }
+
+    /// Whether the value takes two words, which matters to pop2 and the dup2 instructions.
+    pub fn is_category_2(&self) -> bool {
+        match self {
+            OperandFrame::Long(_) | OperandFrame::Double(_) => true,
+            OperandFrame::StdOut | OperandFrame::Int(_) | OperandFrame::Float(_) | OperandFrame::Reference(_) | OperandFrame::Null => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum LocalFrame {
     Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Reference(Reference),
+    Null,
+    /// `System.out`, which may be kept in a local variable like any reference.
+    StdOut,
     None
 }
+
+impl LocalFrame {
+    /// The local variable holding the reference `operand`.
+    pub fn from_reference(operand: OperandFrame) -> Result<LocalFrame, JVMError> {
+        match operand {
+            OperandFrame::Reference(reference) => Ok(LocalFrame::Reference(reference)),
+            OperandFrame::Null => Ok(LocalFrame::Null),
+            OperandFrame::StdOut => Ok(LocalFrame::StdOut),
+            operand => Err(JVMError::Internal(format!("The operand {:?} is no reference", operand))),
+        }
+    }
+}
 #[derive(Debug)]
 pub struct Frame {
-    code: file::File,
+    code: Vec<(usize, Insn)>,
+    position: usize,
+    pc: usize,
     operand_stack: Vec<OperandFrame>,
+    max_stack: usize,
     locale_variables: Vec<LocalFrame>,
+    exception_table: Vec<ExceptionTableEntry>,
 }
 
 impl Frame {
-    pub fn get_u1(&mut self) -> u8 {
-        self.code.get_u1()
+    pub fn new(code_attribute: &code_attribute::CodeAttribute) -> Result<Frame, ParseError> {
+        let locale_variables = vec![LocalFrame::None; code_attribute.get_max_locals() as usize];
+        Ok(Frame {
+            code: code_attribute.get_instructions()?,
+            position: 0,
+            pc: 0,
+            operand_stack: vec![],
+            max_stack: code_attribute.get_max_stack() as usize,
+            locale_variables,
+            exception_table: code_attribute.get_exception_table(),
+        })
     }
-    pub fn get_u2(&mut self) -> u16 {
-        self.code.get_u2()
+    /// Advances to the next instruction and returns it.
+    pub fn next_instruction(&mut self) -> Option<Insn> {
+        let (pc, insn) = self.code.get(self.position)?;
+        self.pc = *pc;
+        self.position += 1;
+        Some(insn.clone())
     }
     pub fn push_operand(&mut self, operand: OperandFrame) {
         self.operand_stack.push(operand);
     }
-    pub fn pop_operand(&mut self) -> OperandFrame {
-        self.operand_stack.pop().unwrap()
+    pub fn pop_operand(&mut self) -> Result<OperandFrame, JVMError> {
+        self.operand_stack.pop().ok_or_else(|| JVMError::Internal(String::from("The operand stack is empty")))
     }
-    pub fn stack_len(&self) -> usize {
-        self.operand_stack.len()
+    /// The operand `depth` entries below the top of the stack.
+    pub fn peek_operand(&self, depth: usize) -> Result<&OperandFrame, JVMError> {
+        self.operand_stack.iter().rev().nth(depth)
+            .ok_or_else(|| JVMError::Internal(format!("The operand stack has no entry {} below the top", depth)))
     }
     pub fn store_locale_variable(&mut self, index: usize, value: LocalFrame) {
         self.locale_variables[index] = value;
@@ -155,30 +832,82 @@ impl Frame {
     pub fn load_locale_variable(&mut self, index: usize) -> LocalFrame {
         self.locale_variables[index].clone()
     }
-    pub fn offset_code(&mut self, offset: isize) {
-        self.code.offset_pointer(offset);
+    /// Continues execution at `offset` bytes from the current instruction.
+    pub fn jump(&mut self, offset: i32) -> Result<(), ParseError> {
+        self.jump_to(self.pc as i64 + offset as i64)
+    }
+    /// Continues execution at the instruction at `target`.
+    fn jump_to(&mut self, target: i64) -> Result<(), ParseError> {
+        self.position = self.code.binary_search_by_key(&target, |(pc, _insn)| *pc as i64)
+            .map_err(|_e| ParseError::new(ParseErrorKind::InvalidBranchTarget { pc: self.pc, target }))?;
+        Ok(())
     }
 }
 
 pub enum JVMEvent {
     Return(Option<OperandFrame>),
     InvokeMethod(usize),
+    /// invokespecial of the `Methodref` at the index.
+    InvokeSpecial(usize),
+    /// invokevirtual or invokeinterface of the method reference at the index.
+    InvokeVirtual(usize),
+    /// Create an object of the class at the index.
+    New(usize),
+    /// Create an array of the class at the index (anewarray).
+    NewArray(usize),
+    /// Create an array of the array class at the index with the number of dimensions
+    /// (multianewarray).
+    NewMultiArray(usize, usize),
+    /// Store a reference in an array (aastore).
+    StoreReference,
+    /// Read or write the instance field referenced by the constant at the index.
+    GetField(usize),
+    PutField(usize),
+    /// Read or write the static field referenced by the constant at the index.
+    GetStatic(usize),
+    PutStatic(usize),
+    /// Throw the throwable on top of the operand stack (athrow).
+    Throw,
     None,
 }
 
+pub type InstructionHandler = fn(&mut Frame, &Insn, &ClassFile, &mut Heap) -> Result<JVMEvent, JVMError>;
+
 pub struct Instruction {
     name: String,
-    handler: fn(&mut Frame, &ClassFile) -> JVMEvent
+    handler: InstructionHandler,
 }
 
 impl Instruction {
-    pub fn get_handler(&self) -> fn(&mut Frame, &ClassFile) -> JVMEvent {
+    pub fn get_handler(&self) -> InstructionHandler {
         self.handler
     }
 }
 
+/// Pops its arguments from the invoking frame and returns the return value, if any. The last
+/// parameter holds the `-D` system properties.
+pub type NativeMethodHandler = fn(&mut Frame, &ClassFile, &mut Heap, &HashMap<String, String>) -> Result<Option<OperandFrame>, JVMError>;
+
+pub struct NativeMethod {
+    class_name: String,
+    name: String,
+    descriptor: String,
+    handler: NativeMethodHandler,
+}
+
+impl NativeMethod {
+    pub fn new(class_name: &str, name: &str, descriptor: &str, handler: NativeMethodHandler) -> NativeMethod {
+        NativeMethod { class_name: class_name.to_string(), name: name.to_string(), descriptor: descriptor.to_string(), handler }
+    }
+}
+
 pub trait InstructionModule {
     fn get_instructions(&self) -> Vec<(u8 , Instruction)>;
+
+    /// Native methods implemented by the module.
+    fn get_native_methods(&self) -> Vec<NativeMethod> {
+        vec![]
+    }
 }
 
 pub trait ContainsMethods {
@@ -190,25 +919,43 @@ pub trait ContainsAttributes {
 }
 
 
-pub fn find_methods_by_name<'a>(class_file: &ClassFile, data: Box<&'a dyn ContainsMethods>, name: Vec<u8>) -> Vec<&'a method_info::MethodInfo> {
+pub fn find_methods_by_name<'a>(class_file: &ClassFile, data: &'a dyn ContainsMethods, name: &str) -> Vec<&'a method_info::MethodInfo> {
     data.get_methods().iter().filter(|f| {
-        match &class_file.get_constant(f.get_name_index() as usize) {
-            const_type::ConstType::ConstantUtf8(bytes) => {
-                name == *bytes
-            }
-            _ => {false}
-        }
+        class_file.get_utf8(f.get_name_index() as usize).is_ok_and(|method_name| method_name == name)
     }).collect()
 }
 
 
-pub fn find_attributes_by_name<'a>(class_file: &ClassFile, data: Box<&'a dyn ContainsAttributes>, name: Vec<u8>) -> Vec<&'a attribute_info::AttributeInfo> {
-    data.get_attributes().iter().filter(|f| {
-        match &class_file.get_constant(f.get_attribute_name_index() as usize) {
-            &const_type::ConstType::ConstantUtf8(bytes) => {
-                name == *bytes
-            }
-            _ => {false}
+/// The name of the method at `method_position` of `class` as in stack traces, e.g. `pkg.C.m(I)V`.
+fn qualified_method_name(class: &ClassFile, method_position: usize) -> Result<String, ParseError> {
+    let method = &class.get_methods()[method_position];
+    Ok(format!("{}.{}{}", class.get_name_of_class(class.get_this_class() as usize)?.replace('/', "."),
+        class.get_utf8(method.get_name_index() as usize)?, class.get_utf8(method.get_descriptor_index() as usize)?))
+}
+
+/// The method the launcher starts: `public static void main(String[])`.
+pub fn find_main_method(class_file: &ClassFile) -> Option<&method_info::MethodInfo> {
+    find_methods_by_name(class_file, class_file, "main").into_iter().find(|method| {
+        let flags = method.get_access_flags();
+        flags.is_public() && flags.is_static()
+            && class_file.get_utf8(method.get_descriptor_index() as usize).is_ok_and(|descriptor| descriptor == "([Ljava/lang/String;)V")
+    })
+}
+
+/// The `Code` attribute of `method`, which every method that is neither native nor abstract has
+/// exactly one of (JVMS §4.7.3).
+fn find_code_attribute(class_file: &ClassFile, method: &method_info::MethodInfo) -> Result<code_attribute::CodeAttribute, JVMError> {
+    match find_attributes_by_name(class_file, method, "Code")[..] {
+        [code_attribute] => Ok(code_attribute.to_code_attribute()?),
+        ref code_attributes => {
+            let location = format!("method {}{}", class_file.get_utf8(method.get_name_index() as usize)?, class_file.get_utf8(method.get_descriptor_index() as usize)?);
+            Err(ClassFormatError::new(location, ClassFormatErrorKind::CodeAttributeCount { expected: 1, found: code_attributes.len() }).into())
         }
+    }
+}
+
+pub fn find_attributes_by_name<'a>(class_file: &ClassFile, data: &'a dyn ContainsAttributes, name: &str) -> Vec<&'a attribute_info::AttributeInfo> {
+    data.get_attributes().iter().filter(|f| {
+        class_file.get_utf8(f.get_attribute_name_index() as usize).is_ok_and(|attribute_name| attribute_name == name)
     }).collect()
-}
\ No newline at end of file
+}
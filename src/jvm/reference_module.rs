@@ -0,0 +1,200 @@
+use super::class_loader;
+use super::heap::{Heap, HeapObject};
+use super::{InstructionModule, JVMError, LocalFrame, OperandFrame};
+
+pub struct ReferenceModule;
+
+impl ReferenceModule {
+    pub fn load_to_stack(frame: &mut super::Frame, index: usize) -> Result<(), JVMError> {
+        let operand = match frame.load_locale_variable(index) {
+            LocalFrame::Reference(reference) => OperandFrame::Reference(reference),
+            LocalFrame::Null => OperandFrame::Null,
+            LocalFrame::StdOut => OperandFrame::StdOut,
+            local_value => return Err(JVMError::Internal(format!("The local variable {:?} is no reference", local_value))),
+        };
+        frame.push_operand(operand);
+        Ok(())
+    }
+    pub fn store_to_local(frame: &mut super::Frame, index: usize) -> Result<(), JVMError> {
+        let operand = frame.pop_operand()?;
+        frame.store_locale_variable(index, LocalFrame::from_reference(operand)?);
+        Ok(())
+    }
+    /// Whether the two references are the same object, as if_acmpeq compares them.
+    pub fn is_same(param1: &OperandFrame, param2: &OperandFrame) -> bool {
+        match (param1, param2) {
+            (OperandFrame::Reference(reference1), OperandFrame::Reference(reference2)) => reference1 == reference2,
+            (OperandFrame::Null, OperandFrame::Null) | (OperandFrame::StdOut, OperandFrame::StdOut) => true,
+            _ => false,
+        }
+    }
+    /// Sets `Throwable.detailMessage`, the first field of the throwable `object`.
+    fn set_detail_message(heap: &mut Heap, object: OperandFrame, message: OperandFrame) -> Result<(), JVMError> {
+        match object {
+            OperandFrame::Reference(reference) => match heap.get_mut(reference) {
+                HeapObject::Object { fields, .. } if !fields.is_empty() => {
+                    fields[0] = message;
+                    Ok(())
+                }
+                object => Err(JVMError::Internal(format!("The object {:?} is no throwable", object))),
+            },
+            object => Err(JVMError::Internal(format!("The operand {:?} is no throwable", object))),
+        }
+    }
+    /// Whether the top operand is null, popping it.
+    pub fn pop_is_null(frame: &mut super::Frame) -> Result<bool, JVMError> {
+        match frame.pop_operand()? {
+            OperandFrame::Null => Ok(true),
+            OperandFrame::Reference(_) | OperandFrame::StdOut => Ok(false),
+            operand => Err(JVMError::Internal(format!("The operand {:?} is no reference", operand))),
+        }
+    }
+}
+
+impl InstructionModule for ReferenceModule {
+    fn get_instructions(&self) -> Vec<(u8 , super::Instruction)> {
+        let mut instructions = vec![(25, super::Instruction {
+            name: String::from("aload"),
+            handler: |frame, insn, _class_file, _heap| {
+                ReferenceModule::load_to_stack(frame, insn.get_local_index().unwrap_or_default() as usize)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (58, super::Instruction {
+            name: String::from("astore"),
+            handler: |frame, insn, _class_file, _heap| {
+                ReferenceModule::store_to_local(frame, insn.get_local_index().unwrap_or_default() as usize)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (1, super::Instruction {
+            name: String::from("aconst_null"),
+            handler: |frame, _insn, _class_file, _heap| {
+                frame.push_operand(OperandFrame::Null);
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (176, super::Instruction {
+            name: String::from("areturn"),
+            handler: |frame, _insn, _class_file, _heap| {
+                Ok(super::JVMEvent::Return(Some(frame.pop_operand()?)))
+            }
+        }),
+        (198, super::Instruction {
+            name: String::from("ifnull"),
+            handler: |frame, insn, _class_file, _heap| {
+                if ReferenceModule::pop_is_null(frame)? {
+                    frame.jump(insn.get_branch_offset().unwrap_or_default())?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (199, super::Instruction {
+            name: String::from("ifnonnull"),
+            handler: |frame, insn, _class_file, _heap| {
+                if !ReferenceModule::pop_is_null(frame)? {
+                    frame.jump(insn.get_branch_offset().unwrap_or_default())?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (165, super::Instruction {
+            name: String::from("if_acmpeq"),
+            handler: |frame, insn, _class_file, _heap| {
+                let param1 = frame.pop_operand()?;
+                let param2 = frame.pop_operand()?;
+                if ReferenceModule::is_same(&param1, &param2) {
+                    frame.jump(insn.get_branch_offset().unwrap_or_default())?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (166, super::Instruction {
+            name: String::from("if_acmpne"),
+            handler: |frame, insn, _class_file, _heap| {
+                let param1 = frame.pop_operand()?;
+                let param2 = frame.pop_operand()?;
+                if !ReferenceModule::is_same(&param1, &param2) {
+                    frame.jump(insn.get_branch_offset().unwrap_or_default())?;
+                }
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (187, super::Instruction {
+            name: String::from("new"),
+            handler: |_frame, insn, _class_file, _heap| {
+                Ok(super::JVMEvent::New(insn.get_constant_index().unwrap_or_default() as usize))
+            }
+        }),
+        (180, super::Instruction {
+            name: String::from("getfield"),
+            handler: |_frame, insn, _class_file, _heap| {
+                Ok(super::JVMEvent::GetField(insn.get_constant_index().unwrap_or_default() as usize))
+            }
+        }),
+        (181, super::Instruction {
+            name: String::from("putfield"),
+            handler: |_frame, insn, _class_file, _heap| {
+                Ok(super::JVMEvent::PutField(insn.get_constant_index().unwrap_or_default() as usize))
+            }
+        }),
+        (183, super::Instruction {
+            name: String::from("invokespecial"),
+            handler: |_frame, insn, _class_file, _heap| {
+                Ok(super::JVMEvent::InvokeSpecial(insn.get_constant_index().unwrap_or_default() as usize))
+            }
+        }),
+        (185, super::Instruction {
+            name: String::from("invokeinterface"),
+            handler: |_frame, insn, _class_file, _heap| {
+                Ok(super::JVMEvent::InvokeVirtual(insn.get_constant_index().unwrap_or_default() as usize))
+            }
+        }),
+        (191, super::Instruction {
+            name: String::from("athrow"),
+            handler: |_frame, _insn, _class_file, _heap| {
+                Ok(super::JVMEvent::Throw)
+            }
+        })];
+        // aload_<n> and astore_<n> only differ from aload and astore in where the index comes from.
+        let (aload, astore) = (instructions[0].1.get_handler(), instructions[1].1.get_handler());
+        for (opcode, name) in [(42, "aload_n(n=0)"), (43, "aload_n(n=1)"), (44, "aload_n(n=2)"), (45, "aload_n(n=3)")] {
+            instructions.push((opcode, super::Instruction { name: String::from(name), handler: aload }));
+        }
+        for (opcode, name) in [(75, "astore_n(n=0)"), (76, "astore_n(n=1)"), (77, "astore_n(n=2)"), (78, "astore_n(n=3)")] {
+            instructions.push((opcode, super::Instruction { name: String::from(name), handler: astore }));
+        }
+        instructions
+    }
+
+    fn get_native_methods(&self) -> Vec<super::NativeMethod> {
+        // Object has no state to initialize, so its constructor only consumes the object.
+        let mut native_methods = vec![super::NativeMethod::new("java/lang/Object", "<init>", "()V", |frame, _class_file, _heap, _system_properties| {
+            frame.pop_operand()?;
+            Ok(None)
+        })];
+        // Constructors are not inherited, so every library throwable gets its own.
+        for (class_name, _super_class) in class_loader::LIBRARY_THROWABLES {
+            native_methods.push(super::NativeMethod::new(class_name, "<init>", "()V", |frame, _class_file, _heap, _system_properties| {
+                frame.pop_operand()?;
+                Ok(None)
+            }));
+            native_methods.push(super::NativeMethod::new(class_name, "<init>", "(Ljava/lang/String;)V", |frame, _class_file, heap, _system_properties| {
+                let message = frame.pop_operand()?;
+                ReferenceModule::set_detail_message(heap, frame.pop_operand()?, message)?;
+                Ok(None)
+            }));
+        }
+        native_methods.push(super::NativeMethod::new("java/lang/Throwable", "getMessage", "()Ljava/lang/String;", |frame, _class_file, heap, _system_properties| {
+            match frame.pop_operand()? {
+                OperandFrame::Reference(reference) => match heap.get(reference) {
+                    HeapObject::Object { fields, .. } => Ok(fields.first().cloned()),
+                    object => Err(JVMError::Internal(format!("The object {:?} is no throwable", object))),
+                },
+                OperandFrame::Null => Err(JVMError::NullPointerException(String::from("Cannot invoke \"java.lang.Throwable.getMessage()\""))),
+                operand => Err(JVMError::Internal(format!("The operand {:?} is no throwable", operand))),
+            }
+        }));
+        native_methods
+    }
+}
@@ -0,0 +1,99 @@
+use super::{Frame, InstructionModule, JVMError, OperandFrame};
+
+/// The operand stack instructions, which work on words: a long or double takes two words and
+/// every other value one (JVMS §2.11.1).
+pub struct StackModule;
+
+impl StackModule {
+    /// Pops values until they take at least `words` words, and returns them bottom first.
+    pub fn pop_words(frame: &mut Frame, words: usize) -> Result<Vec<OperandFrame>, JVMError> {
+        let mut values = vec![];
+        let mut popped = 0;
+        while popped < words {
+            let value = frame.pop_operand()?;
+            popped += if value.is_category_2() { 2 } else { 1 };
+            values.insert(0, value);
+        }
+        Ok(values)
+    }
+    /// Duplicates the top `words` words and inserts the copy `depth` words further down.
+    pub fn duplicate(frame: &mut Frame, words: usize, depth: usize) -> Result<(), JVMError> {
+        let top = StackModule::pop_words(frame, words)?;
+        let below = StackModule::pop_words(frame, depth)?;
+        for value in top.iter().chain(below.iter()).chain(top.iter()) {
+            frame.push_operand(value.clone());
+        }
+        Ok(())
+    }
+}
+
+impl InstructionModule for StackModule {
+    fn get_instructions(&self) -> Vec<(u8 , super::Instruction)> {
+        vec![(87, super::Instruction {
+            name: String::from("pop"),
+            handler: |frame, _insn, _class_file, _heap| {
+                StackModule::pop_words(frame, 1)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (88, super::Instruction {
+            name: String::from("pop2"),
+            handler: |frame, _insn, _class_file, _heap| {
+                StackModule::pop_words(frame, 2)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (89, super::Instruction {
+            name: String::from("dup"),
+            handler: |frame, _insn, _class_file, _heap| {
+                StackModule::duplicate(frame, 1, 0)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (90, super::Instruction {
+            name: String::from("dup_x1"),
+            handler: |frame, _insn, _class_file, _heap| {
+                StackModule::duplicate(frame, 1, 1)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (91, super::Instruction {
+            name: String::from("dup_x2"),
+            handler: |frame, _insn, _class_file, _heap| {
+                StackModule::duplicate(frame, 1, 2)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (92, super::Instruction {
+            name: String::from("dup2"),
+            handler: |frame, _insn, _class_file, _heap| {
+                StackModule::duplicate(frame, 2, 0)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (93, super::Instruction {
+            name: String::from("dup2_x1"),
+            handler: |frame, _insn, _class_file, _heap| {
+                StackModule::duplicate(frame, 2, 1)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (94, super::Instruction {
+            name: String::from("dup2_x2"),
+            handler: |frame, _insn, _class_file, _heap| {
+                StackModule::duplicate(frame, 2, 2)?;
+                Ok(super::JVMEvent::None)
+            }
+        }),
+        (95, super::Instruction {
+            name: String::from("swap"),
+            handler: |frame, _insn, _class_file, _heap| {
+                let param1 = frame.pop_operand()?;
+                let param2 = frame.pop_operand()?;
+                frame.push_operand(param1);
+                frame.push_operand(param2);
+                Ok(super::JVMEvent::None)
+            }
+        }),]
+    }
+}
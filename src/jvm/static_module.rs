@@ -1,108 +1,183 @@
-use super::{InstructionModule, OperandFrame};
-use crate::class_file::const_type;
+use std::collections::HashMap;
+use super::heap::{HeapObject, PrimitiveArray};
+use super::{Frame, Heap, InstructionModule, JVMError, JVMEvent, NativeMethod, OperandFrame};
+use crate::class_file::{const_type, ClassFile};
+use crate::class_file::instruction::Insn;
+use crate::util::number_format;
 
 pub struct StaticModule;
 
+impl StaticModule {
+    /// The value of the system property `key` as a new `String`, or `default` if it is not set.
+    fn get_property(heap: &mut Heap, system_properties: &HashMap<String, String>, key: OperandFrame, default: OperandFrame) -> Result<OperandFrame, JVMError> {
+        let key = match key {
+            OperandFrame::Reference(reference) => heap.get_string(reference)
+                .ok_or_else(|| JVMError::Internal(format!("The property key {:?} is no string", heap.get(reference))))?,
+            OperandFrame::Null => return Err(JVMError::NullPointerException(String::from("key can't be null"))),
+            key => return Err(JVMError::Internal(format!("The property key {:?} is no reference", key))),
+        };
+        if key.is_empty() {
+            return Err(JVMError::IllegalArgumentException(String::from("key can't be empty")));
+        }
+        match system_properties.get(key) {
+            Some(value) => Ok(OperandFrame::Reference(heap.allocate(HeapObject::String(value.clone()))?)),
+            None => Ok(default),
+        }
+    }
+
+    /// Prints the argument of `PrintStream.println` on standard output the way the overload with
+    /// `descriptor` formats it, e.g. an int of `(Z)V` as `true` or `false`.
+    fn println(frame: &mut Frame, heap: &Heap, descriptor: &str) -> Result<(), JVMError> {
+        let line = match descriptor {
+            "()V" => String::new(),
+            _ => {
+                let argument = frame.pop_operand()?;
+                Self::format_argument(heap, descriptor, argument)?
+            }
+        };
+        match frame.pop_operand()? {
+            OperandFrame::StdOut => println!("{}", line),
+            stream => return Err(JVMError::Internal(format!("PrintStream.println on {:?} is not implemented", stream))),
+        }
+        Ok(())
+    }
+
+    /// The text `PrintStream.println` with `descriptor` prints for `argument`.
+    fn format_argument(heap: &Heap, descriptor: &str, argument: OperandFrame) -> Result<String, JVMError> {
+        match (descriptor, argument) {
+            ("(Z)V", OperandFrame::Int(boolean)) => Ok((boolean != 0).to_string()),
+            // A lone surrogate is unmappable and printed as '?', as by the default charset.
+            ("(C)V", OperandFrame::Int(char)) => Ok(char::decode_utf16([char as u16]).map(|char| char.unwrap_or('?')).collect()),
+            ("(I)V", OperandFrame::Int(integer)) => Ok(integer.to_string()),
+            ("(J)V", OperandFrame::Long(long)) => Ok(long.to_string()),
+            ("(F)V", OperandFrame::Float(float)) => Ok(number_format::float_to_string(float)),
+            ("(D)V", OperandFrame::Double(double)) => Ok(number_format::double_to_string(double)),
+            ("([C)V", OperandFrame::Null) => Err(JVMError::NullPointerException(String::from("Cannot read the array length because \"s\" is null"))),
+            ("([C)V", OperandFrame::Reference(reference)) => match heap.get(reference) {
+                HeapObject::PrimitiveArray(PrimitiveArray::Char(chars)) => Ok(char::decode_utf16(chars.iter().copied()).map(|char| char.unwrap_or('?')).collect()),
+                object => Err(JVMError::Internal(format!("The argument {:?} is no char[]", object))),
+            },
+            ("(Ljava/lang/String;)V" | "(Ljava/lang/Object;)V", OperandFrame::Null) => Ok(String::from("null")),
+            ("(Ljava/lang/String;)V" | "(Ljava/lang/Object;)V", OperandFrame::Reference(reference)) => match heap.get_string(reference) {
+                Some(string) => Ok(string.to_string()),
+                // Anything else would be printed with its toString method.
+                None => Err(JVMError::Internal(format!("PrintStream.println of a {} is not implemented", heap.get_class_name(reference)))),
+            },
+            (descriptor, argument) => Err(JVMError::Internal(format!("PrintStream.println{} of {:?} is not implemented", descriptor, argument))),
+        }
+    }
+
+    /// Pushes the constant of ldc or ldc_w.
+    fn load_constant(frame: &mut Frame, insn: &Insn, class_file: &ClassFile, heap: &mut Heap) -> Result<JVMEvent, JVMError> {
+        let constant = class_file.get_constant(insn.get_constant_index().unwrap_or_default() as usize)?;
+        match (constant, OperandFrame::from_numeric_constant(constant)) {
+            (_constant, Some(value)) => frame.push_operand(value),
+            // String literals are interned, so that equal literals are the same object.
+            (&const_type::ConstType::ConstantString(string_index), None) => {
+                let reference = heap.intern(&class_file.get_utf8(string_index as usize)?)?;
+                frame.push_operand(OperandFrame::Reference(reference));
+            }
+            (constant, None) => return Err(JVMError::Internal(format!("ldc of {:?} is not implemented", constant))),
+        }
+        Ok(JVMEvent::None)
+    }
+}
+
 impl InstructionModule for StaticModule {
     fn get_instructions(&self) -> Vec<(u8 , super::Instruction)> {
        vec![(178, super::Instruction {
            name: String::from("get_static"),
-           handler: |frame, class_file| {
-               let static_index = frame.get_u2() as usize;
-               let field_ref = class_file.get_constant(static_index);
+           handler: |frame, insn, class_file, _heap| {
+               let static_index = insn.get_constant_index().unwrap_or_default() as usize;
+               let field_ref = class_file.get_constant(static_index)?;
                match field_ref {
                    &const_type::ConstType::ConstantFieldref(class_index, name_and_type_index) => {
-                       let class_name = class_file.get_name_of_class(class_index as usize);
-                       let name_of_member = class_file.get_name_of_member(name_and_type_index as usize);
-                       if class_name == String::from("java/lang/System") && name_of_member == String::from("out") {
+                       let class_name = class_file.get_name_of_class(class_index as usize)?;
+                       let name_of_member = class_file.get_name_of_member(name_and_type_index as usize)?;
+                       if class_name == "java/lang/System" && name_of_member == "out" {
                            frame.push_operand(OperandFrame::StdOut);
                        } else {
-                           panic!("The Classname({})/Membername({}) is not supported for instruction get_static!", class_name, name_of_member);
+                           return Ok(super::JVMEvent::GetStatic(static_index));
                        }
                    }
-                   _ => {panic!("Get static is not implemented for {:?}", field_ref)}
+                   _ => return Err(JVMError::Internal(format!("getstatic of {:?} is not implemented", field_ref))),
                }
-               super::JVMEvent::None
+               Ok(super::JVMEvent::None)
+           }
+       }),
+       (179, super::Instruction {
+           name: String::from("put_static"),
+           handler: |_frame, insn, _class_file, _heap| {
+               Ok(super::JVMEvent::PutStatic(insn.get_constant_index().unwrap_or_default() as usize))
            }
        }),
        (18, super::Instruction {
            name: String::from("ldc"),
-           handler: |frame, class_file| {
-               let constant_index = frame.get_u1() as usize;
-               frame.push_operand(OperandFrame::Constant(class_file.get_constant(constant_index).clone()));
-               super::JVMEvent::None
-           }
+           handler: |frame, insn, class_file, heap| StaticModule::load_constant(frame, insn, class_file, heap),
+       }),
+       (19, super::Instruction {
+           name: String::from("ldc_w"),
+           handler: |frame, insn, class_file, heap| StaticModule::load_constant(frame, insn, class_file, heap),
        }),
        (182, super::Instruction {
            name: String::from("invokevirtual"),
-           handler: |frame, class_file| {
-               let invoke_index = frame.get_u2() as usize;
-               let method_ref = class_file.get_constant(invoke_index);
+           handler: |frame, insn, class_file, heap| {
+               let invoke_index = insn.get_constant_index().unwrap_or_default() as usize;
+               let method_ref = class_file.get_constant(invoke_index)?;
                match method_ref {
                    &const_type::ConstType::ConstantMethodref(class_index, name_and_type_index) => {
-                       let class_name = class_file.get_name_of_class(class_index as usize);
-                       let name_of_member = class_file.get_name_of_member(name_and_type_index as usize);
-                       if class_name == String::from("java/io/PrintStream") && name_of_member == String::from("println") {
-                           if frame.stack_len() < 2 {
-                               panic!("The Classname({})/Membername({}) expects 2 arguments, but provided {}!", class_name, name_of_member, frame.stack_len());
-                           }
-                           let param1 = frame.pop_operand();
-                           let param2 = frame.pop_operand();
-                           match param2 {
-                               OperandFrame::StdOut => {}
-                               _ => {
-                                   panic!("Unsupported stream type {:?}!", param2);
-                               }
-                           }
-                           match param1 {
-                               OperandFrame::Constant(constant) => {
-                                   match constant {
-                                       const_type::ConstType::ConstantString(string_index) => {
-                                           let utf8_const = &class_file.get_constant(string_index as usize);
-                                           match utf8_const {
-                                               const_type::ConstType::ConstantUtf8(bytes)  => {
-                                                   println!("{}", String::from_utf8(bytes.clone()).unwrap());
-                                               }
-                                               _ => {panic!("String constant is not pointing to a utf8 constant!")}
-                                           }
-                                       }
-                                       _ => {panic!("Println for {:?} is not implemented for that constant type!", constant)}
-                                   }
-                               }
-                               OperandFrame::Int(integer) => {
-                                   println!("{}", integer);
-                               }
-                               _ => {panic!("Println for {:?} is not implemented for that operand type!", param1)}
-                           }
+                       let class_name = class_file.get_name_of_class(class_index as usize)?;
+                       let name_of_member = class_file.get_name_of_member(name_and_type_index as usize)?;
+                       if class_name == "java/io/PrintStream" && name_of_member == "println" {
+                           let descriptor = class_file.get_description_of_member(name_and_type_index as usize)?;
+                           StaticModule::println(frame, heap, &descriptor)?;
                        } else {
-                           panic!("The Classname({})/Membername({}) is not supported for instruction invokevirtual!", class_name, name_of_member);
+                           return Ok(super::JVMEvent::InvokeVirtual(invoke_index));
                        }
                    }
-                   _=> {panic!("The instruction invokevirtual is not implemented for {:?}", method_ref)}
+                   method_ref => return Err(JVMError::Internal(format!("invokevirtual of {:?} is not implemented", method_ref))),
                }
-               super::JVMEvent::None
+               Ok(super::JVMEvent::None)
            }
        }),
        (177, super::Instruction {
            name: String::from("return (void)"),
-           handler: |_frame, _class_file| {
-               super::JVMEvent::Return(None)
+           handler: |_frame, _insn, _class_file, _heap| {
+               Ok(super::JVMEvent::Return(None))
            }
        }),
        (167, super::Instruction {
            name: String::from("goto"),
-           handler: |frame, _class_file| {
-               let offset = frame.get_u2() as i16;
-               frame.offset_code((offset-3) as isize);
-               super::JVMEvent::None
+           handler: |frame, insn, _class_file, _heap| {
+               frame.jump(insn.get_branch_offset().unwrap_or_default())?;
+               Ok(super::JVMEvent::None)
            }
        }),
        (184, super::Instruction {
            name: String::from("invokestatic"),
-           handler: |frame, _class_file| {
-               let index = frame.get_u2() as i16;
-               super::JVMEvent::InvokeMethod(index as usize)
+           handler: |_frame, insn, _class_file, _heap| {
+               Ok(super::JVMEvent::InvokeMethod(insn.get_constant_index().unwrap_or_default() as usize))
            }
        }),]
     }
-}
\ No newline at end of file
+
+    fn get_native_methods(&self) -> Vec<NativeMethod> {
+        vec![
+            NativeMethod::new("java/lang/System", "exit", "(I)V", |frame, _class_file, _heap, _system_properties| {
+                match frame.pop_operand()? {
+                    OperandFrame::Int(status) => Err(JVMError::Exit(status)),
+                    status => Err(JVMError::Internal(format!("The exit status {:?} is no int", status))),
+                }
+            }),
+            NativeMethod::new("java/lang/System", "getProperty", "(Ljava/lang/String;)Ljava/lang/String;", |frame, _class_file, heap, system_properties| {
+                let key = frame.pop_operand()?;
+                Self::get_property(heap, system_properties, key, OperandFrame::Null).map(Some)
+            }),
+            NativeMethod::new("java/lang/System", "getProperty", "(Ljava/lang/String;Ljava/lang/String;)Ljava/lang/String;", |frame, _class_file, heap, system_properties| {
+                let default = frame.pop_operand()?;
+                let key = frame.pop_operand()?;
+                Self::get_property(heap, system_properties, key, default).map(Some)
+            }),
+        ]
+    }
+}
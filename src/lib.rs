@@ -1,34 +1,270 @@
-mod util;
-mod class_file;
+pub mod util;
+pub mod class_file;
+pub mod disasm;
+pub mod verifier;
 mod jvm;
+use std::collections::HashMap;
+use std::env;
 use std::error::Error;
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
 use util::file;
 
-pub fn run (config: Config) -> Result<(), Box<dyn Error>>{
-    let class_binary = fs::read(config.filename)?;
-    let class_file = class_file::parse_file(&mut file::File::new(class_binary));
-    let main_method = jvm::find_methods_by_name(&class_file, Box::new(&class_file), "main".as_bytes().to_vec())[0];
-    let mut jvm = jvm::JVM::new()
+/// Runs the command and returns the exit status of the VM.
+pub fn run (config: Config) -> Result<i32, Box<dyn Error>>{
+    match config.mode {
+        Mode::Run | Mode::RunJar => return run_main_thread(config),
+        Mode::Version => {
+            eprintln!("rust_jvm version \"{}\"", env!("CARGO_PKG_VERSION"));
+            eprintln!("class file versions {}.0 to {}.0 (Java SE {})", class_file::validate::MIN_MAJOR_VERSION,
+                class_file::validate::MAX_MAJOR_VERSION, jvm::jar::JAVA_RELEASE);
+            return Ok(0);
+        }
+        _ => {}
+    }
+    let class_binary = fs::read(&config.filename)?;
+    let class_file = class_file::parse_file(&mut file::File::new(class_binary.clone()))?;
+    if let Mode::Disassemble { show_private } = config.mode {
+        let options = disasm::Options { show_private };
+        print!("{}", disasm::disassemble(&config.filename, &class_binary, &class_file, &options)?);
+        return Ok(0);
+    }
+    let errors = class_file.validate();
+    for error in &errors {
+        println!("{}", error);
+    }
+    if !errors.is_empty() {
+        return Err(format!("{} class format errors in {}", errors.len(), config.filename).into());
+    }
+    Ok(0)
+}
+
+/// Native stack the interpreter may need per byte of Java thread stack, with frames as small as
+/// they get: no locals and no operands.
+const NATIVE_STACK_PER_BYTE: usize = 512;
+/// The most native stack the main thread gets, which limits the Java thread stack to 4 MiB.
+const MAX_NATIVE_STACK_SIZE: usize = 2 << 30;
+
+/// Like the java launcher, runs `main` in a new thread. Every Java frame takes a few kilobytes of
+/// native stack in the interpreter, so the thread gets enough to reach the `-Xss` limit first. A
+/// larger `-Xss` than the native stack allows is lowered to it.
+fn run_main_thread(mut config: Config) -> Result<i32, Box<dyn Error>> {
+    let thread_stack_size = config.thread_stack_size.unwrap_or(jvm::DEFAULT_THREAD_STACK_SIZE)
+        .min(MAX_NATIVE_STACK_SIZE / NATIVE_STACK_PER_BYTE);
+    config.thread_stack_size = Some(thread_stack_size);
+    let main_thread = thread::Builder::new()
+        .name(String::from("main"))
+        .stack_size(thread_stack_size * NATIVE_STACK_PER_BYTE)
+        .spawn(move || run_main_class(config).map_err(|error| error.to_string()))?;
+    Ok(main_thread.join().map_err(|_e| "the main thread panicked")??)
+}
+
+/// Loads the main class, from the named class file, as binary name from the class path or as
+/// `Main-Class` of the JAR, and runs its `main` method. An uncaught exception exits with status 1,
+/// a failure of the VM is returned as error.
+fn run_main_class(config: Config) -> Result<i32, Box<dyn Error>> {
+    let mut class_loader = jvm::class_loader::ClassLoader::new(config.class_path)?.with_verbose(config.verbose_class);
+    let main_class = if let Mode::RunJar = config.mode {
+        let path = Path::new(&config.filename);
+        if !path.is_file() {
+            return Err(format!("Unable to access jarfile {}", config.filename).into());
+        }
+        let main_class = jvm::jar::Jar::open(path)?.get_main_class()
+            .ok_or_else(|| format!("no main manifest attribute, in {}", config.filename))?;
+        class_loader.load_class(&main_class)
+    } else if config.filename.ends_with(".class") {
+        class_loader.define_class(fs::read(&config.filename)?)
+    } else {
+        class_loader.load_class(&config.filename.replace('.', "/"))
+    };
+    let class_file = main_class.map_err(|error| format!("Could not find or load main class {}\nCaused by: {}", config.filename, error))?;
+    let main_method = jvm::find_main_method(&class_file).ok_or_else(|| {
+        let class_name = class_file.get_name_of_class(class_file.get_this_class() as usize).unwrap_or_default().replace('/', ".");
+        format!("Main method not found in class {}, please define the main method as:\n   public static void main(String[] args)", class_name)
+    })?;
+    let mut jvm = jvm::JVM::new(class_loader)
+    .with_thread_stack_size(config.thread_stack_size.unwrap_or(jvm::DEFAULT_THREAD_STACK_SIZE))
+    .with_max_heap_size(config.max_heap_size.unwrap_or(jvm::DEFAULT_MAX_HEAP_SIZE))
+    .with_system_properties(config.system_properties)
+    .with_trace_bytecodes(config.trace_bytecodes)
     .register(Box::new(jvm::static_module::StaticModule{}))
-    .register(Box::new(jvm::integer_module::IntegerModule {}));
-    jvm.execute_main_method(&class_file, main_method);
-    Ok(())
+    .register(Box::new(jvm::integer_module::IntegerModule {}))
+    .register(Box::new(jvm::long_module::LongModule {}))
+    .register(Box::new(jvm::float_module::FloatModule {}))
+    .register(Box::new(jvm::double_module::DoubleModule {}))
+    .register(Box::new(jvm::conversion_module::ConversionModule {}))
+    .register(Box::new(jvm::reference_module::ReferenceModule {}))
+    .register(Box::new(jvm::stack_module::StackModule {}))
+    .register(Box::new(jvm::array_module::ArrayModule {}));
+    match jvm.execute_main_method(&class_file, main_method, &config.args) {
+        Ok(()) => Ok(0),
+        Err(jvm::JVMError::Exit(status)) => Ok(status),
+        Err(error) if error.is_throwable() => {
+            eprintln!("Exception in thread \"main\" {}", error);
+            Ok(1)
+        }
+        // Not an exception of the program, but a class file or instruction the VM cannot handle.
+        Err(error) => Err(format!("VM failure in thread \"main\": {}", error).into()),
+    }
 }
+
+#[derive(Default)]
+pub enum Mode {
+    /// `[options] <main class> [args...]`: run the `main` method of a class file, or of a class on
+    /// the class path given by binary name.
+    #[default]
+    Run,
+    /// `[options] -jar <file> [args...]`: run the `main` method of the `Main-Class` named in the
+    /// manifest of a JAR file.
+    RunJar,
+    /// `-version`: print the version of the VM.
+    Version,
+    /// `disasm [-p] <file>`: print the class file like `javap -c -v`.
+    Disassemble { show_private: bool },
+    /// `check <file>`: print every violation of the class file format checks.
+    Check,
+}
+
+#[derive(Default)]
 pub struct Config {
-    pub filename: String
+    pub filename: String,
+    pub mode: Mode,
+    /// Directories and JAR files searched for classes. Defaults to the directory of the main class
+    /// file, or the current directory if the main class is given by name. With `-jar`, it is the
+    /// JAR file alone.
+    pub class_path: Vec<PathBuf>,
+    /// `-D<name>=<value>`
+    pub system_properties: HashMap<String, String>,
+    /// `-Xss<size>`, in bytes.
+    pub thread_stack_size: Option<usize>,
+    /// `-Xmx<size>`, in bytes.
+    pub max_heap_size: Option<usize>,
+    /// `-Xms<size>`, in bytes. The heap grows on demand, so it only matters as a lower bound of the
+    /// maximum heap size.
+    pub initial_heap_size: Option<usize>,
+    /// `-verbose:class`
+    pub verbose_class: bool,
+    /// `-XX:+TraceBytecodes`
+    pub trace_bytecodes: bool,
+    /// The arguments following the main class or JAR file, passed to `main`.
+    pub args: Vec<String>,
 }
 
 impl Config {
-    pub fn new(args: &[String]) -> Result<Config, &'static str> {
+    pub fn new(args: &[String]) -> Result<Config, String> {
         if args.len() < 2 {
-            return Err("not enough arguments");
+            return Err("not enough arguments".to_string());
         }
 
-        let filename = args[1].clone();
+        if args[1] == "disasm" {
+            let mut show_private = false;
+            let mut filename = None;
+            for arg in &args[2..] {
+                match arg.as_str() {
+                    "-p" | "-private" => show_private = true,
+                    _ if filename.is_none() => filename = Some(arg.clone()),
+                    _ => return Err("too many arguments".to_string()),
+                }
+            }
+            let filename = filename.ok_or("missing class file")?;
+            return Ok(Config {
+                filename,
+                mode: Mode::Disassemble { show_private },
+                ..Config::default()
+            });
+        }
+
+        if args[1] == "check" {
+            let filename = args.get(2).ok_or("missing class file")?.clone();
+            if args.len() > 3 {
+                return Err("too many arguments".to_string());
+            }
+            return Ok(Config {
+                filename,
+                mode: Mode::Check,
+                ..Config::default()
+            });
+        }
 
-        return Ok(Config {
-            filename,
+        // Options come first; the main class or JAR file ends them and is followed by the program
+        // arguments.
+        let mut config = Config::default();
+        let mut class_path = None;
+        let mut filename = None;
+        let mut args = args[1..].iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-version" => config.mode = Mode::Version,
+                "-jar" => {
+                    filename = Some(args.next().ok_or("missing jar file")?.clone());
+                    if !matches!(config.mode, Mode::Version) {
+                        config.mode = Mode::RunJar;
+                    }
+                    break;
+                }
+                "-cp" | "-classpath" | "--classpath" | "--class-path" => {
+                    let paths = args.next().ok_or("missing class path")?;
+                    class_path = Some(env::split_paths(paths).collect());
+                }
+                "-verbose:class" => config.verbose_class = true,
+                "-XX:+TraceBytecodes" => config.trace_bytecodes = true,
+                _ if arg.starts_with("--class-path=") => class_path = Some(env::split_paths(&arg["--class-path=".len()..]).collect()),
+                _ if arg.starts_with("-D") => {
+                    let (name, value) = arg[2..].split_once('=').unwrap_or((&arg[2..], ""));
+                    if name.is_empty() {
+                        return Err(format!("invalid system property: {}", arg));
+                    }
+                    config.system_properties.insert(name.to_string(), value.to_string());
+                }
+                _ if arg.starts_with("-Xss") => config.thread_stack_size = Some(parse_size(&arg[4..]).ok_or(format!("Invalid thread stack size: {}", arg))?),
+                _ if arg.starts_with("-Xmx") => config.max_heap_size = Some(parse_size(&arg[4..]).ok_or(format!("Invalid maximum heap size: {}", arg))?),
+                _ if arg.starts_with("-Xms") => config.initial_heap_size = Some(parse_size(&arg[4..]).ok_or(format!("Invalid initial heap size: {}", arg))?),
+                _ if arg.starts_with('-') => return Err(format!("Unrecognized option: {}", arg)),
+                _ => {
+                    filename = Some(arg.clone());
+                    break;
+                }
+            }
+        }
+        config.args = args.cloned().collect();
+        // As in HotSpot, -Xms raises the default maximum heap size, but not one given by -Xmx.
+        match (config.initial_heap_size, config.max_heap_size) {
+            (Some(initial_heap_size), Some(max_heap_size)) if initial_heap_size > max_heap_size => {
+                return Err("Initial heap size set to a larger value than the maximum heap size".to_string());
+            }
+            (Some(initial_heap_size), None) if initial_heap_size > jvm::DEFAULT_MAX_HEAP_SIZE => config.max_heap_size = Some(initial_heap_size),
+            _ => {}
+        }
+        match config.mode {
+            Mode::Version => return Ok(config),
+            Mode::RunJar => {
+                // The class path of a JAR comes from its manifest, so -cp is ignored.
+                config.filename = filename.ok_or("missing jar file")?;
+                config.class_path = vec![PathBuf::from(&config.filename)];
+                return Ok(config);
+            }
+            _ => {}
+        }
+        let filename: String = filename.ok_or("missing main class")?;
+        config.class_path = class_path.unwrap_or_else(|| {
+            match Path::new(&filename).parent() {
+                Some(directory) if filename.ends_with(".class") => vec![directory.to_path_buf()],
+                _ => vec![PathBuf::from(".")],
+            }
         });
+        config.filename = filename;
+        Ok(config)
     }
-}
\ No newline at end of file
+}
+
+/// Parses a `-X` size: a byte count, optionally followed by `k`, `m` or `g`.
+fn parse_size(size: &str) -> Option<usize> {
+    let (digits, unit) = match size.char_indices().last()? {
+        (position, 'k' | 'K') => (&size[..position], 1 << 10),
+        (position, 'm' | 'M') => (&size[..position], 1 << 20),
+        (position, 'g' | 'G') => (&size[..position], 1 << 30),
+        _ => (size, 1),
+    };
+    digits.parse::<usize>().ok().filter(|&size| size > 0)?.checked_mul(unit)
+}
@@ -10,8 +10,11 @@ fn main() {
         process::exit(1);
     });
 
-    if let Err(e) = rust_jvm::run(config) {
-        eprintln!("Application error: {}", e);
-        process::exit(1);
+    match rust_jvm::run(config) {
+        Ok(status) => process::exit(status),
+        Err(e) => {
+            eprintln!("Application error: {}", e);
+            process::exit(1);
+        }
     }
 }
@@ -1,12 +1,21 @@
+use crate::class_file::parse_error::{ParseError, ParseErrorKind};
+
 #[derive(Debug)]
 pub struct File {
     data: Vec<u8>,
     pointer: isize,
+    base: usize,
 }
 
 impl File {
     pub fn new(data: Vec<u8>) -> File {
-        File { data, pointer: 0}
+        File { data, pointer: 0, base: 0 }
+    }
+
+    /// Creates a file over `data` that was itself read at offset `base` of an enclosing file,
+    /// so errors report offsets relative to the enclosing file.
+    pub fn new_at(data: Vec<u8>, base: usize) -> File {
+        File { data, pointer: 0, base }
     }
 }
 
@@ -15,40 +24,81 @@ impl File {
         self.pointer += offset;
     }
 
-    #[allow(dead_code)]
-    pub fn get_pointer(&mut self) -> usize {
-        self.pointer as usize
+    /// Absolute offset of the next byte to be read.
+    pub fn get_pointer(&self) -> usize {
+        self.base + self.pointer as usize
     }
 
-    pub fn get_u1(&mut self) -> u8 {
-        let out = self.data[self.pointer as usize];
-        self.pointer += 1;
-        out
+    fn take(&mut self, length: usize) -> Result<&[u8], ParseError> {
+        let start = self.pointer as usize;
+        let available = self.data.len().saturating_sub(start);
+        if self.pointer < 0 || available < length {
+            return Err(ParseError::at(self.get_pointer(), ParseErrorKind::UnexpectedEof { needed: length, available }));
+        }
+        self.pointer += length as isize;
+        Ok(&self.data[start..start + length])
     }
 
-    pub fn get_u2(&mut self) -> u16 {
-        let out = ((self.data[self.pointer as usize] as u16) << 8) + ((self.data[(self.pointer + 1)  as usize] as u16));
-        self.pointer += 2;
-        out
+    pub fn get_u1(&mut self) -> Result<u8, ParseError> {
+        Ok(self.take(1)?[0])
     }
 
-    pub fn get_u4(&mut self) -> u32 {
-        let out = ((self.data[self.pointer  as usize] as u32) << 24) + ((self.data[(self.pointer + 1)  as usize] as u32) << 16) + ((self.data[(self.pointer + 2)  as usize] as u32) << 8) + ((self.data[(self.pointer + 3) as usize] as u32));
-        self.pointer += 4;
-        out
+    pub fn get_u2(&mut self) -> Result<u16, ParseError> {
+        let bytes = self.take(2)?;
+        Ok(((bytes[0] as u16) << 8) + (bytes[1] as u16))
     }
 
-    pub fn get_range(&mut self, range: isize) -> Vec<u8> {
-        let out = self.data[(self.pointer) as usize..(self.pointer+range) as usize].to_vec();
-        self.pointer += range;
+    pub fn get_u4(&mut self) -> Result<u32, ParseError> {
+        let bytes = self.take(4)?;
+        Ok(((bytes[0] as u32) << 24) + ((bytes[1] as u32) << 16) + ((bytes[2] as u32) << 8) + (bytes[3] as u32))
+    }
+
+    pub fn get_range(&mut self, range: usize) -> Result<Vec<u8>, ParseError> {
+        Ok(self.take(range)?.to_vec())
+    }
+
+    pub fn get_range_u2(&mut self, range: usize) -> Result<Vec<u16>, ParseError> {
+        (0..range).map(|_f| {self.get_u2()}).collect()
+    }
+
+    pub fn get_remaining(&mut self) -> Vec<u8> {
+        let out = self.data[self.pointer as usize..].to_vec();
+        self.pointer = self.data.len() as isize;
         out
     }
 
-    pub fn get_range_u2(&mut self, range: usize) -> Vec<u16> {
-        (0..range).into_iter().map(|_f| {self.get_u2()}).collect()
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.pointer as usize)
     }
 
-    pub fn has_next(&mut self) -> bool {
+    pub fn has_next(&self) -> bool {
         (self.pointer as usize) < self.data.len()
     }
-}
\ No newline at end of file
+
+    pub fn put_u1(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    pub fn put_u2(&mut self, value: u16) {
+        self.data.extend_from_slice(&value.to_be_bytes());
+    }
+
+    pub fn put_u4(&mut self, value: u32) {
+        self.data.extend_from_slice(&value.to_be_bytes());
+    }
+
+    pub fn put_range(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    pub fn put_range_u2(&mut self, values: &[u16]) {
+        for value in values {
+            self.put_u2(*value);
+        }
+    }
+
+    /// Consumes the file, returning everything read from or written to it.
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
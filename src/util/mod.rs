@@ -1 +1,5 @@
-pub mod file;
\ No newline at end of file
+pub mod file;
+pub mod inflate;
+pub mod number_format;
+pub mod sha256;
+pub mod zip;
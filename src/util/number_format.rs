@@ -0,0 +1,78 @@
+//! Formats floating point numbers the way `Float.toString` and `Double.toString` do: the shortest
+//! digits that uniquely identify the value, in plain notation for magnitudes in [10^-3, 10^7) and
+//! in computerized scientific notation (`1.0E10`) otherwise.
+
+/// Java prints at least two significant digits, so if a single digit identifies the value, the
+/// two-digit decimal closest to it is used instead, e.g. `4.9E-324` rather than `5.0E-324`.
+/// `shortest` and `two_digits` are the value in Rust's `{:e}` and `{:.1e}` formats.
+fn layout(negative: bool, shortest: &str, two_digits: &str) -> String {
+    let scientific = if shortest.contains('.') { shortest } else { two_digits };
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let exponent: i32 = exponent.parse().unwrap();
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let digits = match digits.trim_end_matches('0') {
+        "" => "0",
+        digits => digits,
+    };
+    let mut out = String::new();
+    if negative {
+        out.push('-');
+    }
+    if (-3..7).contains(&exponent) {
+        if exponent < 0 {
+            out.push_str("0.");
+            for _i in 0..(-exponent - 1) {
+                out.push('0');
+            }
+            out.push_str(digits);
+        } else {
+            let integer_digits = exponent as usize + 1;
+            if digits.len() > integer_digits {
+                out.push_str(&digits[..integer_digits]);
+                out.push('.');
+                out.push_str(&digits[integer_digits..]);
+            } else {
+                out.push_str(digits);
+                for _i in digits.len()..integer_digits {
+                    out.push('0');
+                }
+                out.push_str(".0");
+            }
+        }
+    } else {
+        out.push_str(&digits[..1]);
+        out.push('.');
+        if digits.len() > 1 {
+            out.push_str(&digits[1..]);
+        } else {
+            out.push('0');
+        }
+        out.push('E');
+        out.push_str(&exponent.to_string());
+    }
+    out
+}
+
+pub fn double_to_string(value: f64) -> String {
+    if value.is_nan() {
+        String::from("NaN")
+    } else if value.is_infinite() {
+        String::from(if value > 0.0 { "Infinity" } else { "-Infinity" })
+    } else if value == 0.0 {
+        String::from(if value.is_sign_negative() { "-0.0" } else { "0.0" })
+    } else {
+        layout(value < 0.0, &format!("{:e}", value.abs()), &format!("{:.1e}", value.abs()))
+    }
+}
+
+pub fn float_to_string(value: f32) -> String {
+    if value.is_nan() {
+        String::from("NaN")
+    } else if value.is_infinite() {
+        String::from(if value > 0.0 { "Infinity" } else { "-Infinity" })
+    } else if value == 0.0 {
+        String::from(if value.is_sign_negative() { "-0.0" } else { "0.0" })
+    } else {
+        layout(value < 0.0, &format!("{:e}", value.abs()), &format!("{:.1e}", value.abs()))
+    }
+}
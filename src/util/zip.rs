@@ -0,0 +1,200 @@
+//! A ZIP archive reader (PKWARE APPNOTE 6.3) for JAR files. Entries are found through the central
+//! directory and may be stored or deflated.
+
+use std::collections::HashMap;
+use std::fmt;
+use super::inflate::{self, InflateError};
+
+const LOCAL_FILE_HEADER_SIGNATURE: u32 = 0x04034b50;
+const CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x02014b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06054b50;
+const END_OF_CENTRAL_DIRECTORY_SIZE: usize = 22;
+const LOCAL_FILE_HEADER_SIZE: usize = 30;
+const CENTRAL_DIRECTORY_HEADER_SIZE: usize = 46;
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZipError {
+    MissingEndOfCentralDirectory,
+    UnexpectedEof(usize),
+    InvalidSignature { offset: usize, expected: u32 },
+    /// Archives larger than 4 GiB or with more than 65535 entries need ZIP64 records.
+    Zip64Unsupported,
+    UnsupportedMethod { name: String, method: u16 },
+    Inflate { name: String, error: InflateError },
+    SizeMismatch { name: String },
+    CrcMismatch { name: String },
+}
+
+impl fmt::Display for ZipError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ZipError::MissingEndOfCentralDirectory => write!(f, "zip END header not found"),
+            ZipError::UnexpectedEof(offset) => write!(f, "unexpected end of archive at offset {}", offset),
+            ZipError::InvalidSignature { offset, expected } => write!(f, "expected signature {:#010x} at offset {}", expected, offset),
+            ZipError::Zip64Unsupported => write!(f, "ZIP64 archives are not supported"),
+            ZipError::UnsupportedMethod { name, method } => write!(f, "{}: unsupported compression method {}", name, method),
+            ZipError::Inflate { name, error } => write!(f, "{}: {}", name, error),
+            ZipError::SizeMismatch { name } => write!(f, "{}: invalid entry size", name),
+            ZipError::CrcMismatch { name } => write!(f, "{}: invalid entry CRC", name),
+        }
+    }
+}
+
+impl std::error::Error for ZipError {}
+
+#[derive(Debug, Clone)]
+pub struct ZipEntry {
+    name: String,
+    method: u16,
+    crc32: u32,
+    compressed_size: u32,
+    uncompressed_size: u32,
+    local_header_offset: u32,
+}
+
+impl ZipEntry {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn get_method(&self) -> u16 {
+        self.method
+    }
+    pub fn get_crc32(&self) -> u32 {
+        self.crc32
+    }
+    pub fn get_compressed_size(&self) -> u32 {
+        self.compressed_size
+    }
+    pub fn get_uncompressed_size(&self) -> u32 {
+        self.uncompressed_size
+    }
+    pub fn is_directory(&self) -> bool {
+        self.name.ends_with('/')
+    }
+}
+
+pub struct ZipArchive {
+    data: Vec<u8>,
+    entries: Vec<ZipEntry>,
+    /// Positions of the entries, keyed by name.
+    names: HashMap<String, usize>,
+}
+
+impl ZipArchive {
+    /// Reads the central directory of the archive; entries are decompressed on access.
+    pub fn new(data: Vec<u8>) -> Result<ZipArchive, ZipError> {
+        let end = find_end_of_central_directory(&data)?;
+        let entry_count = read_u16(&data, end + 10)? as usize;
+        let directory_size = read_u32(&data, end + 12)?;
+        let directory_offset = read_u32(&data, end + 16)?;
+        if entry_count == 0xffff || directory_size == 0xffffffff || directory_offset == 0xffffffff {
+            return Err(ZipError::Zip64Unsupported);
+        }
+
+        let mut entries = Vec::with_capacity(entry_count);
+        let mut names = HashMap::new();
+        let mut offset = directory_offset as usize;
+        for _ in 0..entry_count {
+            expect_signature(&data, offset, CENTRAL_DIRECTORY_SIGNATURE)?;
+            let name_length = read_u16(&data, offset + 28)? as usize;
+            let extra_length = read_u16(&data, offset + 30)? as usize;
+            let comment_length = read_u16(&data, offset + 32)? as usize;
+            let name_start = offset + CENTRAL_DIRECTORY_HEADER_SIZE;
+            let name = data.get(name_start..name_start + name_length).ok_or(ZipError::UnexpectedEof(name_start))?;
+            let entry = ZipEntry {
+                name: String::from_utf8_lossy(name).into_owned(),
+                method: read_u16(&data, offset + 10)?,
+                crc32: read_u32(&data, offset + 16)?,
+                compressed_size: read_u32(&data, offset + 20)?,
+                uncompressed_size: read_u32(&data, offset + 24)?,
+                local_header_offset: read_u32(&data, offset + 42)?,
+            };
+            // The first of several entries with the same name wins, as in java.util.zip.
+            names.entry(entry.name.clone()).or_insert(entries.len());
+            entries.push(entry);
+            offset = name_start + name_length + extra_length + comment_length;
+        }
+        Ok(ZipArchive { data, entries, names })
+    }
+
+    pub fn get_entries(&self) -> &Vec<ZipEntry> {
+        &self.entries
+    }
+
+    pub fn get_entry(&self, name: &str) -> Option<&ZipEntry> {
+        self.names.get(name).map(|&position| &self.entries[position])
+    }
+
+    /// The decompressed contents of the entry `name`, or `None` if the archive has no such entry.
+    pub fn read(&self, name: &str) -> Result<Option<Vec<u8>>, ZipError> {
+        match self.get_entry(name) {
+            Some(entry) => self.read_entry(entry).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn read_entry(&self, entry: &ZipEntry) -> Result<Vec<u8>, ZipError> {
+        let offset = entry.local_header_offset as usize;
+        expect_signature(&self.data, offset, LOCAL_FILE_HEADER_SIGNATURE)?;
+        // The sizes in the local header may be zero if they follow the data in a data descriptor,
+        // so only the lengths of the name and extra field are taken from it.
+        let name_length = read_u16(&self.data, offset + 26)? as usize;
+        let extra_length = read_u16(&self.data, offset + 28)? as usize;
+        let start = offset + LOCAL_FILE_HEADER_SIZE + name_length + extra_length;
+        let compressed = self.data.get(start..start + entry.compressed_size as usize).ok_or(ZipError::UnexpectedEof(start))?;
+        let contents = match entry.method {
+            METHOD_STORED => compressed.to_vec(),
+            METHOD_DEFLATED => inflate::inflate(compressed).map_err(|error| ZipError::Inflate { name: entry.name.clone(), error })?,
+            method => return Err(ZipError::UnsupportedMethod { name: entry.name.clone(), method }),
+        };
+        if contents.len() != entry.uncompressed_size as usize {
+            return Err(ZipError::SizeMismatch { name: entry.name.clone() });
+        }
+        if crc32(&contents) != entry.crc32 {
+            return Err(ZipError::CrcMismatch { name: entry.name.clone() });
+        }
+        Ok(contents)
+    }
+}
+
+/// The end of central directory record is the last one in the archive, followed only by a
+/// comment of at most 65535 bytes.
+fn find_end_of_central_directory(data: &[u8]) -> Result<usize, ZipError> {
+    let last = data.len().checked_sub(END_OF_CENTRAL_DIRECTORY_SIZE).ok_or(ZipError::MissingEndOfCentralDirectory)?;
+    let first = last.saturating_sub(0xffff);
+    (first..=last).rev()
+        .find(|&offset| read_u32(data, offset) == Ok(END_OF_CENTRAL_DIRECTORY_SIGNATURE)
+            && read_u16(data, offset + 20).is_ok_and(|comment_length| offset + END_OF_CENTRAL_DIRECTORY_SIZE + comment_length as usize == data.len()))
+        .ok_or(ZipError::MissingEndOfCentralDirectory)
+}
+
+fn expect_signature(data: &[u8], offset: usize, expected: u32) -> Result<(), ZipError> {
+    if read_u32(data, offset)? != expected {
+        return Err(ZipError::InvalidSignature { offset, expected });
+    }
+    Ok(())
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, ZipError> {
+    let bytes = data.get(offset..offset + 2).ok_or(ZipError::UnexpectedEof(offset))?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, ZipError> {
+    let bytes = data.get(offset..offset + 4).ok_or(ZipError::UnexpectedEof(offset))?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// CRC-32 with the polynomial 0xedb88320, as used by ZIP.
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffffffffu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb88320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
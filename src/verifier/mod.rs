@@ -0,0 +1,318 @@
+//! Bytecode verification (JVMS §4.10). Class files of version 50 and later are verified by type
+//! checking against their `StackMapTable` attributes (§4.10.1); older class files have no stack
+//! maps, so the types are inferred by data-flow analysis instead (§4.10.2).
+
+pub mod verify_error;
+mod transfer;
+mod types;
+
+use std::collections::HashMap;
+use crate::class_file::ClassFile;
+use crate::class_file::attribute::{Attribute, StackMapFrame, VerificationType};
+use crate::class_file::descriptor;
+use crate::class_file::exception_table_entry::ExceptionTableEntry;
+use crate::class_file::method_info::MethodInfo;
+use crate::class_file::parse_error::ParseError;
+use transfer::{Control, Method};
+use types::{Hierarchy, TypeFrame, VType};
+use verify_error::{VerifyError, VerifyErrorKind};
+
+/// The first class file version verified by type checking.
+const TYPE_CHECKING_VERSION: u16 = 50;
+
+/// Verifies every method of `class_file` that has code.
+pub fn verify_class(class_file: &ClassFile) -> Result<(), VerifyError> {
+    let this_class = class_file.get_name_of_class(class_file.get_this_class() as usize)
+        .map_err(|e| VerifyError::new("", "", None, e.into()))?;
+    let super_class = match class_file.get_super_class() {
+        0 => None,
+        super_class => Some(class_file.get_name_of_class(super_class as usize).map_err(|e| VerifyError::new(&this_class, "", None, e.into()))?),
+    };
+    for method_info in class_file.get_methods() {
+        let hierarchy = Hierarchy { this_class: this_class.clone(), super_class: super_class.clone() };
+        verify_method(class_file, method_info, hierarchy)?;
+    }
+    Ok(())
+}
+
+fn verify_method(class_file: &ClassFile, method_info: &MethodInfo, hierarchy: Hierarchy) -> Result<(), VerifyError> {
+    let name = class_file.get_utf8(method_info.get_name_index() as usize);
+    let method_descriptor = class_file.get_utf8(method_info.get_descriptor_index() as usize);
+    let (name, method_descriptor) = match (name, method_descriptor) {
+        (Ok(name), Ok(method_descriptor)) => (name, method_descriptor),
+        (Err(e), _) | (_, Err(e)) => return Err(VerifyError::new(&hierarchy.this_class, "", None, e.into())),
+    };
+    let method_name = format!("{}{}", name, method_descriptor);
+    let fail = |kind: VerifyErrorKind| VerifyError::new(&hierarchy.this_class, &method_name, None, kind);
+
+    let mut code_attribute = None;
+    for attribute_info in method_info.get_attributes() {
+        if class_file.get_utf8(attribute_info.get_attribute_name_index() as usize).map_err(|e| fail(e.into()))? == "Code" {
+            code_attribute = Some(attribute_info.to_code_attribute().map_err(|e| fail(e.into()))?);
+        }
+    }
+    let code_attribute = match code_attribute {
+        Some(code_attribute) => code_attribute,
+        None => return Ok(()),
+    };
+    let instructions = code_attribute.get_instructions().map_err(|e| fail(e.into()))?;
+    let method_descriptor = descriptor::parse_method_descriptor(&method_descriptor).map_err(|e| fail(e.into()))?;
+
+    let is_constructor = name == "<init>";
+    let mut locals = vec![];
+    if !method_info.get_access_flags().is_static() {
+        if is_constructor && hierarchy.this_class != "java/lang/Object" {
+            locals.push(VType::UninitializedThis);
+        } else {
+            locals.push(VType::Reference(hierarchy.this_class.clone()));
+        }
+    }
+    locals.extend(method_descriptor.get_parameters().iter().map(VType::from_field_type));
+
+    let method = Method {
+        class_file,
+        hierarchy,
+        name: method_name.clone(),
+        instructions,
+        return_type: method_descriptor.get_return_type().map(VType::from_field_type),
+        is_constructor,
+        max_stack: code_attribute.get_max_stack() as usize,
+        max_locals: code_attribute.get_max_locals() as usize,
+    };
+    let initial = TypeFrame { locals: expand_locals(&method, &locals).map_err(|kind| method.error(None, kind))?, stack: vec![] };
+    if method.instructions.is_empty() {
+        return Err(method.error(None, VerifyErrorKind::FallsOffEnd));
+    }
+    let exception_table = code_attribute.get_exception_table();
+
+    if class_file.get_major_version() >= TYPE_CHECKING_VERSION {
+        let mut stack_map = vec![];
+        for attribute_info in code_attribute.get_attributes() {
+            if let Attribute::StackMapTable(frames) = attribute_info.to_attribute(class_file).map_err(|e| method.error(None, e.into()))? {
+                stack_map = frames;
+            }
+        }
+        let frames = decode_stack_map(&method, &locals, &stack_map).map_err(|kind| method.error(None, kind))?;
+        type_check(&method, initial, &frames, &exception_table)
+    } else {
+        infer_types(&method, initial, &exception_table)
+    }
+}
+
+/// Locals as listed in a stack map frame, one entry per value, expanded to one entry per slot and
+/// padded with `Top` to `max_locals`.
+fn expand_locals(method: &Method, locals: &[VType]) -> Result<Vec<VType>, VerifyErrorKind> {
+    let mut expanded = vec![];
+    for value in locals {
+        expanded.push(value.clone());
+        if value.get_size() == 2 {
+            expanded.push(VType::Top);
+        }
+    }
+    if expanded.len() > method.max_locals {
+        return Err(VerifyErrorKind::LocalOutOfRange { index: expanded.len() - 1, max_locals: method.max_locals as u16 });
+    }
+    expanded.resize(method.max_locals, VType::Top);
+    Ok(expanded)
+}
+
+fn to_vtype(class_file: &ClassFile, verification_type: &VerificationType) -> Result<VType, ParseError> {
+    Ok(match verification_type {
+        VerificationType::Top => VType::Top,
+        VerificationType::Integer => VType::Int,
+        VerificationType::Float => VType::Float,
+        VerificationType::Double => VType::Double,
+        VerificationType::Long => VType::Long,
+        VerificationType::Null => VType::Null,
+        VerificationType::UninitializedThis => VType::UninitializedThis,
+        VerificationType::Object(class_index) => VType::Reference(class_file.get_name_of_class(*class_index as usize)?),
+        VerificationType::Uninitialized(offset) => VType::Uninitialized(*offset as usize),
+    })
+}
+
+/// The frames of the `StackMapTable` by pc (JVMS §4.7.4). Each frame is relative to the previous
+/// one, the first to the frame built from the method descriptor, whose `locals` are given.
+fn decode_stack_map(method: &Method, locals: &[VType], stack_map: &[StackMapFrame]) -> Result<HashMap<usize, TypeFrame>, VerifyErrorKind> {
+    let convert = |verification_types: &[VerificationType]| -> Result<Vec<VType>, VerifyErrorKind> {
+        verification_types.iter().map(|verification_type| Ok(to_vtype(method.class_file, verification_type)?)).collect()
+    };
+    let mut frames = HashMap::new();
+    let mut locals = locals.to_vec();
+    let mut pc: Option<usize> = None;
+    for stack_map_frame in stack_map {
+        let offset_delta = stack_map_frame.get_offset_delta() as usize;
+        let frame_pc = pc.map_or(offset_delta, |pc| pc + offset_delta + 1);
+        let stack = match stack_map_frame {
+            StackMapFrame::Same { .. } | StackMapFrame::SameExtended { .. } => vec![],
+            StackMapFrame::SameLocals1StackItem { stack, .. } | StackMapFrame::SameLocals1StackItemExtended { stack, .. } => convert(std::slice::from_ref(stack))?,
+            StackMapFrame::Chop { chopped, .. } => {
+                locals.truncate(locals.len().saturating_sub(*chopped as usize));
+                vec![]
+            }
+            StackMapFrame::Append { locals: appended, .. } => {
+                locals.extend(convert(appended)?);
+                vec![]
+            }
+            StackMapFrame::Full { locals: full, stack, .. } => {
+                locals = convert(full)?;
+                convert(stack)?
+            }
+        };
+        if method.find_instruction(frame_pc as i64).is_none() {
+            return Err(VerifyErrorKind::InvalidStackMapFrame(frame_pc));
+        }
+        let frame = TypeFrame { locals: expand_locals(method, &locals)?, stack };
+        if frame.get_stack_size() > method.max_stack {
+            return Err(VerifyErrorKind::StackOverflow { max_stack: method.max_stack as u16 });
+        }
+        frames.insert(frame_pc, frame);
+        pc = Some(frame_pc);
+    }
+    Ok(frames)
+}
+
+/// The frame an exception handler starts with when an exception is thrown with the locals of `frame`.
+fn handler_frame(method: &Method, frame: &TypeFrame, entry: &ExceptionTableEntry) -> Result<TypeFrame, VerifyErrorKind> {
+    let catch_type = match entry.get_catch_type() {
+        0 => "java/lang/Throwable".to_string(),
+        catch_type => method.class_file.get_name_of_class(catch_type as usize)?,
+    };
+    Ok(TypeFrame { locals: frame.locals.clone(), stack: vec![VType::Reference(catch_type)] })
+}
+
+fn covers(entry: &ExceptionTableEntry, pc: usize) -> bool {
+    entry.get_start_pc() as usize <= pc && pc < entry.get_end_pc() as usize
+}
+
+/// The type checker (JVMS §4.10.1): one linear pass, in which the frame reaching every branch
+/// target, exception handler and instruction following an unconditional jump must be assignable
+/// to the stack map frame recorded for it.
+fn type_check(method: &Method, initial: TypeFrame, frames: &HashMap<usize, TypeFrame>, exception_table: &[ExceptionTableEntry]) -> Result<(), VerifyError> {
+    let check_target = |frame: &TypeFrame, target: i64| -> Result<(), VerifyErrorKind> {
+        if method.find_instruction(target).is_none() {
+            return Err(VerifyErrorKind::InvalidBranchTarget(target));
+        }
+        let target_frame = frames.get(&(target as usize)).ok_or(VerifyErrorKind::MissingStackMapFrame)?;
+        if !frame.is_assignable(target_frame, &method.hierarchy) {
+            return Err(VerifyErrorKind::IncompatibleFrame { expected: target_frame.to_string(), actual: frame.to_string() });
+        }
+        Ok(())
+    };
+    let check_handlers = |frame: &TypeFrame, pc: usize| -> Result<(), VerifyErrorKind> {
+        for entry in exception_table.iter().filter(|entry| covers(entry, pc)) {
+            check_target(&handler_frame(method, frame, entry)?, entry.get_handler_pc() as i64)?;
+        }
+        Ok(())
+    };
+
+    let mut frame = initial;
+    let mut reachable = true;
+    for (index, (pc, insn)) in method.instructions.iter().enumerate() {
+        let error = |kind| method.error(Some(*pc), kind);
+        match frames.get(pc) {
+            Some(stack_map_frame) => {
+                if reachable && !frame.is_assignable(stack_map_frame, &method.hierarchy) {
+                    return Err(error(VerifyErrorKind::IncompatibleFrame { expected: stack_map_frame.to_string(), actual: frame.to_string() }));
+                }
+                frame = stack_map_frame.clone();
+            }
+            None if !reachable => return Err(error(VerifyErrorKind::MissingStackMapFrame)),
+            None => {}
+        }
+        check_handlers(&frame, *pc).map_err(error)?;
+        let next_pc = method.instructions.get(index + 1).map_or(usize::MAX, |(next_pc, _insn)| *next_pc);
+        let control = method.execute(&mut frame, *pc, insn, next_pc).map_err(error)?;
+        check_handlers(&frame, *pc).map_err(error)?;
+        reachable = match control {
+            Control::Next => true,
+            Control::Branch(targets) => {
+                targets.iter().try_for_each(|target| check_target(&frame, *target)).map_err(error)?;
+                true
+            }
+            Control::Jump(targets) => {
+                targets.iter().try_for_each(|target| check_target(&frame, *target)).map_err(error)?;
+                false
+            }
+            Control::Stop => false,
+            Control::Jsr(_) | Control::Ret(_) => return Err(error(VerifyErrorKind::IllegalInstruction(insn.get_mnemonic()))),
+        };
+        if reachable && index + 1 == method.instructions.len() {
+            return Err(error(VerifyErrorKind::FallsOffEnd));
+        }
+    }
+    Ok(())
+}
+
+/// Merges `frame` into the frame known for the instruction at `index`, queueing the instruction
+/// again if that frame changed.
+fn merge_into(method: &Method, states: &mut [Option<TypeFrame>], worklist: &mut Vec<usize>, index: usize, frame: &TypeFrame) -> Result<(), VerifyErrorKind> {
+    let merged = match &states[index] {
+        None => frame.clone(),
+        Some(old) => {
+            let inconsistent = || VerifyErrorKind::InconsistentStack { first: old.to_string(), second: frame.to_string() };
+            if old.stack.len() != frame.stack.len() {
+                return Err(inconsistent());
+            }
+            let mut stack = vec![];
+            for (first, second) in old.stack.iter().zip(&frame.stack) {
+                match method.hierarchy.merge(first, second) {
+                    VType::Top => return Err(inconsistent()),
+                    value => stack.push(value),
+                }
+            }
+            let locals = old.locals.iter().zip(&frame.locals).map(|(first, second)| method.hierarchy.merge(first, second)).collect();
+            let merged = TypeFrame { locals, stack };
+            if merged == *old {
+                return Ok(());
+            }
+            merged
+        }
+    };
+    states[index] = Some(merged);
+    if !worklist.contains(&index) {
+        worklist.push(index);
+    }
+    Ok(())
+}
+
+/// The type inference (JVMS §4.10.2.2): frames are merged at every instruction that can be reached
+/// on several paths until nothing changes. Subroutines are not analysed separately; `ret` continues
+/// at every instruction following a `jsr` to the subroutine, with the merged frame.
+fn infer_types(method: &Method, initial: TypeFrame, exception_table: &[ExceptionTableEntry]) -> Result<(), VerifyError> {
+    let mut states = vec![None; method.instructions.len()];
+    states[0] = Some(initial);
+    let mut worklist = vec![0];
+    while let Some(index) = worklist.pop() {
+        let (pc, insn) = &method.instructions[index];
+        let error = |kind| method.error(Some(*pc), kind);
+        let mut frame = states[index].clone().unwrap();
+        let target_index = |target: i64| method.find_instruction(target).ok_or(VerifyErrorKind::InvalidBranchTarget(target));
+        let next_index = || if index + 1 < method.instructions.len() { Ok(index + 1) } else { Err(VerifyErrorKind::FallsOffEnd) };
+
+        for entry in exception_table.iter().filter(|entry| covers(entry, *pc)) {
+            let handler = handler_frame(method, &frame, entry).map_err(error)?;
+            let handler_index = target_index(entry.get_handler_pc() as i64).map_err(error)?;
+            merge_into(method, &mut states, &mut worklist, handler_index, &handler).map_err(error)?;
+        }
+        let next_pc = method.instructions.get(index + 1).map_or(usize::MAX, |(next_pc, _insn)| *next_pc);
+        let control = method.execute(&mut frame, *pc, insn, next_pc).map_err(error)?;
+        for entry in exception_table.iter().filter(|entry| covers(entry, *pc)) {
+            let handler = handler_frame(method, &frame, entry).map_err(error)?;
+            let handler_index = target_index(entry.get_handler_pc() as i64).map_err(error)?;
+            merge_into(method, &mut states, &mut worklist, handler_index, &handler).map_err(error)?;
+        }
+
+        let successors = match control {
+            Control::Next => vec![next_index()],
+            Control::Branch(targets) => targets.into_iter().map(target_index).chain(std::iter::once(next_index())).collect(),
+            Control::Jump(targets) => targets.into_iter().map(target_index).collect(),
+            Control::Jsr(target) => vec![target_index(target)],
+            Control::Ret(pcs) => pcs.into_iter().map(|pc| target_index(pc as i64)).collect(),
+            Control::Stop => vec![],
+        };
+        for successor in successors {
+            merge_into(method, &mut states, &mut worklist, successor.map_err(error)?, &frame).map_err(error)?;
+        }
+    }
+    Ok(())
+}
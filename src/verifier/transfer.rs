@@ -0,0 +1,532 @@
+//! The effect of every instruction on the types of the local variables and the operand stack
+//! (JVMS §4.10.1.9), shared by the type checker and the type inference.
+
+use crate::class_file::ClassFile;
+use crate::class_file::const_type::ConstType;
+use crate::class_file::descriptor;
+use crate::class_file::instruction::Insn;
+use crate::class_file::parse_error::{ParseError, ParseErrorKind};
+use super::types::{Hierarchy, TypeFrame, VType};
+use super::verify_error::{VerifyError, VerifyErrorKind};
+
+/// Where execution continues after an instruction.
+pub enum Control {
+    /// With the next instruction.
+    Next,
+    /// With the next instruction or one of the targets.
+    Branch(Vec<i64>),
+    /// With one of the targets only.
+    Jump(Vec<i64>),
+    /// Nowhere in this method, the instruction returns or throws.
+    Stop,
+    /// At the subroutine starting at the target, the return address has been pushed.
+    Jsr(i64),
+    /// At one of the return addresses of the subroutine.
+    Ret(Vec<usize>),
+}
+
+/// The method being verified.
+pub struct Method<'a> {
+    pub class_file: &'a ClassFile,
+    pub hierarchy: Hierarchy,
+    /// Name and descriptor, e.g. `main([Ljava/lang/String;)V`.
+    pub name: String,
+    pub instructions: Vec<(usize, Insn)>,
+    pub return_type: Option<VType>,
+    pub is_constructor: bool,
+    pub max_stack: usize,
+    pub max_locals: usize,
+}
+
+impl Method<'_> {
+    pub fn error(&self, pc: Option<usize>, kind: VerifyErrorKind) -> VerifyError {
+        VerifyError::new(&self.hierarchy.this_class, &self.name, pc, kind)
+    }
+
+    /// The index of the instruction starting at `pc`, `None` if no instruction starts there.
+    pub fn find_instruction(&self, pc: i64) -> Option<usize> {
+        self.instructions.binary_search_by_key(&pc, |(instruction_pc, _insn)| *instruction_pc as i64).ok()
+    }
+
+    fn push(&self, frame: &mut TypeFrame, value: VType) -> Result<(), VerifyErrorKind> {
+        frame.stack.push(value);
+        if frame.get_stack_size() > self.max_stack {
+            return Err(VerifyErrorKind::StackOverflow { max_stack: self.max_stack as u16 });
+        }
+        Ok(())
+    }
+
+    fn pop(&self, frame: &mut TypeFrame) -> Result<VType, VerifyErrorKind> {
+        frame.stack.pop().ok_or(VerifyErrorKind::StackUnderflow)
+    }
+
+    fn pop_expect(&self, frame: &mut TypeFrame, expected: &VType) -> Result<VType, VerifyErrorKind> {
+        let actual = self.pop(frame)?;
+        if !self.hierarchy.is_assignable(&actual, expected) {
+            return Err(mismatch("operand stack", expected, &actual));
+        }
+        Ok(actual)
+    }
+
+    /// Pops a reference, which may be uninitialized.
+    fn pop_reference(&self, frame: &mut TypeFrame) -> Result<VType, VerifyErrorKind> {
+        let actual = self.pop(frame)?;
+        if !actual.is_reference() {
+            return Err(mismatch("operand stack", "reference", &actual));
+        }
+        Ok(actual)
+    }
+
+    /// Pops `null` or an array whose descriptor is one of `accepted`.
+    fn pop_array(&self, frame: &mut TypeFrame, accepted: &[&str]) -> Result<VType, VerifyErrorKind> {
+        let actual = self.pop(frame)?;
+        match &actual {
+            VType::Null => Ok(actual),
+            VType::Reference(name) if accepted.contains(&name.as_str()) => Ok(actual),
+            _ => Err(mismatch("operand stack", &accepted.join(" or "), &actual)),
+        }
+    }
+
+    /// Pops values taking up exactly `slots` stack slots, returned bottom first. Used by the
+    /// `pop` and `dup` instructions, which must not split a long or double.
+    fn pop_slots(&self, frame: &mut TypeFrame, slots: usize) -> Result<Vec<VType>, VerifyErrorKind> {
+        let mut values = vec![];
+        let mut popped = 0;
+        while popped < slots {
+            let value = self.pop(frame)?;
+            popped += value.get_size();
+            if popped > slots {
+                return Err(mismatch("operand stack", "category 1 value", &value));
+            }
+            values.insert(0, value);
+        }
+        Ok(values)
+    }
+
+    fn load(&self, frame: &mut TypeFrame, index: usize, expected: VType) -> Result<(), VerifyErrorKind> {
+        self.check_local(index, expected.get_size())?;
+        let actual = &frame.locals[index];
+        if !self.hierarchy.is_assignable(actual, &expected) {
+            return Err(mismatch(&format!("local variable {}", index), &expected, actual));
+        }
+        self.push(frame, expected)
+    }
+
+    fn store(&self, frame: &mut TypeFrame, index: usize, value: VType) -> Result<(), VerifyErrorKind> {
+        self.check_local(index, value.get_size())?;
+        if index > 0 && frame.locals[index - 1].get_size() == 2 {
+            frame.locals[index - 1] = VType::Top;
+        }
+        if value.get_size() == 2 {
+            frame.locals[index + 1] = VType::Top;
+        }
+        frame.locals[index] = value;
+        Ok(())
+    }
+
+    fn check_local(&self, index: usize, size: usize) -> Result<(), VerifyErrorKind> {
+        if index + size > self.max_locals {
+            return Err(VerifyErrorKind::LocalOutOfRange { index: index + size - 1, max_locals: self.max_locals as u16 });
+        }
+        Ok(())
+    }
+
+    fn unary(&self, frame: &mut TypeFrame, operand: VType, result: VType) -> Result<Control, VerifyErrorKind> {
+        self.pop_expect(frame, &operand)?;
+        self.push(frame, result)?;
+        Ok(Control::Next)
+    }
+
+    fn binary(&self, frame: &mut TypeFrame, first: VType, second: VType, result: VType) -> Result<Control, VerifyErrorKind> {
+        self.pop_expect(frame, &second)?;
+        self.pop_expect(frame, &first)?;
+        self.push(frame, result)?;
+        Ok(Control::Next)
+    }
+
+    fn array_load(&self, frame: &mut TypeFrame, accepted: &[&str], result: VType) -> Result<Control, VerifyErrorKind> {
+        self.pop_expect(frame, &VType::Int)?;
+        self.pop_array(frame, accepted)?;
+        self.push(frame, result)?;
+        Ok(Control::Next)
+    }
+
+    fn array_store(&self, frame: &mut TypeFrame, accepted: &[&str], value: VType) -> Result<Control, VerifyErrorKind> {
+        self.pop_expect(frame, &value)?;
+        self.pop_expect(frame, &VType::Int)?;
+        self.pop_array(frame, accepted)?;
+        Ok(Control::Next)
+    }
+
+    fn return_value(&self, frame: &mut TypeFrame, kind: VType) -> Result<Control, VerifyErrorKind> {
+        let matches = match &self.return_type {
+            Some(return_type) if kind.is_reference() => return_type.is_reference(),
+            Some(return_type) => *return_type == kind,
+            None => false,
+        };
+        if !matches {
+            let expected = self.return_type.as_ref().map(|return_type| return_type.to_string()).unwrap_or_else(|| "void".to_string());
+            let actual = if kind.is_reference() { "reference".to_string() } else { kind.to_string() };
+            return Err(VerifyErrorKind::TypeMismatch { location: "return value".to_string(), expected, actual });
+        }
+        self.pop_expect(frame, &self.return_type.clone().unwrap())?;
+        Ok(Control::Stop)
+    }
+
+    fn branch(&self, frame: &mut TypeFrame, operands: &[VType], insn: &Insn, pc: usize) -> Result<Control, VerifyErrorKind> {
+        for operand in operands.iter().rev() {
+            if *operand == VType::Null {
+                self.pop_reference(frame)?;
+            } else {
+                self.pop_expect(frame, operand)?;
+            }
+        }
+        Ok(Control::Branch(insn.get_branch_targets(pc)))
+    }
+
+    fn field_type(&self, index: u16) -> Result<VType, VerifyErrorKind> {
+        let (_class_name, _name, field_descriptor) = self.class_file.get_member_ref(index as usize)?;
+        Ok(VType::from_field_type(&descriptor::parse_field_descriptor(&field_descriptor)?))
+    }
+
+    fn invoke(&self, frame: &mut TypeFrame, method_descriptor: &str, receiver: Option<(&str, &str)>) -> Result<Control, VerifyErrorKind> {
+        let method_descriptor = descriptor::parse_method_descriptor(method_descriptor)?;
+        for parameter in method_descriptor.get_parameters().iter().rev() {
+            self.pop_expect(frame, &VType::from_field_type(parameter))?;
+        }
+        match receiver {
+            Some((_class_name, "<init>")) => {
+                let actual = self.pop(frame)?;
+                let initialized = match &actual {
+                    VType::UninitializedThis => VType::Reference(self.hierarchy.this_class.clone()),
+                    VType::Uninitialized(new_pc) => match self.find_instruction(*new_pc as i64).map(|index| &self.instructions[index].1) {
+                        Some(Insn::New(class_index)) => VType::Reference(self.class_file.get_name_of_class(*class_index as usize)?),
+                        _ => return Err(VerifyErrorKind::InvalidBranchTarget(*new_pc as i64)),
+                    },
+                    _ => return Err(mismatch("operand stack", "uninitialized", &actual)),
+                };
+                frame.replace(&actual, &initialized);
+            }
+            Some((class_name, _name)) => {
+                self.pop_expect(frame, &VType::reference(class_name))?;
+            }
+            None => {}
+        }
+        if let Some(return_type) = method_descriptor.get_return_type() {
+            self.push(frame, VType::from_field_type(return_type))?;
+        }
+        Ok(Control::Next)
+    }
+
+    /// Applies the instruction at `pc` to `frame`.
+    pub fn execute(&self, frame: &mut TypeFrame, pc: usize, insn: &Insn, next_pc: usize) -> Result<Control, VerifyErrorKind> {
+        let insn = match insn {
+            Insn::Wide(inner) => inner.as_ref(),
+            _ => insn,
+        };
+        let local = insn.get_local_index().unwrap_or(0) as usize;
+        let object = VType::reference("java/lang/Object");
+        match insn {
+            Insn::Nop => Ok(Control::Next),
+            Insn::AconstNull => self.push(frame, VType::Null).map(|_| Control::Next),
+            Insn::IconstM1 | Insn::Iconst0 | Insn::Iconst1 | Insn::Iconst2 | Insn::Iconst3 | Insn::Iconst4 | Insn::Iconst5
+            | Insn::Bipush(_) | Insn::Sipush(_) => self.push(frame, VType::Int).map(|_| Control::Next),
+            Insn::Lconst0 | Insn::Lconst1 => self.push(frame, VType::Long).map(|_| Control::Next),
+            Insn::Fconst0 | Insn::Fconst1 | Insn::Fconst2 => self.push(frame, VType::Float).map(|_| Control::Next),
+            Insn::Dconst0 | Insn::Dconst1 => self.push(frame, VType::Double).map(|_| Control::Next),
+            Insn::Ldc(index) | Insn::LdcW(index) | Insn::Ldc2W(index) => {
+                let wide = matches!(insn, Insn::Ldc2W(_));
+                let value = constant_type(self.class_file, *index)?;
+                if (value.get_size() == 2) != wide {
+                    return Err(ParseError::new(ParseErrorKind::UnexpectedConstant {
+                        index: *index as usize,
+                        expected: if wide { "long or double" } else { "loadable single slot" },
+                    }).into());
+                }
+                self.push(frame, value).map(|_| Control::Next)
+            }
+            Insn::Iload(_) | Insn::Iload0 | Insn::Iload1 | Insn::Iload2 | Insn::Iload3 => self.load(frame, local, VType::Int).map(|_| Control::Next),
+            Insn::Lload(_) | Insn::Lload0 | Insn::Lload1 | Insn::Lload2 | Insn::Lload3 => self.load(frame, local, VType::Long).map(|_| Control::Next),
+            Insn::Fload(_) | Insn::Fload0 | Insn::Fload1 | Insn::Fload2 | Insn::Fload3 => self.load(frame, local, VType::Float).map(|_| Control::Next),
+            Insn::Dload(_) | Insn::Dload0 | Insn::Dload1 | Insn::Dload2 | Insn::Dload3 => self.load(frame, local, VType::Double).map(|_| Control::Next),
+            Insn::Aload(_) | Insn::Aload0 | Insn::Aload1 | Insn::Aload2 | Insn::Aload3 => {
+                self.check_local(local, 1)?;
+                let actual = frame.locals[local].clone();
+                if !actual.is_reference() {
+                    return Err(mismatch(&format!("local variable {}", local), "reference", &actual));
+                }
+                self.push(frame, actual).map(|_| Control::Next)
+            }
+            Insn::Iaload => self.array_load(frame, &["[I"], VType::Int),
+            Insn::Laload => self.array_load(frame, &["[J"], VType::Long),
+            Insn::Faload => self.array_load(frame, &["[F"], VType::Float),
+            Insn::Daload => self.array_load(frame, &["[D"], VType::Double),
+            Insn::Baload => self.array_load(frame, &["[B", "[Z"], VType::Int),
+            Insn::Caload => self.array_load(frame, &["[C"], VType::Int),
+            Insn::Saload => self.array_load(frame, &["[S"], VType::Int),
+            Insn::Aaload => {
+                self.pop_expect(frame, &VType::Int)?;
+                let array = self.pop(frame)?;
+                let component = match array.get_component() {
+                    _ if array == VType::Null => VType::Null,
+                    Some(component) if component.is_reference() => component,
+                    _ => return Err(mismatch("operand stack", "array of reference", &array)),
+                };
+                self.push(frame, component).map(|_| Control::Next)
+            }
+            Insn::Istore(_) | Insn::Istore0 | Insn::Istore1 | Insn::Istore2 | Insn::Istore3 => {
+                let value = self.pop_expect(frame, &VType::Int)?;
+                self.store(frame, local, value).map(|_| Control::Next)
+            }
+            Insn::Lstore(_) | Insn::Lstore0 | Insn::Lstore1 | Insn::Lstore2 | Insn::Lstore3 => {
+                let value = self.pop_expect(frame, &VType::Long)?;
+                self.store(frame, local, value).map(|_| Control::Next)
+            }
+            Insn::Fstore(_) | Insn::Fstore0 | Insn::Fstore1 | Insn::Fstore2 | Insn::Fstore3 => {
+                let value = self.pop_expect(frame, &VType::Float)?;
+                self.store(frame, local, value).map(|_| Control::Next)
+            }
+            Insn::Dstore(_) | Insn::Dstore0 | Insn::Dstore1 | Insn::Dstore2 | Insn::Dstore3 => {
+                let value = self.pop_expect(frame, &VType::Double)?;
+                self.store(frame, local, value).map(|_| Control::Next)
+            }
+            Insn::Astore(_) | Insn::Astore0 | Insn::Astore1 | Insn::Astore2 | Insn::Astore3 => {
+                let value = self.pop(frame)?;
+                if !value.is_reference() && !matches!(value, VType::ReturnAddress(_)) {
+                    return Err(mismatch("operand stack", "reference or returnAddress", &value));
+                }
+                self.store(frame, local, value).map(|_| Control::Next)
+            }
+            Insn::Iastore => self.array_store(frame, &["[I"], VType::Int),
+            Insn::Lastore => self.array_store(frame, &["[J"], VType::Long),
+            Insn::Fastore => self.array_store(frame, &["[F"], VType::Float),
+            Insn::Dastore => self.array_store(frame, &["[D"], VType::Double),
+            Insn::Bastore => self.array_store(frame, &["[B", "[Z"], VType::Int),
+            Insn::Castore => self.array_store(frame, &["[C"], VType::Int),
+            Insn::Sastore => self.array_store(frame, &["[S"], VType::Int),
+            Insn::Aastore => {
+                // Whether the value fits the component type is only known at run time (ArrayStoreException).
+                self.pop_expect(frame, &object)?;
+                self.pop_expect(frame, &VType::Int)?;
+                let array = self.pop(frame)?;
+                match array.get_component() {
+                    _ if array == VType::Null => Ok(Control::Next),
+                    Some(component) if component.is_reference() => Ok(Control::Next),
+                    _ => Err(mismatch("operand stack", "array of reference", &array)),
+                }
+            }
+            Insn::Pop => self.pop_slots(frame, 1).map(|_| Control::Next),
+            Insn::Pop2 => self.pop_slots(frame, 2).map(|_| Control::Next),
+            Insn::Dup | Insn::DupX1 | Insn::DupX2 | Insn::Dup2 | Insn::Dup2X1 | Insn::Dup2X2 => {
+                let (duplicated, skipped) = match insn {
+                    Insn::Dup => (1, 0),
+                    Insn::DupX1 => (1, 1),
+                    Insn::DupX2 => (1, 2),
+                    Insn::Dup2 => (2, 0),
+                    Insn::Dup2X1 => (2, 1),
+                    _ => (2, 2),
+                };
+                let top = self.pop_slots(frame, duplicated)?;
+                let under = self.pop_slots(frame, skipped)?;
+                for value in top.iter().chain(&under).chain(&top) {
+                    self.push(frame, value.clone())?;
+                }
+                Ok(Control::Next)
+            }
+            Insn::Swap => {
+                let first = self.pop_slots(frame, 1)?;
+                let second = self.pop_slots(frame, 1)?;
+                for value in first.into_iter().chain(second) {
+                    self.push(frame, value)?;
+                }
+                Ok(Control::Next)
+            }
+            Insn::Iadd | Insn::Isub | Insn::Imul | Insn::Idiv | Insn::Irem | Insn::Ishl | Insn::Ishr | Insn::Iushr
+            | Insn::Iand | Insn::Ior | Insn::Ixor => self.binary(frame, VType::Int, VType::Int, VType::Int),
+            Insn::Ladd | Insn::Lsub | Insn::Lmul | Insn::Ldiv | Insn::Lrem | Insn::Land | Insn::Lor | Insn::Lxor => {
+                self.binary(frame, VType::Long, VType::Long, VType::Long)
+            }
+            Insn::Lshl | Insn::Lshr | Insn::Lushr => self.binary(frame, VType::Long, VType::Int, VType::Long),
+            Insn::Fadd | Insn::Fsub | Insn::Fmul | Insn::Fdiv | Insn::Frem => self.binary(frame, VType::Float, VType::Float, VType::Float),
+            Insn::Dadd | Insn::Dsub | Insn::Dmul | Insn::Ddiv | Insn::Drem => self.binary(frame, VType::Double, VType::Double, VType::Double),
+            Insn::Ineg | Insn::I2b | Insn::I2c | Insn::I2s => self.unary(frame, VType::Int, VType::Int),
+            Insn::Lneg => self.unary(frame, VType::Long, VType::Long),
+            Insn::Fneg => self.unary(frame, VType::Float, VType::Float),
+            Insn::Dneg => self.unary(frame, VType::Double, VType::Double),
+            Insn::Iinc { .. } => {
+                self.load(frame, local, VType::Int)?;
+                self.pop(frame).map(|_| Control::Next)
+            }
+            Insn::I2l => self.unary(frame, VType::Int, VType::Long),
+            Insn::I2f => self.unary(frame, VType::Int, VType::Float),
+            Insn::I2d => self.unary(frame, VType::Int, VType::Double),
+            Insn::L2i => self.unary(frame, VType::Long, VType::Int),
+            Insn::L2f => self.unary(frame, VType::Long, VType::Float),
+            Insn::L2d => self.unary(frame, VType::Long, VType::Double),
+            Insn::F2i => self.unary(frame, VType::Float, VType::Int),
+            Insn::F2l => self.unary(frame, VType::Float, VType::Long),
+            Insn::F2d => self.unary(frame, VType::Float, VType::Double),
+            Insn::D2i => self.unary(frame, VType::Double, VType::Int),
+            Insn::D2l => self.unary(frame, VType::Double, VType::Long),
+            Insn::D2f => self.unary(frame, VType::Double, VType::Float),
+            Insn::Lcmp => self.binary(frame, VType::Long, VType::Long, VType::Int),
+            Insn::Fcmpl | Insn::Fcmpg => self.binary(frame, VType::Float, VType::Float, VType::Int),
+            Insn::Dcmpl | Insn::Dcmpg => self.binary(frame, VType::Double, VType::Double, VType::Int),
+            Insn::Ifeq(_) | Insn::Ifne(_) | Insn::Iflt(_) | Insn::Ifge(_) | Insn::Ifgt(_) | Insn::Ifle(_) => {
+                self.branch(frame, &[VType::Int], insn, pc)
+            }
+            Insn::IfIcmpeq(_) | Insn::IfIcmpne(_) | Insn::IfIcmplt(_) | Insn::IfIcmpge(_) | Insn::IfIcmpgt(_) | Insn::IfIcmple(_) => {
+                self.branch(frame, &[VType::Int, VType::Int], insn, pc)
+            }
+            // `Null` stands for any reference here.
+            Insn::IfAcmpeq(_) | Insn::IfAcmpne(_) => self.branch(frame, &[VType::Null, VType::Null], insn, pc),
+            Insn::Ifnull(_) | Insn::Ifnonnull(_) => self.branch(frame, &[VType::Null], insn, pc),
+            Insn::Goto(_) | Insn::GotoW(_) => Ok(Control::Jump(insn.get_branch_targets(pc))),
+            Insn::Jsr(_) | Insn::JsrW(_) => {
+                self.push(frame, VType::ReturnAddress(vec![next_pc]))?;
+                Ok(Control::Jsr(insn.get_branch_targets(pc)[0]))
+            }
+            Insn::Ret(_) => {
+                self.check_local(local, 1)?;
+                match &frame.locals[local] {
+                    VType::ReturnAddress(pcs) => Ok(Control::Ret(pcs.clone())),
+                    actual => Err(mismatch(&format!("local variable {}", local), "returnAddress", actual)),
+                }
+            }
+            Insn::Tableswitch { .. } | Insn::Lookupswitch { .. } => {
+                self.pop_expect(frame, &VType::Int)?;
+                Ok(Control::Jump(insn.get_branch_targets(pc)))
+            }
+            Insn::Ireturn => self.return_value(frame, VType::Int),
+            Insn::Lreturn => self.return_value(frame, VType::Long),
+            Insn::Freturn => self.return_value(frame, VType::Float),
+            Insn::Dreturn => self.return_value(frame, VType::Double),
+            Insn::Areturn => self.return_value(frame, object),
+            Insn::Return => {
+                if self.return_type.is_some() {
+                    return Err(mismatch("return value", &self.return_type.clone().unwrap(), "void"));
+                }
+                if self.is_constructor && frame.locals.contains(&VType::UninitializedThis) {
+                    return Err(VerifyErrorKind::UninitializedThisOnReturn);
+                }
+                Ok(Control::Stop)
+            }
+            Insn::Getstatic(index) => {
+                let value = self.field_type(*index)?;
+                self.push(frame, value).map(|_| Control::Next)
+            }
+            Insn::Putstatic(index) => {
+                self.pop_expect(frame, &self.field_type(*index)?)?;
+                Ok(Control::Next)
+            }
+            Insn::Getfield(index) => {
+                let (class_name, _name, _descriptor) = self.class_file.get_member_ref(*index as usize)?;
+                self.pop_expect(frame, &VType::Reference(class_name))?;
+                let value = self.field_type(*index)?;
+                self.push(frame, value).map(|_| Control::Next)
+            }
+            Insn::Putfield(index) => {
+                let (class_name, _name, _descriptor) = self.class_file.get_member_ref(*index as usize)?;
+                self.pop_expect(frame, &self.field_type(*index)?)?;
+                // A constructor may assign the fields of its own class before calling super().
+                let object = self.pop(frame)?;
+                let own_field = object == VType::UninitializedThis && class_name == self.hierarchy.this_class;
+                if !own_field && !self.hierarchy.is_assignable(&object, &VType::Reference(class_name.clone())) {
+                    return Err(mismatch("operand stack", &class_name, &object));
+                }
+                Ok(Control::Next)
+            }
+            Insn::Invokevirtual(index) | Insn::Invokespecial(index) | Insn::Invokeinterface { index, .. } => {
+                let (class_name, name, method_descriptor) = self.class_file.get_member_ref(*index as usize)?;
+                if name == "<init>" && !matches!(insn, Insn::Invokespecial(_)) {
+                    return Err(VerifyErrorKind::IllegalInstruction(insn.get_mnemonic()));
+                }
+                self.invoke(frame, &method_descriptor, Some((&class_name, &name)))
+            }
+            Insn::Invokestatic(index) => {
+                let (_class_name, _name, method_descriptor) = self.class_file.get_member_ref(*index as usize)?;
+                self.invoke(frame, &method_descriptor, None)
+            }
+            Insn::Invokedynamic(index) => {
+                let (_bootstrap_method_attr_index, name_and_type_index) = self.class_file.get_invoke_dynamic(*index as usize)?;
+                let method_descriptor = self.class_file.get_description_of_member(name_and_type_index as usize)?;
+                self.invoke(frame, &method_descriptor, None)
+            }
+            Insn::New(index) => {
+                self.class_file.get_name_of_class(*index as usize)?;
+                self.push(frame, VType::Uninitialized(pc)).map(|_| Control::Next)
+            }
+            Insn::Newarray(atype) => {
+                let descriptor = match atype {
+                    4 => "[Z",
+                    5 => "[C",
+                    6 => "[F",
+                    7 => "[D",
+                    8 => "[B",
+                    9 => "[S",
+                    10 => "[I",
+                    11 => "[J",
+                    _ => return Err(ParseError::new(ParseErrorKind::InvalidTag { structure: "array type", tag: *atype }).into()),
+                };
+                self.unary(frame, VType::Int, VType::reference(descriptor))
+            }
+            Insn::Anewarray(index) => {
+                let class_name = self.class_file.get_name_of_class(*index as usize)?;
+                let array = if class_name.starts_with('[') { format!("[{}", class_name) } else { format!("[L{};", class_name) };
+                self.unary(frame, VType::Int, VType::Reference(array))
+            }
+            Insn::Multianewarray { index, dimensions } => {
+                for _ in 0..*dimensions {
+                    self.pop_expect(frame, &VType::Int)?;
+                }
+                let array = self.class_file.get_name_of_class(*index as usize)?;
+                self.push(frame, VType::Reference(array)).map(|_| Control::Next)
+            }
+            Insn::Arraylength => {
+                let array = self.pop(frame)?;
+                if array != VType::Null && array.get_component().is_none() {
+                    return Err(mismatch("operand stack", "array", &array));
+                }
+                self.push(frame, VType::Int).map(|_| Control::Next)
+            }
+            Insn::Athrow => {
+                self.pop_expect(frame, &VType::reference("java/lang/Throwable"))?;
+                Ok(Control::Stop)
+            }
+            Insn::Checkcast(index) => {
+                self.pop_expect(frame, &object)?;
+                let class_name = self.class_file.get_name_of_class(*index as usize)?;
+                self.push(frame, VType::Reference(class_name)).map(|_| Control::Next)
+            }
+            Insn::Instanceof(index) => {
+                self.class_file.get_name_of_class(*index as usize)?;
+                self.unary(frame, object, VType::Int)
+            }
+            Insn::Monitorenter | Insn::Monitorexit => self.pop_expect(frame, &object).map(|_| Control::Next),
+            Insn::Wide(_) => Err(VerifyErrorKind::IllegalInstruction("wide")),
+        }
+    }
+}
+
+fn mismatch<E: ToString + ?Sized, A: ToString + ?Sized>(location: &str, expected: &E, actual: &A) -> VerifyErrorKind {
+    VerifyErrorKind::TypeMismatch { location: location.to_string(), expected: expected.to_string(), actual: actual.to_string() }
+}
+
+/// The type `ldc`, `ldc_w` or `ldc2_w` pushes for the constant at `index`.
+fn constant_type(class_file: &ClassFile, index: u16) -> Result<VType, VerifyErrorKind> {
+    Ok(match class_file.get_constant(index as usize)? {
+        ConstType::ConstantInteger(_) => VType::Int,
+        ConstType::ConstantFloat(_) => VType::Float,
+        ConstType::ConstantLong(_, _) => VType::Long,
+        ConstType::ConstantDouble(_, _) => VType::Double,
+        ConstType::ConstantString(_) => VType::reference("java/lang/String"),
+        ConstType::ConstantClass(_) => VType::reference("java/lang/Class"),
+        ConstType::ConstantMethodType(_) => VType::reference("java/lang/invoke/MethodType"),
+        ConstType::ConstantMethodHandle(_, _) => VType::reference("java/lang/invoke/MethodHandle"),
+        ConstType::ConstantDynamic(_, name_and_type_index) => {
+            let field_descriptor = class_file.get_description_of_member(*name_and_type_index as usize)?;
+            VType::from_field_type(&descriptor::parse_field_descriptor(&field_descriptor)?)
+        }
+        _ => return Err(ParseError::new(ParseErrorKind::UnexpectedConstant { index: index as usize, expected: "loadable" }).into()),
+    })
+}
@@ -0,0 +1,204 @@
+use std::fmt;
+use crate::class_file::descriptor::{BaseType, FieldType};
+
+/// A verification type (JVMS §4.10.1.2). References hold the name used by `CONSTANT_Class`
+/// entries: the internal class name, or the descriptor for arrays.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VType {
+    Top,
+    Int,
+    Float,
+    Long,
+    Double,
+    Null,
+    UninitializedThis,
+    /// An object created by the `new` instruction at the given pc.
+    Uninitialized(usize),
+    Reference(String),
+    /// Pushed by `jsr`; the set of pcs a `ret` may return to. Only used when inferring types.
+    ReturnAddress(Vec<usize>),
+}
+
+impl VType {
+    pub fn from_field_type(field_type: &FieldType) -> VType {
+        match field_type {
+            FieldType::Base(BaseType::Float) => VType::Float,
+            FieldType::Base(BaseType::Long) => VType::Long,
+            FieldType::Base(BaseType::Double) => VType::Double,
+            FieldType::Base(_) => VType::Int,
+            FieldType::Object(class_name) => VType::Reference(class_name.clone()),
+            FieldType::Array(_) => VType::Reference(field_type.get_descriptor()),
+        }
+    }
+
+    pub fn reference(class_name: &str) -> VType {
+        VType::Reference(class_name.to_string())
+    }
+
+    pub fn get_size(&self) -> usize {
+        match self {
+            VType::Long | VType::Double => 2,
+            _ => 1,
+        }
+    }
+
+    /// Whether the type is a reference, initialized or not, or `null`.
+    pub fn is_reference(&self) -> bool {
+        matches!(self, VType::Null | VType::UninitializedThis | VType::Uninitialized(_) | VType::Reference(_))
+    }
+
+    /// The type of the elements of an array type, `None` if this is no array type.
+    pub fn get_component(&self) -> Option<VType> {
+        match self {
+            VType::Reference(name) if name.starts_with('[') => {
+                let component = &name[1..];
+                Some(match component {
+                    "F" => VType::Float,
+                    "J" => VType::Long,
+                    "D" => VType::Double,
+                    "B" | "C" | "I" | "S" | "Z" => VType::Int,
+                    _ if component.starts_with('[') => VType::Reference(component.to_string()),
+                    _ => VType::Reference(component.trim_start_matches('L').trim_end_matches(';').to_string()),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for VType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VType::Top => f.write_str("top"),
+            VType::Int => f.write_str("int"),
+            VType::Float => f.write_str("float"),
+            VType::Long => f.write_str("long"),
+            VType::Double => f.write_str("double"),
+            VType::Null => f.write_str("null"),
+            VType::UninitializedThis => f.write_str("uninitializedThis"),
+            VType::Uninitialized(pc) => write!(f, "uninitialized({})", pc),
+            VType::Reference(name) => f.write_str(name),
+            VType::ReturnAddress(_) => f.write_str("returnAddress"),
+        }
+    }
+}
+
+/// What the verifier knows about the class hierarchy: only the class being verified and its
+/// direct superclass, as no other classes are loaded. Class types that cannot be related with
+/// this knowledge are assumed to be assignable to each other; primitives, arrays and
+/// initialization state are always checked exactly.
+pub struct Hierarchy {
+    pub this_class: String,
+    pub super_class: Option<String>,
+}
+
+impl Hierarchy {
+    pub fn is_assignable(&self, from: &VType, to: &VType) -> bool {
+        if from == to || *to == VType::Top {
+            return true;
+        }
+        match (from, to) {
+            (VType::Null, VType::Reference(_)) => true,
+            (VType::Reference(from_name), VType::Reference(to_name)) => self.is_reference_assignable(from_name, to_name),
+            (VType::ReturnAddress(_), VType::ReturnAddress(_)) => true,
+            _ => false,
+        }
+    }
+
+    fn is_reference_assignable(&self, from: &str, to: &str) -> bool {
+        if to == "java/lang/Object" {
+            return true;
+        }
+        match (from.starts_with('['), to.starts_with('[')) {
+            (true, true) => {
+                let from_component = VType::Reference(from.to_string()).get_component().unwrap_or(VType::Top);
+                let to_component = VType::Reference(to.to_string()).get_component().unwrap_or(VType::Top);
+                match (&from_component, &to_component) {
+                    (VType::Reference(_), VType::Reference(_)) => self.is_assignable(&from_component, &to_component),
+                    _ => from == to,
+                }
+            }
+            (true, false) => to == "java/lang/Cloneable" || to == "java/io/Serializable",
+            (false, true) => false,
+            (false, false) => {
+                // The only class known to be unrelated to a supertype is the superclass of the verified class.
+                !(self.super_class.as_deref() == Some(from) && to == self.this_class)
+            }
+        }
+    }
+
+    /// The least type both `first` and `second` are assignable to, used when inferring types.
+    /// `Top` if there is none.
+    pub fn merge(&self, first: &VType, second: &VType) -> VType {
+        if first == second {
+            return first.clone();
+        }
+        match (first, second) {
+            (VType::Null, VType::Reference(_)) => second.clone(),
+            (VType::Reference(_), VType::Null) => first.clone(),
+            (VType::Reference(first_name), VType::Reference(second_name)) => {
+                if self.is_assignable(first, second) && !self.is_assignable(second, first) {
+                    second.clone()
+                } else if self.is_assignable(second, first) && !self.is_assignable(first, second) {
+                    first.clone()
+                } else if first_name.starts_with("[L") && second_name.starts_with("[L") {
+                    VType::reference("[Ljava/lang/Object;")
+                } else {
+                    VType::reference("java/lang/Object")
+                }
+            }
+            (VType::ReturnAddress(first_pcs), VType::ReturnAddress(second_pcs)) => {
+                let mut pcs = first_pcs.clone();
+                for pc in second_pcs {
+                    if !pcs.contains(pc) {
+                        pcs.push(*pc);
+                    }
+                }
+                pcs.sort();
+                VType::ReturnAddress(pcs)
+            }
+            _ => VType::Top,
+        }
+    }
+}
+
+/// The types of the local variables and operand stack before an instruction. Locals are kept
+/// per slot, with `Top` in the slot after a long or double; the stack is kept per value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeFrame {
+    pub locals: Vec<VType>,
+    pub stack: Vec<VType>,
+}
+
+impl TypeFrame {
+    pub fn get_stack_size(&self) -> usize {
+        self.stack.iter().map(|value| value.get_size()).sum()
+    }
+
+    /// Whether every local and stack entry is assignable to the corresponding one of `to` (JVMS §4.10.1.4).
+    pub fn is_assignable(&self, to: &TypeFrame, hierarchy: &Hierarchy) -> bool {
+        self.locals.len() == to.locals.len()
+            && self.stack.len() == to.stack.len()
+            && self.locals.iter().zip(&to.locals).all(|(from, to)| hierarchy.is_assignable(from, to))
+            && self.stack.iter().zip(&to.stack).all(|(from, to)| hierarchy.is_assignable(from, to))
+    }
+
+    /// Every use of `from` replaced by `to`, as done when a constructor is invoked on an uninitialized object.
+    pub fn replace(&mut self, from: &VType, to: &VType) {
+        for value in self.locals.iter_mut().chain(self.stack.iter_mut()) {
+            if value == from {
+                *value = to.clone();
+            }
+        }
+    }
+}
+
+fn join(types: &[VType]) -> String {
+    types.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(", ")
+}
+
+impl fmt::Display for TypeFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{locals: [{}], stack: [{}]}}", join(&self.locals), join(&self.stack))
+    }
+}
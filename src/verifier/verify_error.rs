@@ -0,0 +1,102 @@
+use std::error::Error;
+use std::fmt;
+use crate::class_file::parse_error::ParseError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyErrorKind {
+    /// A value of type `actual` was found where `expected` is required. `location` names the
+    /// operand stack or the local variable.
+    TypeMismatch { location: String, expected: String, actual: String },
+    StackUnderflow,
+    StackOverflow { max_stack: u16 },
+    LocalOutOfRange { index: usize, max_locals: u16 },
+    /// The frame reaching an instruction is not assignable to the frame the `StackMapTable` records for it.
+    IncompatibleFrame { expected: String, actual: String },
+    /// The stack map frame at a branch target, handler or after an unconditional jump is missing.
+    MissingStackMapFrame,
+    /// A `StackMapTable` frame is recorded for a pc at which no instruction starts.
+    InvalidStackMapFrame(usize),
+    /// Two paths reach an instruction with operand stacks that cannot be merged.
+    InconsistentStack { first: String, second: String },
+    InvalidBranchTarget(i64),
+    FallsOffEnd,
+    /// A constructor returns before calling `super()` or `this()`.
+    UninitializedThisOnReturn,
+    /// The instruction cannot appear in a class of this version, like `jsr` in a version 50 class.
+    IllegalInstruction(&'static str),
+    Parse(ParseError),
+}
+
+impl fmt::Display for VerifyErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VerifyErrorKind::TypeMismatch { location, expected, actual } => {
+                write!(f, "bad type in {}: expected {}, found {}", location, expected, actual)
+            }
+            VerifyErrorKind::StackUnderflow => write!(f, "operand stack underflow"),
+            VerifyErrorKind::StackOverflow { max_stack } => write!(f, "operand stack exceeds max_stack {}", max_stack),
+            VerifyErrorKind::LocalOutOfRange { index, max_locals } => write!(f, "local variable {} exceeds max_locals {}", index, max_locals),
+            VerifyErrorKind::IncompatibleFrame { expected, actual } => {
+                write!(f, "current frame is not assignable to stack map frame: expected {}, found {}", expected, actual)
+            }
+            VerifyErrorKind::MissingStackMapFrame => write!(f, "expecting a stack map frame"),
+            VerifyErrorKind::InvalidStackMapFrame(pc) => write!(f, "stack map frame at pc {} is no instruction", pc),
+            VerifyErrorKind::InconsistentStack { first, second } => write!(f, "inconsistent stack heights or types: {} and {}", first, second),
+            VerifyErrorKind::InvalidBranchTarget(target) => write!(f, "branch target {} is no instruction", target),
+            VerifyErrorKind::FallsOffEnd => write!(f, "execution falls off the end of the code"),
+            VerifyErrorKind::UninitializedThisOnReturn => write!(f, "constructor returns with an uninitialized this"),
+            VerifyErrorKind::IllegalInstruction(mnemonic) => write!(f, "{} is not allowed in this class file version", mnemonic),
+            VerifyErrorKind::Parse(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// A method failed verification (JVMS §4.10). Corresponds to `java.lang.VerifyError`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyError {
+    class_name: String,
+    method: String,
+    pc: Option<usize>,
+    kind: Box<VerifyErrorKind>,
+}
+
+impl VerifyError {
+    pub fn new(class_name: &str, method: &str, pc: Option<usize>, kind: VerifyErrorKind) -> VerifyError {
+        VerifyError { class_name: class_name.to_string(), method: method.to_string(), pc, kind: Box::new(kind) }
+    }
+
+    pub fn get_class_name(&self) -> &str {
+        &self.class_name
+    }
+
+    /// Name and descriptor of the method, e.g. `main([Ljava/lang/String;)V`.
+    pub fn get_method(&self) -> &str {
+        &self.method
+    }
+
+    pub fn get_pc(&self) -> Option<usize> {
+        self.pc
+    }
+
+    pub fn get_kind(&self) -> &VerifyErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "java.lang.VerifyError: {}.{}", self.class_name.replace('/', "."), self.method)?;
+        if let Some(pc) = self.pc {
+            write!(f, " at pc {}", pc)?;
+        }
+        write!(f, ": {}", self.kind)
+    }
+}
+
+impl Error for VerifyError {}
+
+impl From<ParseError> for VerifyErrorKind {
+    fn from(error: ParseError) -> VerifyErrorKind {
+        VerifyErrorKind::Parse(error)
+    }
+}
@@ -0,0 +1,12 @@
+use rust_jvm::class_file::access_flags::{ClassAccessFlags, MethodAccessFlags, InnerClassAccessFlags, ModuleAccessFlags};
+
+#[test]
+fn same_bit_differs_per_context() {
+    assert!(ClassAccessFlags::new(0x0020).is_super());
+    assert!(MethodAccessFlags::new(0x0020).is_synchronized());
+    assert!(ModuleAccessFlags::new(0x0020).is_open());
+    assert_eq!(ClassAccessFlags::new(0x0021).to_string(), "(0x0021) ACC_PUBLIC, ACC_SUPER");
+    assert_eq!(MethodAccessFlags::new(0x0129).to_string(), "(0x0129) ACC_PUBLIC, ACC_STATIC, ACC_SYNCHRONIZED, ACC_NATIVE");
+    assert_eq!(MethodAccessFlags::new(0x0129).get_modifiers(), vec!["public", "static", "synchronized", "native"]);
+    assert_eq!(InnerClassAccessFlags::new(0x0609).get_modifiers(), vec!["public", "static"]);
+}
@@ -0,0 +1,111 @@
+use std::fs;
+use rust_jvm::class_file::{self, annotation::{Annotation, ElementValue, TargetInfo}, const_type::ConstType, ClassFile};
+use rust_jvm::util::file;
+
+fn load_fixture(name: &str) -> ClassFile {
+    let path = format!("{}/java/test/{}.class", env!("CARGO_MANIFEST_DIR"), name);
+    let bytes = fs::read(&path).unwrap_or_else(|e| panic!("Can't read fixture {}: {}", path, e));
+    class_file::parse_file(&mut file::File::new(bytes)).unwrap()
+}
+
+/// The annotation in Java-like syntax with the constants resolved, e.g. `@LTag;(value="f", nums={3})`.
+fn render(class_file: &ClassFile, annotation: &Annotation) -> String {
+    let pairs: Vec<String> = annotation.get_element_value_pairs().iter().map(|pair| {
+        format!("{}={}", class_file.get_utf8(pair.get_element_name_index() as usize).unwrap(), render_value(class_file, pair.get_value()))
+    }).collect();
+    format!("@{}({})", class_file.get_utf8(annotation.get_type_index() as usize).unwrap(), pairs.join(", "))
+}
+
+fn render_value(class_file: &ClassFile, value: &ElementValue) -> String {
+    match value {
+        ElementValue::Const { tag: 's', const_value_index } => format!("{:?}", class_file.get_utf8(*const_value_index as usize).unwrap()),
+        ElementValue::Const { tag, const_value_index } => match class_file.get_constant(*const_value_index as usize).unwrap() {
+            ConstType::ConstantInteger(bytes) => format!("{}{}", *bytes as i32, tag),
+            constant => panic!("{:?}", constant),
+        },
+        ElementValue::Enum { type_name_index, const_name_index } => format!("{}.{}",
+            class_file.get_utf8(*type_name_index as usize).unwrap(), class_file.get_utf8(*const_name_index as usize).unwrap()),
+        ElementValue::Class(class_info_index) => format!("{}.class", class_file.get_utf8(*class_info_index as usize).unwrap()),
+        ElementValue::Annotation(annotation) => render(class_file, annotation),
+        ElementValue::Array(values) => {
+            let values: Vec<String> = values.iter().map(|value| render_value(class_file, value)).collect();
+            format!("{{{}}}", values.join(", "))
+        }
+    }
+}
+
+fn render_all(class_file: &ClassFile, annotations: &[Annotation]) -> Vec<String> {
+    annotations.iter().map(|annotation| render(class_file, annotation)).collect()
+}
+
+#[test]
+fn visible_and_invisible_class_annotations() {
+    let class_file = load_fixture("Annotated");
+    let annotations = class_file.get_annotations().unwrap();
+    assert_eq!(render_all(&class_file, annotations.get_visible()), vec![r#"@LTag;(value="cls")"#]);
+    assert_eq!(render_all(&class_file, annotations.get_invisible()), vec!["@LInvis;()"]);
+    assert!(annotations.get_default().is_none());
+}
+
+#[test]
+fn arrays_and_type_annotations_on_fields() {
+    let class_file = load_fixture("Annotated");
+    let annotations = class_file.get_fields()[0].get_annotations(&class_file).unwrap();
+    assert_eq!(render_all(&class_file, annotations.get_visible()), vec![r#"@LTag;(value="f", nums={3I})"#]);
+    assert!(annotations.get_invisible().is_empty());
+    let type_annotations = annotations.get_visible_type();
+    assert_eq!(type_annotations.len(), 2);
+    // List<@Tag String>: a FIELD target with a path to the first type argument.
+    assert_eq!(type_annotations[0].get_target_type(), 0x13);
+    assert_eq!(type_annotations[0].get_target_info(), &TargetInfo::Empty);
+    let path: Vec<(u8, u8)> = type_annotations[0].get_target_path().iter().map(|entry| (entry.get_type_path_kind(), entry.get_type_argument_index())).collect();
+    assert_eq!(path, vec![(3, 0)]);
+    assert_eq!(render(&class_file, type_annotations[0].get_annotation()), "@LTag;()");
+    assert!(type_annotations[1].get_target_path().is_empty());
+}
+
+#[test]
+fn class_and_enum_values_on_methods() {
+    let class_file = load_fixture("Annotated");
+    let m = &class_file.get_methods()[1];
+    let annotations = m.get_annotations(&class_file).unwrap();
+    assert_eq!(render_all(&class_file, annotations.get_visible()), vec!["@LTag;(k=Ljava/lang/String;.class, e=Ljava/lang/annotation/ElementType;.FIELD)"]);
+    let targets: Vec<&TargetInfo> = annotations.get_visible_type().iter().map(|annotation| annotation.get_target_info()).collect();
+    assert_eq!(targets, vec![&TargetInfo::Throws { throws_type_index: 0 }, &TargetInfo::FormalParameter { formal_parameter_index: 0 }]);
+}
+
+#[test]
+fn visible_and_invisible_parameter_annotations() {
+    let class_file = load_fixture("Annotated");
+    let annotations = class_file.get_methods()[1].get_annotations(&class_file).unwrap();
+    let visible: Vec<Vec<String>> = annotations.get_visible_parameters().iter().map(|parameter| render_all(&class_file, parameter)).collect();
+    assert_eq!(visible, vec![vec![String::from(r#"@LTag;(value="p")"#)], vec![]]);
+    let invisible: Vec<Vec<String>> = annotations.get_invisible_parameters().iter().map(|parameter| render_all(&class_file, parameter)).collect();
+    assert_eq!(invisible, vec![vec![], vec![String::from("@LInvis;()")]]);
+}
+
+#[test]
+fn nested_annotations_and_arrays_of_enums() {
+    let class_file = load_fixture("Tag");
+    let annotations = class_file.get_annotations().unwrap();
+    assert_eq!(render_all(&class_file, annotations.get_visible()), vec![
+        "@Ljava/lang/annotation/Retention;(value=Ljava/lang/annotation/RetentionPolicy;.RUNTIME)",
+        "@Ljava/lang/annotation/Target;(value={Ljava/lang/annotation/ElementType;.TYPE, Ljava/lang/annotation/ElementType;.METHOD, \
+            Ljava/lang/annotation/ElementType;.FIELD, Ljava/lang/annotation/ElementType;.PARAMETER, Ljava/lang/annotation/ElementType;.TYPE_USE})",
+    ]);
+}
+
+#[test]
+fn element_defaults() {
+    let class_file = load_fixture("Tag");
+    let defaults: Vec<String> = class_file.get_methods().iter().map(|method| {
+        render_value(&class_file, method.get_annotations(&class_file).unwrap().get_default().unwrap())
+    }).collect();
+    assert_eq!(defaults, vec![
+        r#""x""#,
+        "{1I, 2I}",
+        "Ljava/lang/Object;.class",
+        "Ljava/lang/annotation/ElementType;.TYPE",
+        "@Ljava/lang/annotation/Retention;(value=Ljava/lang/annotation/RetentionPolicy;.CLASS)",
+    ]);
+}
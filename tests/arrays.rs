@@ -0,0 +1,61 @@
+mod common;
+
+use std::process::Output;
+
+fn run(main_class: &str) -> Output {
+    common::run("arrays", &[main_class])
+}
+
+fn assert_throws(main_class: &str, exception: &str) {
+    let output = run(main_class);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains(&format!("Exception in thread \"main\" {}", exception)),
+        "{}: {}", main_class, String::from_utf8_lossy(&output.stderr));
+}
+
+fn assert_prints(main_class: &str, expected: &[&str]) {
+    common::assert_prints("arrays", main_class, expected);
+}
+
+#[test]
+fn int_arrays() {
+    assert_prints("Arrays", &["5", "30"]);
+}
+
+#[test]
+fn other_primitive_component_types() {
+    // byte[], char[], short[], boolean[], long[], float[] and double[]
+    assert_prints("PrimitiveArrays", &["0", "-56", "a", "-3", "false", "true", "0", "0.0", "0.0", "3"]);
+}
+
+#[test]
+fn arrays_of_references_and_of_arrays() {
+    // String[], int[][] from multianewarray and anewarray, and String[][][]
+    assert_prints("ReferenceArrays", &["one", "3", "4", "7", "0", "null", "16", "3", "null"]);
+}
+
+#[test]
+fn covariant_stores() {
+    assert_prints("CovariantStores", &["square"]);
+}
+
+#[test]
+fn checks_for_null() {
+    assert_throws("NullArrays", "java.lang.NullPointerException: Cannot read the array length");
+}
+
+#[test]
+fn checks_indexes() {
+    assert_throws("OutOfBounds", "java.lang.ArrayIndexOutOfBoundsException: Index 3 out of bounds for length 3");
+}
+
+#[test]
+fn checks_sizes() {
+    assert_throws("NegativeSize", "java.lang.NegativeArraySizeException: -1");
+}
+
+#[test]
+fn checks_stored_types() {
+    assert_throws("WrongStore", "java.lang.ArrayStoreException: Shape");
+    assert_throws("WrongArrayStore", "java.lang.ArrayStoreException: [Ljava.lang.Object;");
+}
@@ -0,0 +1,239 @@
+use std::fs;
+use rust_jvm::class_file::{self, attribute::{self, Attribute, StackMapFrame, VerificationType}, attribute_info::AttributeInfo, ClassFile};
+use rust_jvm::util::file;
+
+fn load_fixture(name: &str) -> ClassFile {
+    let path = format!("{}/java/test/{}.class", env!("CARGO_MANIFEST_DIR"), name);
+    let bytes = fs::read(&path).unwrap_or_else(|e| panic!("Can't read fixture {}: {}", path, e));
+    class_file::parse_file(&mut file::File::new(bytes)).unwrap()
+}
+
+/// The decoded attribute called `name` among `attributes`.
+fn find(class_file: &ClassFile, attributes: &[AttributeInfo], name: &str) -> Attribute {
+    let attribute_info = attributes.iter()
+        .find(|attribute| class_file.get_utf8(attribute.get_attribute_name_index() as usize).unwrap() == name)
+        .unwrap_or_else(|| panic!("no {} attribute", name));
+    attribute_info.to_attribute(class_file).unwrap()
+}
+
+fn field_attributes(class_file: &ClassFile, field_name: &str) -> Vec<AttributeInfo> {
+    class_file.get_fields().iter()
+        .find(|field| class_file.get_utf8(field.get_name_index() as usize).unwrap() == field_name)
+        .unwrap().get_attributes()
+}
+
+fn method_attributes(class_file: &ClassFile, method_name: &str) -> Vec<AttributeInfo> {
+    class_file.get_methods().iter()
+        .find(|method| class_file.get_utf8(method.get_name_index() as usize).unwrap() == method_name)
+        .unwrap().get_attributes()
+}
+
+fn code_attributes(class_file: &ClassFile, method_name: &str) -> Vec<AttributeInfo> {
+    match find(class_file, &method_attributes(class_file, method_name), "Code") {
+        Attribute::Code(code_attribute) => code_attribute.get_attributes(),
+        attribute => panic!("{:?} is no Code attribute", attribute),
+    }
+}
+
+/// The index of the `Class` constant naming `name`.
+fn class_index(class_file: &ClassFile, name: &str) -> u16 {
+    (1..).find(|&index| class_file.get_name_of_class(index).is_ok_and(|class_name| class_name == name)).unwrap() as u16
+}
+
+fn class_names(class_file: &ClassFile, indices: &[u16]) -> Vec<String> {
+    indices.iter().map(|&index| class_file.get_name_of_class(index as usize).unwrap()).collect()
+}
+
+#[test]
+fn constant_value() {
+    let class_file = load_fixture("Attributes");
+    match find(&class_file, &field_attributes(&class_file, "K"), "ConstantValue") {
+        Attribute::ConstantValue(index) => assert!(matches!(class_file.get_constant(index as usize).unwrap(), class_file::const_type::ConstType::ConstantInteger(5))),
+        attribute => panic!("{:?}", attribute),
+    }
+}
+
+#[test]
+fn signatures_and_deprecation() {
+    let class_file = load_fixture("Attributes");
+    let items = field_attributes(&class_file, "items");
+    assert!(matches!(find(&class_file, &items, "Deprecated"), Attribute::Deprecated));
+    match find(&class_file, &items, "Signature") {
+        Attribute::Signature(index) => assert_eq!(class_file.get_utf8(index as usize).unwrap(), "Ljava/util/List<TT;>;"),
+        attribute => panic!("{:?}", attribute),
+    }
+    match find(&class_file, class_file.get_attributes(), "Signature") {
+        Attribute::Signature(index) => assert_eq!(class_file.get_utf8(index as usize).unwrap(), "<T::Ljava/lang/Comparable<TT;>;>Ljava/lang/Object;"),
+        attribute => panic!("{:?}", attribute),
+    }
+    match find(&class_file, class_file.get_attributes(), "SourceFile") {
+        Attribute::SourceFile(index) => assert_eq!(class_file.get_utf8(index as usize).unwrap(), "Attributes.java"),
+        attribute => panic!("{:?}", attribute),
+    }
+}
+
+#[test]
+fn exceptions_and_method_parameters() {
+    let class_file = load_fixture("Attributes");
+    let m = method_attributes(&class_file, "m");
+    match find(&class_file, &m, "Exceptions") {
+        Attribute::Exceptions(exceptions) => assert_eq!(class_names(&class_file, &exceptions), vec!["java/lang/Exception", "java/io/IOException"]),
+        attribute => panic!("{:?}", attribute),
+    }
+    match find(&class_file, &m, "MethodParameters") {
+        Attribute::MethodParameters(parameters) => {
+            let names: Vec<String> = parameters.iter().map(|parameter| class_file.get_utf8(parameter.get_name_index() as usize).unwrap()).collect();
+            assert_eq!(names, vec!["t", "q"]);
+            assert!(parameters.iter().all(|parameter| parameter.get_access_flags().get_modifiers().is_empty()));
+        }
+        attribute => panic!("{:?}", attribute),
+    }
+}
+
+#[test]
+fn line_numbers_and_local_variables() {
+    let class_file = load_fixture("Attributes");
+    let code = code_attributes(&class_file, "m");
+    match find(&class_file, &code, "LineNumberTable") {
+        Attribute::LineNumberTable(lines) => {
+            let lines: Vec<(u16, u16)> = lines.iter().map(|line| (line.get_start_pc(), line.get_line_number())).collect();
+            assert_eq!(lines, vec![(0, 8), (7, 9), (17, 10), (48, 11)]);
+        }
+        attribute => panic!("{:?}", attribute),
+    }
+    match find(&class_file, &code, "LocalVariableTable") {
+        Attribute::LocalVariableTable(variables) => {
+            let i = &variables[0];
+            assert_eq!((i.get_start_pc(), i.get_length(), i.get_index()), (20, 28, 5));
+            assert_eq!(class_file.get_utf8(i.get_name_index() as usize).unwrap(), "i");
+            assert_eq!(class_file.get_utf8(i.get_descriptor_index() as usize).unwrap(), "I");
+            assert_eq!(variables.len(), 6);
+        }
+        attribute => panic!("{:?}", attribute),
+    }
+    match find(&class_file, &code, "LocalVariableTypeTable") {
+        Attribute::LocalVariableTypeTable(variables) => {
+            let signatures: Vec<String> = variables.iter().map(|variable| class_file.get_utf8(variable.get_descriptor_index() as usize).unwrap()).collect();
+            assert_eq!(signatures, vec!["LAttributes<TT;>;", "TT;"]);
+        }
+        attribute => panic!("{:?}", attribute),
+    }
+}
+
+#[test]
+fn stack_map_table() {
+    let class_file = load_fixture("Attributes");
+    match find(&class_file, &code_attributes(&class_file, "m"), "StackMapTable") {
+        Attribute::StackMapTable(frames) => {
+            let object = |name: &str| VerificationType::Object(class_index(&class_file, name));
+            assert_eq!(frames, vec![
+                StackMapFrame::Append { offset_delta: 20, locals: vec![object("java/lang/Runnable"), object("java/lang/Object"), VerificationType::Integer] },
+                StackMapFrame::Same { offset_delta: 15 },
+                StackMapFrame::Same { offset_delta: 5 },
+                StackMapFrame::Chop { offset_delta: 5, chopped: 1 },
+            ]);
+        }
+        attribute => panic!("{:?}", attribute),
+    }
+}
+
+#[test]
+fn extended_stack_map_frames() {
+    let class_file = load_fixture("StackMaps");
+    match find(&class_file, &code_attributes(&class_file, "extended"), "StackMapTable") {
+        Attribute::StackMapTable(frames) => assert_eq!(frames, vec![
+            StackMapFrame::SameExtended { offset_delta: 67 },
+            StackMapFrame::SameLocals1StackItemExtended { offset_delta: 68, stack: VerificationType::Integer },
+            StackMapFrame::Full { offset_delta: 0, locals: vec![VerificationType::Integer; 2], stack: vec![VerificationType::Integer; 2] },
+        ]),
+        attribute => panic!("{:?}", attribute),
+    }
+}
+
+#[test]
+fn nest_members_permitted_subclasses_and_inner_classes() {
+    let class_file = load_fixture("Attributes");
+    match find(&class_file, class_file.get_attributes(), "NestMembers") {
+        Attribute::NestMembers(members) => assert_eq!(class_names(&class_file, &members), vec!["Attributes$Point", "Attributes$Sub", "Attributes$1"]),
+        attribute => panic!("{:?}", attribute),
+    }
+    match find(&class_file, class_file.get_attributes(), "PermittedSubclasses") {
+        Attribute::PermittedSubclasses(subclasses) => assert_eq!(class_names(&class_file, &subclasses), vec!["Attributes$Sub"]),
+        attribute => panic!("{:?}", attribute),
+    }
+    match find(&class_file, class_file.get_attributes(), "InnerClasses") {
+        Attribute::InnerClasses(inner_classes) => {
+            let anonymous = &inner_classes[0];
+            assert_eq!(class_file.get_name_of_class(anonymous.get_inner_class_info_index() as usize).unwrap(), "Attributes$1");
+            assert_eq!((anonymous.get_outer_class_info_index(), anonymous.get_inner_name_index()), (0, 0));
+            let sub = &inner_classes[2];
+            assert_eq!(class_file.get_name_of_class(sub.get_inner_class_info_index() as usize).unwrap(), "Attributes$Sub");
+            assert_eq!(class_file.get_name_of_class(sub.get_outer_class_info_index() as usize).unwrap(), "Attributes");
+            assert_eq!(class_file.get_utf8(sub.get_inner_name_index() as usize).unwrap(), "Sub");
+            assert_eq!(sub.get_inner_class_access_flags().get_modifiers(), vec!["public", "static", "final"]);
+            assert_eq!(inner_classes.len(), 4);
+        }
+        attribute => panic!("{:?}", attribute),
+    }
+}
+
+#[test]
+fn bootstrap_methods() {
+    let class_file = load_fixture("Attributes");
+    match find(&class_file, class_file.get_attributes(), "BootstrapMethods") {
+        Attribute::BootstrapMethods(methods) => {
+            assert_eq!(methods.len(), 1);
+            assert_eq!(methods[0].get_bootstrap_method_ref(), 88);
+            assert_eq!(methods[0].get_bootstrap_arguments(), &vec![95, 96, 95]);
+        }
+        attribute => panic!("{:?}", attribute),
+    }
+}
+
+#[test]
+fn record_components() {
+    let class_file = load_fixture("Attributes$Point");
+    match find(&class_file, class_file.get_attributes(), "Record") {
+        Attribute::Record(components) => {
+            let components: Vec<(String, String)> = components.iter().map(|component| (
+                class_file.get_utf8(component.get_name_index() as usize).unwrap(),
+                class_file.get_utf8(component.get_descriptor_index() as usize).unwrap(),
+            )).collect();
+            assert_eq!(components, vec![(String::from("x"), String::from("I")), (String::from("y"), String::from("I"))]);
+        }
+        attribute => panic!("{:?}", attribute),
+    }
+    match find(&class_file, class_file.get_attributes(), "NestHost") {
+        Attribute::NestHost(index) => assert_eq!(class_file.get_name_of_class(index as usize).unwrap(), "Attributes"),
+        attribute => panic!("{:?}", attribute),
+    }
+}
+
+#[test]
+fn enclosing_method() {
+    let class_file = load_fixture("Attributes$1");
+    match find(&class_file, class_file.get_attributes(), "EnclosingMethod") {
+        Attribute::EnclosingMethod { class_index, method_index } => {
+            assert_eq!(class_file.get_name_of_class(class_index as usize).unwrap(), "Attributes");
+            assert_eq!(class_file.get_name_of_member(method_index as usize).unwrap(), "m");
+            assert_eq!(class_file.get_description_of_member(method_index as usize).unwrap(), "(Ljava/lang/Comparable;I)V");
+        }
+        attribute => panic!("{:?}", attribute),
+    }
+}
+
+#[test]
+fn unknown_attributes_keep_their_name() {
+    match attribute::parse_file(&mut file::File::new(vec![1, 2, 3]), "Custom").unwrap() {
+        Attribute::Unknown { name, info } => {
+            assert_eq!(name, "Custom");
+            assert_eq!(info, vec![1, 2, 3]);
+        }
+        attribute => panic!("{:?}", attribute),
+    }
+}
+
+#[test]
+fn rejects_trailing_bytes() {
+    assert!(attribute::parse_file(&mut file::File::new(vec![0, 1, 2]), "ConstantValue").is_err());
+}
@@ -0,0 +1,127 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use rust_jvm::class_file::{self, class_format_error::{ClassFormatError, ClassFormatErrorKind}, parse_error::ParseErrorKind};
+use rust_jvm::util::file;
+
+const FIXTURES: &[&str] = &[
+    "java/main/HelloWorld.class",
+    "java/test/Annotated.class",
+    "java/test/Attributes.class",
+    "java/test/Attributes$1.class",
+    "java/test/Attributes$Point.class",
+    "java/test/Attributes$Sub.class",
+    "java/test/Dispatch.class",
+    "java/test/Instructions.class",
+    "java/test/Instructions$Shape.class",
+    "java/test/Invis.class",
+    "java/test/ModifiedUtf8.class",
+    "java/test/StackMaps.class",
+    "java/test/Tag.class",
+    "java/test/WideConstants.class",
+    "java/test/exceptions/CatchThrown.class",
+    "java/test/exceptions/Finally.class",
+    "java/test/condy/Condy.class",
+    "java/test/module/module-info.class",
+    "java/test/module/fixtures/api/Api.class",
+    "java/test/module/open/module-info.class",
+    "java/test/module/open/p/q/R.class",
+];
+
+/// access_flags, this_class and super_class of HelloWorld: `ACC_PUBLIC | ACC_SUPER`, #14 and #2.
+const HELLO_WORLD_HEADER: &[u8] = &[0x00, 0x21, 0x00, 0x0e, 0x00, 0x02];
+
+fn read(name: &str) -> Vec<u8> {
+    fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join(name)).unwrap()
+}
+
+fn validate(bytes: Vec<u8>) -> Vec<ClassFormatError> {
+    class_file::parse_file(&mut file::File::new(bytes)).unwrap().validate()
+}
+
+fn hello_world_header(bytes: &[u8]) -> usize {
+    bytes.windows(HELLO_WORLD_HEADER.len()).position(|window| window == HELLO_WORLD_HEADER).unwrap()
+}
+
+#[test]
+fn fixtures_are_well_formed() {
+    for fixture in FIXTURES {
+        assert_eq!(validate(read(fixture)), vec![], "{}", fixture);
+    }
+}
+
+#[test]
+fn reports_every_header_error() {
+    let mut bytes = read("java/main/HelloWorld.class");
+    bytes[3] = 0xBF;
+    bytes[6..8].copy_from_slice(&70u16.to_be_bytes());
+    let errors = validate(bytes);
+    assert_eq!(errors, vec![
+        ClassFormatError::new("class header", ClassFormatErrorKind::InvalidMagic(0xCAFEBABF)),
+        ClassFormatError::new("class header", ClassFormatErrorKind::UnsupportedVersion { major_version: 70, minor_version: 0 }),
+    ]);
+    assert_eq!(errors[1].to_string(), "java.lang.ClassFormatError: class header: unsupported class file version 70.0");
+}
+
+#[test]
+fn this_class_must_be_a_class_constant() {
+    let mut bytes = read("java/main/HelloWorld.class");
+    let header = hello_world_header(&bytes);
+    // #16 is the Utf8 HelloWorld that #14 names.
+    bytes[header + 3] = 16;
+    let errors = validate(bytes);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(errors[0].get_location(), "this_class");
+    assert!(matches!(errors[0].get_kind(), ClassFormatErrorKind::Parse(error)
+        if *error.get_kind() == ParseErrorKind::UnexpectedConstant { index: 16, expected: "class" }));
+}
+
+#[test]
+fn interface_flags() {
+    let mut bytes = read("java/main/HelloWorld.class");
+    let header = hello_world_header(&bytes);
+    bytes[header] = 0x02;
+    let errors = validate(bytes);
+    assert_eq!(errors[0], ClassFormatError::new("class header", ClassFormatErrorKind::IllegalAccessFlags {
+        flags: 0x0221,
+        reason: "an interface must be abstract",
+    }));
+    // An interface has no constructors, and its static methods need no other flags since version 52.
+    assert_eq!(errors[1].get_location(), "method <init>()V");
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+}
+
+#[test]
+fn illegal_method_name() {
+    let mut bytes = read("java/main/HelloWorld.class");
+    let position = bytes.windows(6).position(|window| window == b"\x01\x00\x03fac").unwrap();
+    bytes[position + 4] = b';';
+    let errors = validate(bytes);
+    let invalid_name = ClassFormatErrorKind::InvalidName { kind: "method", name: "f;c".to_string() };
+    assert_eq!(errors, vec![
+        ClassFormatError::new("constant #13", invalid_name.clone()),
+        ClassFormatError::new("method f;c(I)I", invalid_name),
+    ]);
+}
+
+#[test]
+fn check_mode_lists_errors() {
+    let run = |path: &Path| Command::new(env!("CARGO_BIN_EXE_rust_jvm")).arg("check").arg(path).output().unwrap();
+    let output = run(&Path::new(env!("CARGO_MANIFEST_DIR")).join("java/main/HelloWorld.class"));
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+
+    let mut bytes = read("java/main/HelloWorld.class");
+    bytes[6..8].copy_from_slice(&70u16.to_be_bytes());
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("UnsupportedVersion.class");
+    fs::write(&path, bytes).unwrap();
+    let output = run(&path);
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "java.lang.ClassFormatError: class header: unsupported class file version 70.0\n");
+
+    // Running the class rejects it before any code is executed.
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_jvm")).arg(&path).output().unwrap();
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("java.lang.ClassFormatError: class header: unsupported class file version 70.0"));
+}
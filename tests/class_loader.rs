@@ -0,0 +1,114 @@
+mod common;
+
+use std::env;
+use std::path::Path;
+use std::process::{Command, Output};
+use common::printed;
+
+fn run(class_path: &[&str], main_class: &str) -> Output {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("java/test/classpath");
+    let class_path = env::join_paths(class_path.iter().map(|directory| root.join(directory))).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_rust_jvm")).arg("-cp").arg(class_path).arg(main_class).output().unwrap()
+}
+
+#[test]
+fn resolves_methods_in_other_classes() {
+    let output = run(&["app", "lib"], "Main");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(printed(&output), vec!["42", "36"]);
+}
+
+#[test]
+fn first_class_path_entry_wins() {
+    let output = run(&["app", "old", "lib"], "Main");
+    assert_eq!(printed(&output), vec!["42"]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("java.lang.NoSuchMethodError: util.MathUtil.square(I)I"));
+}
+
+#[test]
+fn missing_class() {
+    let output = run(&["app"], "Main");
+    assert!(!output.status.success());
+    assert!(printed(&output).is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("java.lang.NoClassDefFoundError: util/MathUtil"));
+
+    let output = run(&["lib"], "Main");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("java.lang.NoClassDefFoundError: Main"));
+}
+
+fn run_jar(jar: &str) -> Output {
+    let jar = Path::new(env!("CARGO_MANIFEST_DIR")).join("java/test/jar").join(jar);
+    Command::new(env!("CARGO_BIN_EXE_rust_jvm")).arg("-jar").arg(jar).output().unwrap()
+}
+
+#[test]
+fn runs_the_main_class_of_a_jar() {
+    // app.jar is deflated and names the stored lib/util.jar in its Class-Path.
+    let output = run_jar("app.jar");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(printed(&output), vec!["42", "36"]);
+
+    let output = run_jar("lib/util.jar");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no main manifest attribute"));
+}
+
+#[test]
+fn loads_classes_from_jars_on_the_class_path() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("java/test");
+    let class_path = env::join_paths([root.join("classpath/app"), root.join("jar/lib/util.jar")]).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_jvm")).arg("-cp").arg(class_path).arg("Main").output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(printed(&output), vec!["42", "36"]);
+}
+
+#[test]
+fn multi_release_jar_uses_the_highest_supported_version() {
+    // The root has the old MathUtil without square, versions/9 the current one and versions/21 a
+    // class file this VM could not load.
+    let output = run_jar("multi-release.jar");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(printed(&output), vec!["42", "36"]);
+}
+
+fn run_resolution(class_path: &[&str]) -> Output {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("java/test/resolution");
+    let class_path = env::join_paths(class_path.iter().map(|directory| root.join(directory))).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_rust_jvm")).arg("-cp").arg(class_path).arg("Main").output().unwrap()
+}
+
+#[test]
+fn resolves_by_descriptor_in_superclasses() {
+    // Sub.fac is inherited from Base, and Base.add is overloaded.
+    let output = run_resolution(&[""]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(printed(&output), vec!["120", "5", "5", "1", "7"]);
+}
+
+#[test]
+fn resolves_default_methods_of_superinterfaces() {
+    // Main invokes the static Impl.hello(), but the replaced Impl only inherits a default method.
+    let output = run_resolution(&["default_method", ""]);
+    assert_eq!(printed(&output), vec!["120", "5", "5", "1"]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("java.lang.IncompatibleClassChangeError: Expected static method Greeter.hello()I"));
+
+    let output = run_resolution(&["most_specific", ""]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("java.lang.IncompatibleClassChangeError: Expected static method Loud.hello()I"));
+}
+
+#[test]
+fn methodref_to_an_interface() {
+    let output = run_resolution(&["interface", ""]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(printed(&output), vec!["120", "5", "5"]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("java.lang.IncompatibleClassChangeError: Found interface Util, but class was expected"));
+}
+
+#[test]
+fn superclass_cycle() {
+    // A extends B and B extends A, which javac only compiles against stubs.
+    let output = common::run("circularity", &["Main"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(printed(&output), vec!["1"]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("java.lang.ClassCircularityError: A"));
+}
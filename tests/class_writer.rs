@@ -0,0 +1,43 @@
+use std::fs;
+use std::path::Path;
+use rust_jvm::class_file::{self, attribute::Attribute};
+use rust_jvm::util::file;
+
+fn fixtures() -> Vec<(String, Vec<u8>)> {
+    let mut fixtures = Vec::new();
+    for directory in ["java/main", "java/test"] {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join(directory);
+        for entry in fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|extension| extension == "class") {
+                fixtures.push((path.display().to_string(), fs::read(&path).unwrap()));
+            }
+        }
+    }
+    assert!(!fixtures.is_empty());
+    fixtures
+}
+
+#[test]
+fn unmodified_class_files_round_trip() {
+    for (path, bytes) in fixtures() {
+        let class_file = class_file::parse_file(&mut file::File::new(bytes.clone())).unwrap();
+        assert_eq!(class_file.to_bytes(), bytes, "{} did not round-trip", path);
+        let reparsed = class_file::parse_file(&mut file::File::new(class_file.to_bytes())).unwrap();
+        assert_eq!(reparsed.to_bytes(), bytes, "{} did not round-trip twice", path);
+    }
+}
+
+#[test]
+fn code_attributes_round_trip() {
+    for (path, bytes) in fixtures() {
+        let class_file = class_file::parse_file(&mut file::File::new(bytes)).unwrap();
+        for method in class_file.get_methods() {
+            for attribute_info in method.get_attributes() {
+                if let Attribute::Code(code_attribute) = attribute_info.to_attribute(&class_file).unwrap() {
+                    assert_eq!(&code_attribute.to_bytes(), attribute_info.get_info(), "Code attribute of {} did not round-trip", path);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Helpers shared by the tests that run the fixtures under `java/test` with the launcher.
+#![allow(dead_code)]
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Runs the launcher with `args`, on the class path of the fixtures in `java/test/<fixture>`.
+pub fn run(fixture: &str, args: &[&str]) -> Output {
+    let class_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("java/test").join(fixture);
+    Command::new(env!("CARGO_BIN_EXE_rust_jvm")).arg("-cp").arg(class_path).args(args).output().unwrap()
+}
+
+/// The lines the program printed to standard output.
+pub fn printed(output: &Output) -> Vec<String> {
+    String::from_utf8_lossy(&output.stdout).lines().map(String::from).collect()
+}
+
+
+/// Runs `main_class` of the fixtures in `java/test/<fixture>` and checks that it prints `expected` and exits normally.
+pub fn assert_prints(fixture: &str, main_class: &str, expected: &[&str]) {
+    let output = run(fixture, &[main_class]);
+    assert_eq!(printed(&output), expected, "{}", main_class);
+    assert_eq!(output.status.code(), Some(0), "{}: {}", main_class, String::from_utf8_lossy(&output.stderr));
+}
+
+/// Checks the sections a fixture printed, each a line naming an instruction followed by its result for each of `operands`, in
+/// order. `sections` gives the results of each instruction.
+pub fn assert_sections(printed: &[String], operands: &[&str], sections: &[(&str, &[&str])]) {
+    for (instruction, results) in sections {
+        assert_eq!(results.len(), operands.len(), "{}", instruction);
+        let start = printed.iter().position(|line| line == instruction).unwrap_or_else(|| panic!("no {} section", instruction)) + 1;
+        let printed_results = printed.get(start..start + operands.len()).unwrap_or_else(|| panic!("{} section cut short", instruction));
+        for ((operand, expected), result) in operands.iter().zip(*results).zip(printed_results) {
+            assert_eq!(result, expected, "{} of {}", instruction, operand);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::fs;
+use rust_jvm::class_file::{self, const_type::ConstType, ClassFile};
+use rust_jvm::util::file;
+
+fn load_fixture(name: &str) -> ClassFile {
+    let path = format!("{}/java/test/{}.class", env!("CARGO_MANIFEST_DIR"), name);
+    let bytes = fs::read(&path).unwrap_or_else(|e| panic!("Can't read fixture {}: {}", path, e));
+    class_file::parse_file(&mut file::File::new(bytes)).unwrap()
+}
+
+#[test]
+fn long_and_double_occupy_two_slots() {
+    let class_file = load_fixture("WideConstants");
+    match class_file.get_constant(7).unwrap() {
+        ConstType::ConstantLong(high, low) => {
+            assert_eq!(((*high as u64) << 32 | *low as u64) as i64, 9876543210);
+        }
+        other => panic!("#7 should be a long constant, was {:?}", other),
+    }
+    assert!(matches!(class_file.get_constant(8).unwrap(), ConstType::Unusable));
+    match class_file.get_constant(9).unwrap() {
+        ConstType::ConstantDouble(high, low) => {
+            assert_eq!(f64::from_bits((*high as u64) << 32 | *low as u64), std::f64::consts::E);
+        }
+        other => panic!("#9 should be a double constant, was {:?}", other),
+    }
+    assert!(matches!(class_file.get_constant(10).unwrap(), ConstType::Unusable));
+}
+
+#[test]
+fn indices_after_wide_constants_match_javap() {
+    let class_file = load_fixture("WideConstants");
+    assert!(matches!(class_file.get_constant(11).unwrap(), ConstType::ConstantString(12)));
+    assert_eq!(class_file.get_utf8(12).unwrap(), "after wide constants");
+    assert_eq!(class_file.get_name_of_class(14).unwrap(), "java/lang/System");
+    assert_eq!(class_file.get_name_of_class(31).unwrap(), "WideConstants");
+    assert!(matches!(class_file.get_constant(36).unwrap(), ConstType::ConstantLong(_, _)));
+    assert!(matches!(class_file.get_constant(37).unwrap(), ConstType::Unusable));
+    assert!(matches!(class_file.get_constant(40).unwrap(), ConstType::ConstantDouble(_, _)));
+    assert!(matches!(class_file.get_constant(41).unwrap(), ConstType::Unusable));
+    assert_eq!(class_file.get_utf8(47).unwrap(), "WideConstants.java");
+    assert!(class_file.get_constant(48).is_err());
+}
+
+#[test]
+fn unusable_slot_is_not_a_utf8_constant() {
+    let class_file = load_fixture("WideConstants");
+    assert!(class_file.get_utf8(8).is_err());
+}
+
+#[test]
+fn module_and_package_constants() {
+    let class_file = load_fixture("module/module-info");
+    assert!(matches!(class_file.get_constant(6).unwrap(), ConstType::ConstantModule(7)));
+    assert_eq!(class_file.get_name_of_module(6).unwrap(), "fixtures");
+    assert_eq!(class_file.get_name_of_module(8).unwrap(), "java.base");
+    assert_eq!(class_file.get_name_of_module(11).unwrap(), "java.logging");
+    assert!(matches!(class_file.get_constant(13).unwrap(), ConstType::ConstantPackage(14)));
+    assert_eq!(class_file.get_name_of_package(13).unwrap(), "fixtures/api");
+    assert!(class_file.get_name_of_module(13).is_err());
+    assert!(class_file.get_name_of_package(6).is_err());
+}
+
+#[test]
+fn invoke_dynamic_constants() {
+    let class_file = load_fixture("module/fixtures/api/Api");
+    assert_eq!(class_file.get_invoke_dynamic(7).unwrap(), (0, 8));
+    assert_eq!(class_file.get_name_of_member(8).unwrap(), "makeConcatWithConstants");
+    assert_eq!(class_file.get_description_of_member(8).unwrap(), "(Ljava/lang/String;)Ljava/lang/String;");
+    assert!(class_file.get_dynamic(7).is_err());
+}
+
+#[test]
+fn dynamic_constants() {
+    let class_file = load_fixture("condy/Condy");
+    assert!(matches!(class_file.get_constant(15).unwrap(), ConstType::ConstantDynamic(0, 14)));
+    assert_eq!(class_file.get_dynamic(15).unwrap(), (0, 14));
+    assert_eq!(class_file.get_name_of_member(14).unwrap(), "value");
+    assert_eq!(class_file.get_description_of_member(14).unwrap(), "Ljava/lang/Object;");
+    assert!(class_file.get_invoke_dynamic(15).is_err());
+}
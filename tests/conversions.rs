@@ -0,0 +1,131 @@
+mod common;
+
+use common::printed;
+
+/// The ints the fixture converts, in order.
+const INTS: &[&str] = &[
+    "0", "1", "-1", "127", "-128", "32768", "65535", "65536", "0x1007f", "0x10080", "0x17fff", "0x18000", "-65537",
+    "16777217", "Integer.MAX_VALUE", "Integer.MIN_VALUE",
+];
+
+/// The longs the fixture converts, in order.
+const LONGS: &[&str] = &[
+    "0", "-1", "5000000005", "2147483648", "9007199254740993", "Long.MAX_VALUE", "Long.MIN_VALUE",
+];
+
+/// The floats the fixture converts, in order.
+const FLOATS: &[&str] = &[
+    "0.0", "-0.0", "1.1", "1.5", "-1.5", "2.5", "-2.9", "3.0e9", "-3.0e9", "1.0e20", "-1.0e20", "Float.MAX_VALUE",
+    "Float.MIN_VALUE", "NaN", "Infinity", "-Infinity",
+];
+
+/// The doubles the fixture converts, in order.
+const DOUBLES: &[&str] = &[
+    "0.0", "-0.0", "0.1", "1.5", "-1.5", "2147483647.9", "-2147483648.9", "3.0e9", "-3.0e9", "1.0e20", "-1.0e20",
+    "1.0e300", "Double.MIN_VALUE", "NaN", "Infinity", "-Infinity",
+];
+
+/// The results of each conversion of `INTS`.
+const INT_CONVERSIONS: &[(&str, &[&str])] = &[
+    ("i2l", &[
+        "0", "1", "-1", "127", "-128", "32768", "65535", "65536", "65663", "65664", "98303", "98304", "-65537",
+        "16777217", "2147483647", "-2147483648",
+    ]),
+    // JDK 17 prints more digits than needed for Integer.MAX_VALUE and Integer.MIN_VALUE.
+    ("i2f", &[
+        "0.0", "1.0", "-1.0", "127.0", "-128.0", "32768.0", "65535.0", "65536.0", "65663.0", "65664.0", "98303.0",
+        "98304.0", "-65537.0", "1.6777216E7", "2.1474836E9", "-2.1474836E9",
+    ]),
+    ("i2d", &[
+        "0.0", "1.0", "-1.0", "127.0", "-128.0", "32768.0", "65535.0", "65536.0", "65663.0", "65664.0", "98303.0",
+        "98304.0", "-65537.0", "1.6777217E7", "2.147483647E9", "-2.147483648E9",
+    ]),
+    ("i2b", &["0", "1", "-1", "127", "-128", "0", "-1", "0", "127", "-128", "-1", "0", "-1", "1", "-1", "0"]),
+    ("i2c", &[
+        "0", "1", "65535", "127", "65408", "32768", "65535", "0", "127", "128", "32767", "32768", "65535", "1",
+        "65535", "0",
+    ]),
+    ("i2s", &[
+        "0", "1", "-1", "127", "-128", "-32768", "-1", "0", "127", "128", "32767", "-32768", "-1", "1", "-1", "0",
+    ]),
+];
+
+/// The results of each conversion of `LONGS`.
+const LONG_CONVERSIONS: &[(&str, &[&str])] = &[
+    ("l2i", &["0", "-1", "705032709", "-2147483648", "1", "-1", "0"]),
+    // JDK 17 prints more digits than needed for 2147483648 and 9007199254740993.
+    ("l2f", &["0.0", "-1.0", "5.0E9", "2.1474836E9", "9.007199E15", "9.223372E18", "-9.223372E18"]),
+    ("l2d", &[
+        "0.0", "-1.0", "5.000000005E9", "2.147483648E9", "9.007199254740992E15", "9.223372036854776E18",
+        "-9.223372036854776E18",
+    ]),
+];
+
+/// The results of each conversion of `FLOATS`.
+const FLOAT_CONVERSIONS: &[(&str, &[&str])] = &[
+    ("f2i", &[
+        "0", "0", "1", "1", "-1", "2", "-2", "2147483647", "-2147483648", "2147483647", "-2147483648", "2147483647",
+        "0", "0", "2147483647", "-2147483648",
+    ]),
+    ("f2l", &[
+        "0", "0", "1", "1", "-1", "2", "-2", "3000000000", "-3000000000", "9223372036854775807",
+        "-9223372036854775808", "9223372036854775807", "0", "0", "9223372036854775807", "-9223372036854775808",
+    ]),
+    ("f2d", &[
+        "0.0", "-0.0", "1.100000023841858", "1.5", "-1.5", "2.5", "-2.9000000953674316", "3.0E9", "-3.0E9",
+        "1.0000000200408773E20", "-1.0000000200408773E20", "3.4028234663852886E38", "1.401298464324817E-45", "NaN",
+        "Infinity", "-Infinity",
+    ]),
+];
+
+/// The results of each conversion of `DOUBLES`.
+const DOUBLE_CONVERSIONS: &[(&str, &[&str])] = &[
+    ("d2i", &[
+        "0", "0", "0", "1", "-1", "2147483647", "-2147483648", "2147483647", "-2147483648", "2147483647",
+        "-2147483648", "2147483647", "0", "0", "2147483647", "-2147483648",
+    ]),
+    ("d2l", &[
+        "0", "0", "0", "1", "-1", "2147483647", "-2147483648", "3000000000", "-3000000000", "9223372036854775807",
+        "-9223372036854775808", "9223372036854775807", "0", "0", "9223372036854775807", "-9223372036854775808",
+    ]),
+    // JDK 17 prints more digits than needed for 2147483647.9 and -2147483648.9.
+    ("d2f", &[
+        "0.0", "-0.0", "0.1", "1.5", "-1.5", "2.1474836E9", "-2.1474836E9", "3.0E9", "-3.0E9", "1.0E20", "-1.0E20",
+        "Infinity", "0.0", "NaN", "Infinity", "-Infinity",
+    ]),
+];
+
+fn converted() -> Vec<String> {
+    let output = common::run("conversions", &["Conversions"]);
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+    printed(&output)
+}
+
+#[test]
+fn converts_ints() {
+    common::assert_sections(&converted(), INTS, INT_CONVERSIONS);
+}
+
+#[test]
+fn converts_longs() {
+    common::assert_sections(&converted(), LONGS, LONG_CONVERSIONS);
+}
+
+#[test]
+fn converts_floats() {
+    common::assert_sections(&converted(), FLOATS, FLOAT_CONVERSIONS);
+}
+
+#[test]
+fn converts_doubles() {
+    common::assert_sections(&converted(), DOUBLES, DOUBLE_CONVERSIONS);
+}
+
+#[test]
+fn prints_nothing_else() {
+    let tables = [(INTS, INT_CONVERSIONS), (LONGS, LONG_CONVERSIONS), (FLOATS, FLOAT_CONVERSIONS), (DOUBLES, DOUBLE_CONVERSIONS)];
+    let lines: usize = tables.iter()
+        .map(|(inputs, conversions)| conversions.len() * (1 + inputs.len()))
+        .sum();
+    assert_eq!(converted().len(), lines);
+}
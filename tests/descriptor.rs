@@ -0,0 +1,56 @@
+use rust_jvm::class_file::descriptor::{self, BaseType, FieldType, TypeArgument, ReferenceTypeSignature};
+
+#[test]
+fn field_descriptors() {
+    assert_eq!(descriptor::parse_field_descriptor("I").unwrap(), FieldType::Base(BaseType::Int));
+    let array = descriptor::parse_field_descriptor("[[Ljava/lang/String;").unwrap();
+    assert_eq!(array, FieldType::Array(Box::new(FieldType::Array(Box::new(FieldType::Object(String::from("java/lang/String")))))));
+    assert_eq!(array.to_string(), "java.lang.String[][]");
+    assert_eq!(array.get_descriptor(), "[[Ljava/lang/String;");
+    for invalid in ["", "V", "Q", "L;", "Ljava/lang/String", "II", "["] {
+        assert!(descriptor::parse_field_descriptor(invalid).is_err(), "{:?} was accepted", invalid);
+    }
+}
+
+#[test]
+fn method_descriptors_and_slots() {
+    let method = descriptor::parse_method_descriptor("(IJ[DLjava/lang/Object;D)V").unwrap();
+    assert_eq!(method.get_parameters().len(), 5);
+    assert_eq!(method.get_argument_slots(), 1 + 2 + 1 + 1 + 2);
+    assert_eq!(method.get_return_type(), None);
+    assert_eq!(method.get_return_slots(), 0);
+    let method = descriptor::parse_method_descriptor("()J").unwrap();
+    assert_eq!(method.get_return_slots(), 2);
+    for invalid in ["", "()", "(V)V", "(I", "I)V", "()VV"] {
+        assert!(descriptor::parse_method_descriptor(invalid).is_err(), "{:?} was accepted", invalid);
+    }
+}
+
+#[test]
+fn generic_signatures() {
+    let class = descriptor::parse_class_signature("<K:Ljava/lang/Object;V::Ljava/lang/Comparable<-TV;>;>Ljava/util/AbstractMap<TK;TV;>;Ljava/io/Serializable;").unwrap();
+    assert_eq!(class.get_type_parameters().len(), 2);
+    assert_eq!(class.get_type_parameters()[0].to_string(), "K");
+    assert_eq!(class.get_type_parameters()[1].to_string(), "V extends java.lang.Comparable<? super V>");
+    assert_eq!(class.get_superclass().to_string(), "java.util.AbstractMap<K, V>");
+    assert_eq!(class.get_interfaces()[0].get_class_name(), "java/io/Serializable");
+
+    let method = descriptor::parse_method_signature("<E:Ljava/lang/Exception;>(TT;[Ljava/util/List<*>;I)V^TE;^Ljava/io/IOException;").unwrap();
+    assert_eq!(method.get_parameters().iter().map(|parameter| parameter.to_string()).collect::<Vec<String>>(), vec!["T", "java.util.List<?>[]", "int"]);
+    assert!(method.get_result().is_none());
+    assert_eq!(method.get_throws()[0], ReferenceTypeSignature::TypeVariable(String::from("E")));
+
+    let field = descriptor::parse_field_signature("Ljava/util/Map<TK;TV;>.Entry<+Ljava/lang/Number;>;").unwrap();
+    assert_eq!(field.to_string(), "java.util.Map<K, V>.Entry<? extends java.lang.Number>");
+    match field {
+        ReferenceTypeSignature::Class(class_type) => {
+            assert_eq!(class_type.get_class_name(), "java/util/Map$Entry");
+            assert!(matches!(class_type.get_segments()[1].get_type_arguments()[0], TypeArgument::Extends(_)));
+        }
+        other => panic!("expected a class type, got {:?}", other),
+    }
+    for invalid in ["", "TT", "Ljava/util/List<>;", "I"] {
+        assert!(descriptor::parse_field_signature(invalid).is_err(), "{:?} was accepted", invalid);
+    }
+    assert!(descriptor::parse_class_signature("<>Ljava/lang/Object;").is_err());
+}
@@ -0,0 +1,60 @@
+use std::fs;
+use std::path::Path;
+use rust_jvm::class_file;
+use rust_jvm::disasm::{self, Options};
+use rust_jvm::util::file;
+
+/// Compares `disasm -p` with `javap -p -c -v` output recorded in `tests/golden`. The recordings
+/// start after the "Classfile" and "Last modified" lines, which depend on the checkout.
+fn assert_matches_javap(fixture: &str, golden: &str) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let path = root.join(fixture);
+    let bytes = fs::read(&path).unwrap();
+    let class_file = class_file::parse_file(&mut file::File::new(bytes.clone())).unwrap();
+    let output = disasm::disassemble(path.to_str().unwrap(), &bytes, &class_file, &Options { show_private: true }).unwrap();
+    let expected = fs::read_to_string(root.join("tests/golden").join(golden)).unwrap();
+    let actual: Vec<&str> = output.lines().skip(2).collect();
+    let expected: Vec<&str> = expected.lines().collect();
+    for (line, (actual, expected)) in actual.iter().zip(&expected).enumerate() {
+        assert_eq!(actual, expected, "{} differs from {} at line {}", fixture, golden, line + 3);
+    }
+    assert_eq!(actual.len(), expected.len(), "{} and {} differ in length", fixture, golden);
+}
+
+#[test]
+fn interface_with_default_method() {
+    assert_matches_javap("java/test/objects/Shape.class", "objects_Shape.javap");
+    assert_matches_javap("java/test/statics/WithDefault.class", "statics_WithDefault.javap");
+}
+
+#[test]
+fn several_interfaces() {
+    assert_matches_javap("java/test/statics/Impl.class", "statics_Impl.javap");
+    assert_matches_javap("java/test/resolution/most_specific/Impl.class", "resolution_most_specific_Impl.javap");
+}
+
+#[test]
+fn generics_and_attributes() {
+    assert_matches_javap("java/test/Attributes.class", "Attributes.javap");
+}
+
+#[test]
+fn annotations() {
+    assert_matches_javap("java/test/Annotated.class", "Annotated.javap");
+}
+
+#[test]
+fn instructions() {
+    assert_matches_javap("java/test/Instructions.class", "Instructions.javap");
+}
+
+#[test]
+fn stack_map_frames() {
+    assert_matches_javap("java/test/StackMaps.class", "StackMaps.javap");
+}
+
+#[test]
+fn modules() {
+    assert_matches_javap("java/test/module/module-info.class", "module_module-info.javap");
+    assert_matches_javap("java/test/module/open/module-info.class", "module_open_module-info.javap");
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use std::process::Output;
+use common::printed;
+
+fn run(main_class: &str) -> Output {
+    common::run("exceptions", &[main_class])
+}
+
+fn run_caught(main_class: &str) -> Vec<String> {
+    let output = run(main_class);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    printed(&output)
+}
+
+#[test]
+fn catches_arithmetic_exceptions() {
+    // The long remainder is caught as RuntimeException.
+    assert_eq!(run_caught("CatchArithmetic"), vec!["/ by zero", "/ by zero", "done"]);
+}
+
+#[test]
+fn catches_array_exceptions() {
+    assert_eq!(run_caught("CatchArrays"), vec!["Index 2 out of bounds for length 2", "-1", "CatchArrays", "null array"]);
+}
+
+#[test]
+fn catches_thrown_objects_by_class_and_superclass() {
+    // Failure extends the library class IllegalStateException and keeps its own field.
+    assert_eq!(run_caught("CatchThrown"), vec!["deep", "7", "deep", "library", "null", "null throwable"]);
+}
+
+#[test]
+fn runs_finally_blocks() {
+    assert_eq!(run_caught("Finally"), vec!["finally", "deep", "2"]);
+}
+
+#[test]
+fn catches_resolution_errors() {
+    // CatchLinkage was compiled against a Lib with a static removed() and a static changed().
+    assert_eq!(run_caught("CatchLinkage"), vec!["Lib.removed()V", "Expected static method Lib.changed()V", "linkage"]);
+}
+
+#[test]
+fn catches_stack_overflow() {
+    assert_eq!(run_caught("CatchStackOverflow"), vec!["stack overflow", "stack overflow"]);
+}
+
+#[test]
+fn uncaught_thrown_objects() {
+    let output = run("Uncaught");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(printed(&output), vec!["1"]);
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "Exception in thread \"main\" Failure: uncaught\n");
+}
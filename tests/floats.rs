@@ -0,0 +1,43 @@
+mod common;
+
+fn assert_prints(main_class: &str, expected: &[&str]) {
+    common::assert_prints("floats", main_class, expected);
+}
+
+#[test]
+fn float_constants_arithmetic_and_special_values() {
+    assert_prints("Floats", &["0.0", "2.0", "0.33333334", "0.3", "2.0E10", "0.99999", "-0.33333334", "NaN", "Infinity", "-Infinity", "-0.0", "Infinity", "0.0"]);
+}
+
+#[test]
+fn frem() {
+    assert_prints("FloatRemainders", &["1.5", "-1.5", "1.5", "NaN", "NaN", "2.0", "-0.0"]);
+}
+
+#[test]
+fn fcmpl_and_fcmpg_with_nan_and_freturn() {
+    assert_prints("FloatComparisons", &["unordered", "equal", "not less", "not greater", "1.5"]);
+}
+
+#[test]
+fn double_constants_arithmetic_and_special_values() {
+    assert_prints("Doubles", &[
+        "0.0", "1.0", "0.3333333333333333", "0.30000000000000004", "1.0E10", "1.0E-5", "0.001", "1234567.0", "1.2345678E7",
+        "14.285714285714286", "-0.3333333333333333", "NaN", "-Infinity", "-0.0", "1.7976931348623157E308", "4.9E-324",
+    ]);
+}
+
+#[test]
+fn drem() {
+    assert_prints("DoubleRemainders", &["1.5", "-1.5", "0.0", "NaN", "NaN", "1.0"]);
+}
+
+#[test]
+fn dcmpl_and_dcmpg_with_nan() {
+    assert_prints("DoubleComparisons", &["unordered", "not less", "not greater"]);
+}
+
+#[test]
+fn arguments_return_values_fields_and_array_elements() {
+    assert_prints("DoubleValues", &["7.5", "0.3333333333333333", "0.75", "2.5"]);
+}
@@ -0,0 +1,57 @@
+use std::fs;
+use std::path::Path;
+use rust_jvm::class_file::{self, ClassFile, attribute::Attribute, code_attribute::CodeAttribute, instruction::{self, Insn}};
+use rust_jvm::util::file;
+
+fn load(name: &str) -> ClassFile {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(name);
+    class_file::parse_file(&mut file::File::new(fs::read(path).unwrap())).unwrap()
+}
+
+fn code_of(class_file: &ClassFile, method_name: &str) -> CodeAttribute {
+    let method = class_file.get_methods().iter()
+        .find(|method| class_file.get_utf8(method.get_name_index() as usize).unwrap() == method_name)
+        .unwrap();
+    for attribute_info in method.get_attributes() {
+        if let Attribute::Code(code_attribute) = attribute_info.to_attribute(class_file).unwrap() {
+            return code_attribute;
+        }
+    }
+    panic!("{} has no Code attribute", method_name);
+}
+
+#[test]
+fn switches_skip_padding() {
+    let class_file = load("java/test/Instructions.class");
+    let instructions = code_of(&class_file, "tableSwitch").get_instructions().unwrap();
+    assert_eq!(instructions[1], (1, Insn::Tableswitch { default: 36, low: 1, high: 3, offsets: vec![27, 30, 33] }));
+    assert_eq!(instructions[1].1.get_branch_targets(1), vec![28, 31, 34, 37]);
+    assert_eq!(instructions[2].0, 28);
+
+    let instructions = code_of(&class_file, "lookupSwitch").get_instructions().unwrap();
+    match &instructions[1].1 {
+        Insn::Lookupswitch { pairs, .. } => {
+            assert_eq!(pairs.iter().map(|(key, _offset)| *key).collect::<Vec<i32>>(), vec![-100, 7, 100000]);
+        }
+        insn => panic!("expected lookupswitch, got {:?}", insn),
+    }
+}
+
+#[test]
+fn wide_and_invoke_operands() {
+    let class_file = load("java/test/Instructions.class");
+    let instructions = code_of(&class_file, "wide").get_instructions().unwrap();
+    assert!(instructions.contains(&(64, Insn::Wide(Box::new(Insn::Iinc { index: 15, constant: 1000 })))));
+    assert!(instructions.contains(&(140, Insn::Invokedynamic(40))));
+    assert!(instructions.contains(&(154, Insn::Invokeinterface { index: 44, count: 1 })));
+}
+
+#[test]
+fn local_operands_are_one_byte() {
+    // iload 4, istore 5, return
+    let instructions = instruction::parse_code(&[21, 4, 54, 5, 177]).unwrap();
+    assert_eq!(instructions, vec![(0, Insn::Iload(4)), (2, Insn::Istore(5)), (4, Insn::Return)]);
+    assert_eq!(instructions[0].1.get_local_index(), Some(4));
+    assert!(instruction::parse_code(&[203]).is_err());
+    assert!(instruction::parse_code(&[196, 16, 0, 1]).is_err());
+}
@@ -0,0 +1,168 @@
+mod common;
+
+use std::process::Output;
+use common::printed;
+
+/// The operand pairs of the arithmetic and bitwise instructions, in order.
+const PAIRS: &[&str] = &[
+    "7, 2", "-7, 2", "7, -2", "-7, -2", "0, 5", "MAX_VALUE, 1", "MIN_VALUE, -1", "MIN_VALUE, MIN_VALUE",
+    "0x12345678, 0xf0f0f0f0",
+];
+
+/// The operands of ineg and of the branches comparing with zero, in order.
+const VALUES: &[&str] = &["MIN_VALUE", "-1", "0", "1", "MAX_VALUE"];
+
+/// The values and distances of the shifts, in order.
+const SHIFTS: &[&str] = &["-17, 0", "-17, 1", "-17, 4", "-17, 31", "-17, 32", "-17, 33", "-17, -1"];
+
+/// The constants pushed with sipush, in order.
+const SHORTS: &[&str] = &["128", "-129", "32767", "-32768"];
+
+/// The local variables incremented with iinc, in order.
+const INCREMENTS: &[&str] = &["MAX_VALUE + 1", "-128", "1000"];
+
+/// The operand pairs of the branches comparing two ints, in order.
+const COMPARED: &[&str] = &[
+    "7, 2", "-7, 2", "7, -2", "-7, -2", "0, 5", "MAX_VALUE, 1", "MIN_VALUE, -1", "MIN_VALUE, MIN_VALUE",
+    "0x12345678, 0xf0f0f0f0", "3, 3",
+];
+
+/// The results of the arithmetic instructions for `PAIRS`.
+const ARITHMETIC: &[(&str, &[&str])] = &[
+    ("iadd", &["9", "-5", "5", "-9", "5", "-2147483648", "2147483647", "0", "52774760"]),
+    ("isub", &["5", "-9", "9", "-5", "-5", "2147483646", "-2147483647", "0", "558065032"]),
+    ("imul", &["14", "-14", "-14", "14", "0", "2147483647", "-2147483648", "0", "-1298034560"]),
+    ("idiv", &["3", "-3", "-3", "3", "0", "2147483647", "-2147483648", "1", "-1"]),
+    ("irem", &["1", "-1", "1", "-1", "0", "0", "0", "0", "52774760"]),
+];
+
+/// The results of the bitwise instructions for `PAIRS`.
+const BITWISE: &[(&str, &[&str])] = &[
+    ("iand", &["2", "0", "6", "-8", "0", "1", "-2147483648", "-2147483648", "271601776"]),
+    ("ior", &["7", "-5", "-1", "-1", "5", "2147483647", "-1", "-2147483648", "-218827016"]),
+    ("ixor", &["5", "-5", "-7", "7", "5", "2147483646", "2147483647", "0", "-490428792"]),
+];
+
+/// The results of ineg for `VALUES`.
+const NEGATION: &[(&str, &[&str])] = &[
+    ("ineg", &["-2147483648", "1", "0", "-1", "-2147483647"]),
+];
+
+/// The results of the shifts for `SHIFTS`.
+const SHIFTING: &[(&str, &[&str])] = &[
+    ("ishl", &["-17", "-34", "-272", "-2147483648", "-17", "-34", "-2147483648"]),
+    ("ishr", &["-17", "-9", "-2", "-1", "-17", "-9", "-1"]),
+    ("iushr", &["-17", "2147483639", "268435454", "1", "-17", "2147483639", "1"]),
+];
+
+/// The values sipush pushed for `SHORTS`.
+const PUSHING: &[(&str, &[&str])] = &[
+    ("sipush", &["128", "-129", "32767", "-32768"]),
+];
+
+/// The incremented variables for `INCREMENTS`.
+const INCREMENTING: &[(&str, &[&str])] = &[
+    ("iinc", &["-2147483648", "2147483520", "-2147482776"]),
+];
+
+/// The results of the branches comparing with zero for `VALUES`: 1 if the branch is taken.
+const ZERO_BRANCHES: &[(&str, &[&str])] = &[
+    ("ifeq", &["0", "0", "1", "0", "0"]),
+    ("ifne", &["1", "1", "0", "1", "1"]),
+    ("iflt", &["1", "1", "0", "0", "0"]),
+    ("ifge", &["0", "0", "1", "1", "1"]),
+    ("ifgt", &["0", "0", "0", "1", "1"]),
+    ("ifle", &["1", "1", "1", "0", "0"]),
+];
+
+/// The results of the branches comparing two ints for `COMPARED`: 1 if the branch is taken.
+const COMPARISON_BRANCHES: &[(&str, &[&str])] = &[
+    ("if_icmpeq", &["0", "0", "0", "0", "0", "0", "0", "1", "0", "1"]),
+    ("if_icmpne", &["1", "1", "1", "1", "1", "1", "1", "0", "1", "0"]),
+    ("if_icmplt", &["0", "1", "0", "1", "1", "0", "1", "0", "0", "0"]),
+    ("if_icmpge", &["1", "0", "1", "0", "0", "1", "0", "1", "1", "1"]),
+    ("if_icmpgt", &["1", "0", "1", "0", "0", "1", "0", "0", "1", "0"]),
+    ("if_icmple", &["0", "1", "0", "1", "1", "0", "1", "1", "0", "1"]),
+];
+
+fn run(main_class: &str) -> Output {
+    common::run("integers", &[main_class])
+}
+
+fn computed() -> Vec<String> {
+    let output = run("Integers");
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+    printed(&output)
+}
+
+#[test]
+fn arithmetic() {
+    common::assert_sections(&computed(), PAIRS, ARITHMETIC);
+}
+
+#[test]
+fn bitwise_operations() {
+    common::assert_sections(&computed(), PAIRS, BITWISE);
+}
+
+#[test]
+fn negation() {
+    common::assert_sections(&computed(), VALUES, NEGATION);
+}
+
+#[test]
+fn shifts_use_five_bits_of_the_distance() {
+    common::assert_sections(&computed(), SHIFTS, SHIFTING);
+}
+
+#[test]
+fn sipush() {
+    common::assert_sections(&computed(), SHORTS, PUSHING);
+}
+
+#[test]
+fn iinc_wraps_around() {
+    common::assert_sections(&computed(), INCREMENTS, INCREMENTING);
+}
+
+#[test]
+fn branches_comparing_with_zero() {
+    common::assert_sections(&computed(), VALUES, ZERO_BRANCHES);
+}
+
+#[test]
+fn branches_comparing_two_ints() {
+    common::assert_sections(&computed(), COMPARED, COMPARISON_BRANCHES);
+}
+
+#[test]
+fn prints_nothing_else() {
+    let tables = [
+        (PAIRS, ARITHMETIC),
+        (PAIRS, BITWISE),
+        (VALUES, NEGATION),
+        (SHIFTS, SHIFTING),
+        (SHORTS, PUSHING),
+        (INCREMENTS, INCREMENTING),
+        (VALUES, ZERO_BRANCHES),
+        (COMPARED, COMPARISON_BRANCHES),
+    ];
+    let lines: usize = tables.iter().map(|(operands, instructions)| instructions.len() * (1 + operands.len())).sum();
+    assert_eq!(computed().len(), lines);
+}
+
+fn assert_divides_by_zero(main_class: &str) {
+    let output = run(main_class);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Exception in thread \"main\" java.lang.ArithmeticException: / by zero"));
+}
+
+#[test]
+fn throws_on_division_by_zero() {
+    assert_divides_by_zero("DivideByZero");
+}
+
+#[test]
+fn throws_on_remainder_by_zero() {
+    assert_divides_by_zero("RemainderByZero");
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use std::path::Path;
+use std::process::{Command, Output};
+use common::printed;
+use rust_jvm::{Config, Mode};
+
+fn parse(args: &[&str]) -> Result<Config, String> {
+    let args: Vec<String> = ["rust_jvm"].iter().chain(args).map(|arg| arg.to_string()).collect();
+    Config::new(&args)
+}
+
+fn run(args: &[&str]) -> Output {
+    common::run("launcher", args)
+}
+
+#[test]
+fn parses_java_options() {
+    let config = parse(&["-Dfoo=bar", "-Dempty", "-Xss512k", "-Xmx2g", "-Xms64m", "-verbose:class", "-XX:+TraceBytecodes", "-cp", "lib", "com.example.Main", "-Dx", "arg"]).unwrap();
+    assert!(matches!(config.mode, Mode::Run));
+    assert_eq!(config.filename, "com.example.Main");
+    assert_eq!(config.class_path, vec![Path::new("lib")]);
+    assert_eq!(config.system_properties.get("foo").map(String::as_str), Some("bar"));
+    assert_eq!(config.system_properties.get("empty").map(String::as_str), Some(""));
+    assert_eq!(config.thread_stack_size, Some(512 * 1024));
+    assert_eq!(config.max_heap_size, Some(2 << 30));
+    assert_eq!(config.initial_heap_size, Some(64 << 20));
+    assert!(config.verbose_class);
+    assert!(config.trace_bytecodes);
+    // Everything after the main class belongs to the program.
+    assert_eq!(config.args, vec!["-Dx", "arg"]);
+}
+
+#[test]
+fn jar_replaces_the_class_path() {
+    let config = parse(&["-cp", "ignored", "-jar", "app.jar", "-version"]).unwrap();
+    assert!(matches!(config.mode, Mode::RunJar));
+    assert_eq!(config.class_path, vec![Path::new("app.jar")]);
+    assert_eq!(config.args, vec!["-version"]);
+}
+
+#[test]
+fn parses_version() {
+    assert!(matches!(parse(&["-version"]).unwrap().mode, Mode::Version));
+}
+
+#[test]
+fn initial_heap_size_bounds_the_maximum() {
+    assert_eq!(parse(&["-Xms64m", "Main"]).unwrap().max_heap_size, None);
+    assert_eq!(parse(&["-Xms1g", "Main"]).unwrap().max_heap_size, Some(1 << 30));
+    assert_eq!(parse(&["-Xmx2g", "-Xms1g", "Main"]).unwrap().max_heap_size, Some(2 << 30));
+    assert_eq!(parse(&["-Xms2g", "-Xmx1g", "Main"]).err().unwrap(), "Initial heap size set to a larger value than the maximum heap size");
+}
+
+#[test]
+fn rejects_invalid_options() {
+    assert_eq!(parse(&["-Xss", "Main"]).err().unwrap(), "Invalid thread stack size: -Xss");
+    assert_eq!(parse(&["-Xmx1t", "Main"]).err().unwrap(), "Invalid maximum heap size: -Xmx1t");
+    assert_eq!(parse(&["-server", "Main"]).err().unwrap(), "Unrecognized option: -server");
+    assert_eq!(parse(&["-cp", "lib"]).err().unwrap(), "missing main class");
+}
+
+#[test]
+fn exit_status() {
+    let output = run(&["com.example.Exit", "ignored"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(printed(&output), vec!["7"]);
+}
+
+#[test]
+fn missing_main_class() {
+    let output = run(&["com.example.Missing"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Could not find or load main class com.example.Missing"));
+}
+
+/// The depth Recursion reached, as it prints the depth of each call before recursing.
+fn recursion_depth(options: &[&str]) -> usize {
+    let args: Vec<&str> = options.iter().copied().chain(["com.example.Recursion"]).collect();
+    let output = run(&args);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Exception in thread \"main\" java.lang.StackOverflowError"));
+    printed(&output).last().map_or(0, |line| line.parse().unwrap())
+}
+
+#[test]
+fn stack_overflow_is_uncaught() {
+    let depth = recursion_depth(&[]);
+    assert!(depth > 1000, "{} frames", depth);
+}
+
+#[test]
+fn stack_depth_is_limited_by_xss() {
+    let depth = recursion_depth(&["-Xss64k"]);
+    assert!(depth < recursion_depth(&[]) / 8, "{} frames", depth);
+}
+
+#[test]
+fn thread_stack_size_is_capped() {
+    // The Java thread stack is capped at 4 MiB, so that the native stack stays bounded.
+    let capped_depth = recursion_depth(&["-Xss4m"]);
+    assert_eq!(recursion_depth(&["-Xss1g"]), capped_depth);
+    assert!(capped_depth > recursion_depth(&[]) * 3, "{} frames", capped_depth);
+}
+
+#[test]
+fn does_not_trace_by_default() {
+    let output = run(&["com.example.Exit"]);
+    assert_eq!(printed(&output), vec!["7"]);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Instruction: "));
+}
+
+#[test]
+fn traces_bytecodes_to_stderr() {
+    let output = run(&["-XX:+TraceBytecodes", "com.example.Exit"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "7\n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let traced: Vec<&str> = stderr.lines().collect();
+    assert_eq!(traced[..2], ["Instruction: get_static(178)", "Instruction: bipush(16)"]);
+}
+
+#[test]
+fn version() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_jvm")).arg("-version").output().unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with(&format!("rust_jvm version \"{}\"", env!("CARGO_PKG_VERSION"))));
+}
+
+#[test]
+fn passes_program_arguments_to_main() {
+    let output = run(&["com.example.Args", "first", "second argument"]);
+    assert_eq!(printed(&output), vec!["2", "first", "second argument"]);
+    // Args finally reads one element past the end.
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .starts_with("Exception in thread \"main\" java.lang.ArrayIndexOutOfBoundsException: Index 2 out of bounds for length 2"));
+}
+
+#[test]
+fn passes_no_program_arguments() {
+    let output = run(&["com.example.Args"]);
+    assert_eq!(printed(&output), vec!["0"]);
+}
+
+#[test]
+fn main_must_be_public_static_and_take_strings() {
+    let output = run(&["com.example.NoMain"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(printed(&output).is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Main method not found in class com.example.NoMain"));
+}
+
+#[test]
+fn heap_is_limited_by_xmx() {
+    let output = run(&["-Xmx1k", "com.example.Args", "fits"]);
+    assert_eq!(printed(&output), vec!["1", "fits"]);
+}
+
+#[test]
+fn runs_out_of_heap_space() {
+    let output = run(&["-Xmx64", "com.example.Args", "does not fit in 64 bytes with the array"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(printed(&output).is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("java.lang.OutOfMemoryError: Java heap space"));
+}
+
+#[test]
+fn system_properties_from_the_command_line() {
+    let output = run(&["-Dgreeting=hello world", "-Dempty", "com.example.SystemProperties"]);
+    assert_eq!(printed(&output), vec!["hello world", "", "null", "default"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Exception in thread \"main\" java.lang.IllegalArgumentException: key can't be empty"));
+}
+
+#[test]
+fn vm_failures_are_no_exceptions() {
+    // String concatenation compiles to invokedynamic, which the interpreter does not implement.
+    let output = run(&["com.example.Concat"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(printed(&output), vec!["1"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("Exception in thread"), "{}", stderr);
+    assert!(stderr.contains("VM failure in thread \"main\": internal error: The opcode 186 is not implemented"), "{}", stderr);
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use std::process::Output;
+
+fn run(main_class: &str) -> Output {
+    common::run("longs", &[main_class])
+}
+
+fn assert_prints(main_class: &str, expected: &[&str]) {
+    common::assert_prints("longs", main_class, expected);
+}
+
+fn assert_divides_by_zero(main_class: &str) {
+    let output = run(main_class);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Exception in thread \"main\" java.lang.ArithmeticException: / by zero"));
+}
+
+#[test]
+fn constants_and_arithmetic() {
+    assert_prints("Longs", &["0", "1", "10000000000", "-2000000000", "15000000000", "714285714", "-714285714", "2", "-2", "-5000000000"]);
+}
+
+#[test]
+fn overflow_wraps_around() {
+    assert_prints("LongOverflow", &["-9223372036854775808", "9223372036854775807", "-2", "-9223372036854775808", "0", "-9223372036854775808"]);
+}
+
+#[test]
+fn shifts_use_six_bits_of_the_distance() {
+    assert_prints("LongShifts", &["1099511627776", "1", "-1", "15", "-1", "0"]);
+}
+
+#[test]
+fn bitwise_operations() {
+    assert_prints("LongBitwise", &["61952", "5000000015", "-5000000001"]);
+}
+
+#[test]
+fn lcmp() {
+    assert_prints("LongComparison", &["equal", "different"]);
+}
+
+#[test]
+fn arguments_return_values_fields_and_array_elements() {
+    assert_prints("LongValues", &["-9223372031854775809", "2", "5000000000", "5000000000"]);
+}
+
+#[test]
+fn throws_on_division_by_zero() {
+    assert_divides_by_zero("DivideByZero");
+}
+
+#[test]
+fn throws_on_remainder_by_zero() {
+    assert_divides_by_zero("RemainderByZero");
+}
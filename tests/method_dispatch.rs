@@ -0,0 +1,13 @@
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn synchronized_and_native_static_methods() {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("java/test/Dispatch.class");
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_jvm")).arg(fixture).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stdout.lines().any(|line| line == "42"), "synchronized method did not run: {}", stdout);
+    assert!(!output.status.success());
+    assert!(stderr.contains("java.lang.UnsatisfiedLinkError: Dispatch.answer()I"), "unexpected error: {}", stderr);
+}
@@ -0,0 +1,75 @@
+use std::fs;
+use rust_jvm::class_file::{self, mutf8, parse_error::ParseErrorKind};
+use rust_jvm::util::file;
+
+fn invalid_position(bytes: &[u8]) -> usize {
+    match mutf8::decode_utf16(bytes).unwrap_err().get_kind() {
+        ParseErrorKind::InvalidModifiedUtf8 { position } => *position,
+        kind => panic!("{:?} is no modified UTF-8 error", kind),
+    }
+}
+
+#[test]
+fn decodes_nul_from_two_bytes() {
+    assert_eq!(mutf8::decode(&[0x61, 0xC0, 0x80, 0x62]).unwrap(), "a\0b");
+    assert_eq!(mutf8::decode_utf16(&[0xC0, 0x80]).unwrap(), vec![0]);
+}
+
+#[test]
+fn decodes_two_and_three_byte_characters() {
+    assert_eq!(mutf8::decode(&[0xC3, 0xA9]).unwrap(), "\u{e9}");
+    assert_eq!(mutf8::decode(&[0xE2, 0x82, 0xAC]).unwrap(), "\u{20ac}");
+}
+
+#[test]
+fn decodes_supplementary_characters_from_surrogate_pairs() {
+    let grin = [0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80];
+    assert_eq!(mutf8::decode_utf16(&grin).unwrap(), vec![0xD83D, 0xDE00]);
+    assert_eq!(mutf8::decode(&grin).unwrap(), "\u{1f600}");
+    // Java strings may hold unpaired surrogates, Rust strings can't.
+    assert_eq!(mutf8::decode_utf16(&grin[..3]).unwrap(), vec![0xD83D]);
+    assert_eq!(mutf8::decode(&grin[..3]).unwrap(), "\u{fffd}");
+}
+
+#[test]
+fn encodes_nul_and_supplementary_characters_as_java_does() {
+    assert_eq!(mutf8::encode("a\0b"), vec![0x61, 0xC0, 0x80, 0x62]);
+    assert_eq!(mutf8::encode("\u{e9}\u{20ac}"), vec![0xC3, 0xA9, 0xE2, 0x82, 0xAC]);
+    assert_eq!(mutf8::encode("\u{1f600}"), vec![0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80]);
+    assert_eq!(mutf8::encode_utf16(&[0xDE00]), vec![0xED, 0xB8, 0x80]);
+}
+
+#[test]
+fn round_trips() {
+    for string in ["", "\0", "a\0b", "caf\u{e9} \u{20ac}", "\u{1f600}", "\u{10ffff}\0\u{10000}", "\u{7f}\u{80}\u{7ff}\u{800}\u{ffff}"] {
+        assert_eq!(mutf8::decode(&mutf8::encode(string)).unwrap(), string);
+    }
+    // A surrogate pair, and unpaired surrogates, which only survive as UTF-16.
+    for units in [&[0xD83D, 0xDE00][..], &[0xD83D], &[0xDE00, 0x61, 0xD800], &[0, 0xD83D, 0]] {
+        assert_eq!(mutf8::decode_utf16(&mutf8::encode_utf16(units)).unwrap(), units);
+    }
+}
+
+#[test]
+fn reports_the_position_of_invalid_bytes() {
+    // NUL is never encoded as one byte.
+    assert_eq!(invalid_position(&[0x61, 0x00]), 1);
+    // Four-byte forms of standard UTF-8 are not used.
+    assert_eq!(invalid_position(&[0x61, 0x62, 0xF0, 0x9F, 0x98, 0x80]), 2);
+    // A stray continuation byte.
+    assert_eq!(invalid_position(&[0x80]), 0);
+    // A missing or malformed continuation byte is reported where it should be.
+    assert_eq!(invalid_position(&[0x61, 0xC3]), 2);
+    assert_eq!(invalid_position(&[0xE2, 0x82, 0x41]), 2);
+}
+
+#[test]
+fn decodes_constants_compiled_by_javac() {
+    let bytes = fs::read(format!("{}/java/test/ModifiedUtf8.class", env!("CARGO_MANIFEST_DIR"))).unwrap();
+    let class_file = class_file::parse_file(&mut file::File::new(bytes)).unwrap();
+    assert_eq!(class_file.get_utf8(13).unwrap(), "nul:\0 caf\u{e9} grin:\u{1f600}");
+    let units = class_file.get_utf16(13).unwrap();
+    assert_eq!(units.len(), 18);
+    assert_eq!(units[4], 0);
+    assert_eq!(&units[16..], &[0xD83D, 0xDE00]);
+}
@@ -0,0 +1,27 @@
+use rust_jvm::util::number_format::{double_to_string, float_to_string};
+
+#[test]
+fn formats_doubles_like_java() {
+    assert_eq!(double_to_string(1.0), "1.0");
+    assert_eq!(double_to_string(100.0), "100.0");
+    assert_eq!(double_to_string(0.001), "0.001");
+    assert_eq!(double_to_string(1.0e7), "1.0E7");
+    assert_eq!(double_to_string(-1.0e-5), "-1.0E-5");
+    assert_eq!(double_to_string(0.1 + 0.2), "0.30000000000000004");
+    assert_eq!(double_to_string(f64::MAX), "1.7976931348623157E308");
+    // A single digit would identify these, but Java prints the closest two digits.
+    assert_eq!(double_to_string(f64::from_bits(1)), "4.9E-324");
+    assert_eq!(double_to_string(2.0e-323), "2.0E-323");
+    assert_eq!(double_to_string(-0.0), "-0.0");
+    assert_eq!(double_to_string(f64::NAN), "NaN");
+    assert_eq!(double_to_string(f64::NEG_INFINITY), "-Infinity");
+}
+
+#[test]
+fn formats_floats_like_java() {
+    assert_eq!(float_to_string(1.0 / 3.0), "0.33333334");
+    assert_eq!(float_to_string(1.0e10), "1.0E10");
+    assert_eq!(float_to_string(f32::MAX), "3.4028235E38");
+    assert_eq!(float_to_string(f32::from_bits(1)), "1.4E-45");
+    assert_eq!(float_to_string(f32::INFINITY), "Infinity");
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use std::process::Output;
+use common::printed;
+
+fn run(main_class: &str) -> Output {
+    common::run("objects", &[main_class])
+}
+
+fn assert_prints(main_class: &str, expected: &[&str]) {
+    common::assert_prints("objects", main_class, expected);
+}
+
+#[test]
+fn fields_and_private_methods() {
+    assert_prints("FieldsAndMethods", &["3", "25"]);
+}
+
+#[test]
+fn overrides_call_super_and_inherit_fields() {
+    assert_prints("Inheritance", &["14", "2"]);
+}
+
+#[test]
+fn interface_and_default_methods() {
+    assert_prints("Interfaces", &["25", "4"]);
+}
+
+#[test]
+fn recursion_along_a_linked_list_ending_in_null() {
+    assert_prints("Lists", &["6"]);
+}
+
+#[test]
+fn reference_comparison_null_fields_and_interned_literals() {
+    assert_prints("References", &["1", "0", "null", "origin", "1"]);
+}
+
+#[test]
+fn post_increment_of_a_field() {
+    // Post-increment of a field uses dup_x1, a discarded result pop.
+    assert_prints("Increments", &["1", "2", "3"]);
+}
+
+#[test]
+fn reading_a_field_of_null_throws() {
+    let output = run("NullFields");
+    assert!(printed(&output).is_empty());
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Exception in thread \"main\" java.lang.NullPointerException: Cannot read field \"x\""));
+}
+
+#[test]
+fn println_formats_by_the_descriptor() {
+    let output = run("Printing");
+    assert_eq!(printed(&output), vec!["", "true", "false", "a", "hi", "text", "null", "null"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("VM failure in thread \"main\": internal error: PrintStream.println of a Point is not implemented"), "{}", stderr);
+}
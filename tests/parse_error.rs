@@ -0,0 +1,48 @@
+use std::fs;
+use std::path::Path;
+use rust_jvm::class_file::{self, parse_error::{ParseError, ParseErrorKind}};
+use rust_jvm::util::file;
+
+fn read(name: &str) -> Vec<u8> {
+    fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join(name)).unwrap()
+}
+
+fn parse(bytes: Vec<u8>) -> ParseError {
+    class_file::parse_file(&mut file::File::new(bytes)).unwrap_err()
+}
+
+#[test]
+fn reports_truncated_class_files() {
+    let error = parse(read("java/main/HelloWorld.class")[..6].to_vec());
+    assert_eq!(error.get_kind(), &ParseErrorKind::UnexpectedEof { needed: 2, available: 0 });
+    assert_eq!(error.get_offset(), Some(6));
+    assert_eq!(error.get_context(), &vec![String::from("class header")]);
+
+    let bytes = read("java/main/HelloWorld.class");
+    let error = parse(bytes[..bytes.len() - 1].to_vec());
+    assert!(matches!(error.get_kind(), ParseErrorKind::UnexpectedEof { .. }), "{}", error);
+}
+
+#[test]
+fn reports_unknown_constant_tags() {
+    let mut bytes = read("java/main/HelloWorld.class");
+    // The first constant pool entry follows magic, version and constant_pool_count.
+    bytes[10] = 2;
+    let error = parse(bytes);
+    assert_eq!(error.get_kind(), &ParseErrorKind::UnknownConstantTag(2));
+    assert_eq!(error.get_offset(), Some(10));
+    assert_eq!(error.get_context(), &vec![String::from("constant pool entry #1")]);
+}
+
+#[test]
+fn renders_context_kind_and_offset() {
+    let error = parse(read("java/main/HelloWorld.class")[..6].to_vec());
+    assert_eq!(error.to_string(), "class header: unexpected end of data (needed 2 bytes, 0 available) at offset 0x6");
+
+    let mut bytes = read("java/main/HelloWorld.class");
+    bytes[10] = 2;
+    assert_eq!(parse(bytes).to_string(), "constant pool entry #1: unknown constant tag 2 at offset 0xa");
+
+    let error = ParseError::new(ParseErrorKind::InvalidConstantIndex(42)).within("field").within("class");
+    assert_eq!(error.to_string(), "class field: invalid constant pool index #42");
+}
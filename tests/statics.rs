@@ -0,0 +1,53 @@
+mod common;
+
+use std::process::Output;
+use common::printed;
+
+fn run(main_class: &str) -> Output {
+    common::run("statics", &[main_class])
+}
+
+fn assert_prints(main_class: &str, expected: &[&str]) {
+    common::assert_prints("statics", main_class, expected);
+}
+
+#[test]
+fn default_values_and_static_field_access() {
+    // The main class is initialized before main runs.
+    assert_prints("Statics", &["Statics", "0", "0", "0.0", "null", "8", "hello"]);
+}
+
+#[test]
+fn constant_value_attributes() {
+    assert_prints("ConstantValues", &["42", "1099511627776", "0.5", "0.3333333333333333", "constant"]);
+}
+
+#[test]
+fn initializes_the_superclass_first_and_each_class_once() {
+    // Only the class declaring the field is initialized, so OtherChild is not.
+    assert_prints("SuperclassFirst", &["Parent", "Child", "14", "14", "7"]);
+}
+
+#[test]
+fn new_and_invokestatic_initialize_the_class() {
+    assert_prints("InitializationTriggers", &["Lazy", "Util", "8"]);
+}
+
+#[test]
+fn initializes_only_superinterfaces_with_default_methods() {
+    assert_prints("DefaultMethods", &["WithDefault", "Impl"]);
+}
+
+#[test]
+fn circular_initialization() {
+    assert_prints("CircularInitialization", &["11", "10"]);
+}
+
+#[test]
+fn exceptions_in_initializers() {
+    let output = run("FailingInitializer");
+    assert!(printed(&output).is_empty());
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains(
+        "Exception in thread \"main\" java.lang.ExceptionInInitializerError\nCaused by: java.lang.NullPointerException: Cannot read field \"field\""));
+}
@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::Path;
+use rust_jvm::class_file;
+use rust_jvm::util::file;
+use rust_jvm::verifier::{self, verify_error::{VerifyError, VerifyErrorKind}};
+
+const FIXTURES: &[&str] = &[
+    "java/main/HelloWorld.class",
+    "java/test/Annotated.class",
+    "java/test/Attributes.class",
+    "java/test/Attributes$1.class",
+    "java/test/Attributes$Point.class",
+    "java/test/Attributes$Sub.class",
+    "java/test/Dispatch.class",
+    "java/test/Instructions.class",
+    "java/test/Instructions$Shape.class",
+    "java/test/Invis.class",
+    "java/test/StackMaps.class",
+    "java/test/Tag.class",
+    "java/test/WideConstants.class",
+    "java/test/exceptions/CatchThrown.class",
+    "java/test/exceptions/Finally.class",
+];
+
+/// The code of `HelloWorld.fac(I)I`; the `iload_0` at pc 7 is the first byte of the slice.
+const FAC_TAIL: &[u8] = &[0x1a, 0x1a, 0x04, 0x64, 0xb8, 0x00, 0x0d, 0x68, 0xac];
+
+fn read(name: &str) -> Vec<u8> {
+    fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join(name)).unwrap()
+}
+
+fn verify(bytes: Vec<u8>) -> Result<(), VerifyError> {
+    verifier::verify_class(&class_file::parse_file(&mut file::File::new(bytes)).unwrap())
+}
+
+fn set_major_version(bytes: &mut [u8], major_version: u16) {
+    bytes[6..8].copy_from_slice(&major_version.to_be_bytes());
+}
+
+/// HelloWorld with `fac` loading its int argument with `fload_0`.
+fn float_load_in_fac() -> Vec<u8> {
+    let mut bytes = read("java/main/HelloWorld.class");
+    let position = bytes.windows(FAC_TAIL.len()).position(|window| window == FAC_TAIL).unwrap();
+    bytes[position] = 0x22;
+    bytes
+}
+
+#[test]
+fn fixtures_pass_type_checking() {
+    for fixture in FIXTURES {
+        if let Err(error) = verify(read(fixture)) {
+            panic!("{}: {}", fixture, error);
+        }
+    }
+}
+
+#[test]
+fn fixtures_pass_type_inference() {
+    for fixture in FIXTURES {
+        let mut bytes = read(fixture);
+        set_major_version(&mut bytes, 49);
+        if let Err(error) = verify(bytes) {
+            panic!("{}: {}", fixture, error);
+        }
+    }
+}
+
+#[test]
+fn type_checker_reports_pc_and_types() {
+    let error = verify(float_load_in_fac()).unwrap_err();
+    assert_eq!(error.get_class_name(), "HelloWorld");
+    assert_eq!(error.get_method(), "fac(I)I");
+    assert_eq!(error.get_pc(), Some(7));
+    assert_eq!(error.get_kind(), &VerifyErrorKind::TypeMismatch {
+        location: "local variable 0".to_string(),
+        expected: "float".to_string(),
+        actual: "int".to_string(),
+    });
+    assert_eq!(error.to_string(), "java.lang.VerifyError: HelloWorld.fac(I)I at pc 7: bad type in local variable 0: expected float, found int");
+}
+
+#[test]
+fn type_inference_reports_pc_and_types() {
+    let mut bytes = float_load_in_fac();
+    set_major_version(&mut bytes, 49);
+    let error = verify(bytes).unwrap_err();
+    assert_eq!(error.get_pc(), Some(7));
+    assert!(matches!(error.get_kind(), VerifyErrorKind::TypeMismatch { expected, actual, .. } if expected == "float" && actual == "int"));
+}
+
+#[test]
+fn branch_target_without_stack_map_frame() {
+    let mut bytes = read("java/main/HelloWorld.class");
+    // `if_icmpne 7` at pc 2 of fac, retargeted to the `iload_0` at pc 8.
+    let position = bytes.windows(FAC_TAIL.len()).position(|window| window == FAC_TAIL).unwrap();
+    assert_eq!(bytes[position - 5..position - 2], [0xa0, 0x00, 0x05]);
+    bytes[position - 3] = 0x06;
+    let error = verify(bytes.clone()).unwrap_err();
+    assert_eq!((error.get_pc(), error.get_kind()), (Some(2), &VerifyErrorKind::MissingStackMapFrame));
+
+    // Without stack maps the branch is followed: the path skipping the first `iload_0` is one value short.
+    set_major_version(&mut bytes, 49);
+    let error = verify(bytes).unwrap_err();
+    assert_eq!((error.get_pc(), error.get_kind()), (Some(14), &VerifyErrorKind::StackUnderflow));
+}
+
+#[test]
+fn stack_overflow_is_rejected() {
+    let mut bytes = read("java/main/HelloWorld.class");
+    // Lower max_stack of fac from 3 to 2; the code attribute starts 8 bytes before its code.
+    let position = bytes.windows(FAC_TAIL.len()).position(|window| window == FAC_TAIL).unwrap();
+    let code_start = position - 7;
+    assert_eq!(bytes[code_start - 8..code_start - 6], [0x00, 0x03]);
+    bytes[code_start - 7] = 0x02;
+    let error = verify(bytes).unwrap_err();
+    assert_eq!((error.get_pc(), error.get_kind()), (Some(9), &VerifyErrorKind::StackOverflow { max_stack: 2 }));
+}
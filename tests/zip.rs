@@ -0,0 +1,63 @@
+use std::fs;
+use std::path::Path;
+use rust_jvm::util::{inflate, zip::{self, ZipArchive, ZipError}};
+
+fn archive() -> ZipArchive {
+    ZipArchive::new(fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("java/test/jar/entries.zip")).unwrap()).unwrap()
+}
+
+fn lines() -> Vec<u8> {
+    (0..2000).map(|i| format!("line {}: the quick brown fox jumps over the lazy dog\n", i)).collect::<String>().into_bytes()
+}
+
+fn bytes() -> Vec<u8> {
+    (0..70000u64).map(|i| ((i * i + 7 * i) % 251) as u8).collect()
+}
+
+#[test]
+fn lists_the_central_directory() {
+    let archive = archive();
+    let names: Vec<&str> = archive.get_entries().iter().map(|entry| entry.get_name()).collect();
+    assert_eq!(names, vec!["dir/", "dir/lines.txt", "dir/stored.bin", "uncompressed.bin", "empty.txt"]);
+    assert!(archive.get_entry("dir/").unwrap().is_directory());
+    let entry = archive.get_entry("dir/lines.txt").unwrap();
+    assert_eq!((entry.get_method(), entry.get_uncompressed_size()), (8, 108890));
+    assert!(archive.get_entry("missing").is_none());
+}
+
+#[test]
+fn reads_stored_and_deflated_entries() {
+    let archive = archive();
+    // Dynamic Huffman blocks.
+    assert_eq!(archive.read("dir/lines.txt").unwrap().unwrap(), lines());
+    assert_eq!(archive.read("dir/stored.bin").unwrap().unwrap(), bytes());
+    // Several stored blocks inside a deflate stream.
+    assert_eq!(archive.read("uncompressed.bin").unwrap().unwrap(), bytes());
+    assert_eq!(archive.read("empty.txt").unwrap().unwrap(), Vec::<u8>::new());
+    assert_eq!(archive.read("missing").unwrap(), None);
+}
+
+#[test]
+fn inflates_fixed_huffman_blocks() {
+    // "abcabcabc" compressed with the fixed code: three literals and a match of length 6.
+    assert_eq!(inflate::inflate(&[0x4b, 0x4c, 0x4a, 0x4e, 0x04, 0x23, 0x00]).unwrap(), b"abcabcabc");
+    assert!(inflate::inflate(&[0x07]).is_err());
+}
+
+#[test]
+fn rejects_corrupt_archives() {
+    assert_eq!(ZipArchive::new(b"not a zip file".to_vec()).err(), Some(ZipError::MissingEndOfCentralDirectory));
+
+    let mut data = fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("java/test/jar/entries.zip")).unwrap();
+    let stored = data.windows(14).position(|window| window == b"dir/stored.bin").unwrap() + 14;
+    data[stored] ^= 0xff;
+    let archive = ZipArchive::new(data).unwrap();
+    assert_eq!(archive.read("dir/stored.bin"), Err(ZipError::CrcMismatch { name: "dir/stored.bin".to_string() }));
+    assert_eq!(archive.read("dir/lines.txt").unwrap().unwrap(), lines());
+}
+
+#[test]
+fn crc32() {
+    assert_eq!(zip::crc32(b"123456789"), 0xcbf43926);
+    assert_eq!(zip::crc32(b""), 0);
+}